@@ -3,20 +3,36 @@ pub mod state;
 
 use iced::{
 	alignment::{Horizontal, Vertical},
-	widget::{canvas, container, row, stack, text, text_input},
-	Element,
+	widget::{canvas, checkbox, column, container, row, stack, text, text_input},
+	Element, Font,
 	Length::{self, Fill},
 };
 pub use message::*;
-use rsap::{expression::parse, widgets::empty_canvas};
+use rsap::{
+	expression::{parse_program, Expression},
+	widgets::{empty_canvas, Plot, ShadedPlot},
+};
 pub use state::*;
 
 pub fn update(state: &mut State, message: Message) {
 	match message {
 		Message::InputChanged(input) => {
-			state.expression = parse(&input).ok();
+			match parse_program(&input) {
+				Ok(program) => {
+					state.expression = Some(Expression::from(program));
+					state.error = None;
+				}
+				Err(err) => {
+					state.expression = None;
+					state.error = Some(err);
+				}
+			}
 			state.input = input;
 		}
+		Message::MarkersToggled(markers) => state.markers = markers,
+		Message::AreaToggled(area) => state.area = area,
+		Message::AreaFromChanged(input) => state.area_from = input,
+		Message::AreaToChanged(input) => state.area_to = input,
 	}
 }
 
@@ -36,16 +52,57 @@ pub fn view(state: &State) -> Element<'_, Message> {
 		});
 	}
 
-	let input_row: Element<_> =
-		container(row!(container(text!("y = ")).padding(5), input,).padding(10))
-			.width(Length::Fill)
-			.height(Length::Fill)
-			.align_x(Horizontal::Right)
-			.align_y(Vertical::Bottom)
-			.into();
+	let markers = checkbox(state.markers)
+		.label("Roots and extrema")
+		.on_toggle(Message::MarkersToggled);
+
+	let area = row!(
+		checkbox(state.area)
+			.label("Area from")
+			.on_toggle(Message::AreaToggled),
+		text_input("a", &state.area_from)
+			.on_input(Message::AreaFromChanged)
+			.width(80),
+		text!("to"),
+		text_input("b", &state.area_to)
+			.on_input(Message::AreaToChanged)
+			.width(80),
+	)
+	.spacing(10)
+	.align_y(Vertical::Center);
+
+	let mut input_column = column!(
+		row!(container(text!("y = ")).padding(5), input,),
+		container(markers).padding(5),
+		container(area).padding(5),
+	);
+
+	// show the input with a caret under the error location
+	if let Some(err) = state.error.as_ref().filter(|_| !state.input.is_empty()) {
+		input_column = input_column.push(
+			text(err.render(&state.input))
+				.font(Font::MONOSPACE)
+				.style(text::danger),
+		);
+	}
+
+	let input_row: Element<_> = container(input_column.padding(10))
+		.width(Length::Fill)
+		.height(Length::Fill)
+		.align_x(Horizontal::Right)
+		.align_y(Vertical::Bottom)
+		.into();
 
 	let plot_canvas: Element<_> = if let Some(expr) = &state.expression {
-		canvas(expr.clone()).width(Fill).height(Fill).into()
+		let plot = Plot::new(expr.clone()).with_markers(state.markers);
+		if let Some((from, to)) = state.area_bounds() {
+			canvas(ShadedPlot::new(plot, from, to))
+				.width(Fill)
+				.height(Fill)
+				.into()
+		} else {
+			canvas(plot).width(Fill).height(Fill).into()
+		}
 	} else {
 		empty_canvas().width(Fill).height(Fill).into()
 	};
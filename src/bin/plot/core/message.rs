@@ -1,4 +1,8 @@
 #[derive(Debug, Clone)]
 pub enum Message {
 	InputChanged(String),
+	MarkersToggled(bool),
+	AreaToggled(bool),
+	AreaFromChanged(String),
+	AreaToChanged(String),
 }
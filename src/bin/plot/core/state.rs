@@ -1,7 +1,25 @@
-use rsap::expression::Expression;
+use rsap::expression::{parse, Expression, ParseError};
 
 #[derive(Default)]
 pub struct State {
 	pub expression: Option<Expression>,
+	pub error: Option<ParseError>,
 	pub input: String,
+	/// mark the roots and the extrema
+	pub markers: bool,
+	/// shade the signed area between the bounds
+	pub area: bool,
+	pub area_from: String,
+	pub area_to: String,
+}
+
+impl State {
+	/// The bounds of the shaded area, if it is shown and the bounds are constant expressions.
+	pub fn area_bounds(&self) -> Option<(f64, f64)> {
+		if !self.area {
+			return None;
+		}
+		let bound = |input: &str| parse(input).ok()?.constant_value();
+		Some((bound(&self.area_from)?, bound(&self.area_to)?))
+	}
 }
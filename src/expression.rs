@@ -1,36 +1,64 @@
 //! Function expression.
 
 pub mod binary;
+pub mod call;
+pub mod compiler;
+pub mod conditional;
 pub mod constant;
+pub mod custom;
+pub mod env;
+pub mod error;
 pub mod factorial;
 pub mod func;
 pub mod func2;
+pub mod integral;
 pub mod lexer;
 pub mod number;
+pub mod operator;
 pub mod parenthesis;
 pub mod parser;
+pub mod program;
+pub mod registry;
+pub mod scalar;
 pub mod traits;
 pub mod unary;
 pub mod variable;
 
 pub use binary::*;
+pub use call::*;
+pub use compiler::*;
+pub use conditional::*;
 pub use constant::*;
+pub use custom::*;
+pub use env::*;
+pub use error::*;
 pub use factorial::*;
 pub use func::Func;
 pub use func2::Func2;
+pub use integral::*;
 pub use lexer::*;
 pub use number::*;
+pub use operator::*;
 pub use parenthesis::*;
 pub use parser::*;
+pub use program::*;
+pub use registry::*;
+pub use scalar::*;
 pub use traits::*;
 pub use unary::*;
 pub use variable::*;
 
+use crate::{
+	math::{Complex, Interval},
+	neg, num, paren, var,
+};
+
 /// Top-level expression
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Expression {
 	Number(Number),
-	Factorial(Factorial),
+	Factorial(Box<Factorial>),
 	Constant(Constant),
 	Variable(Variable),
 	Unary(Box<Unary>),
@@ -38,6 +66,355 @@ pub enum Expression {
 	Binary(Box<Binary>),
 	Func(Box<Func>),
 	Func2(Box<Func2>),
+	Call(Box<Call>),
+	Custom(Box<Custom>),
+	Conditional(Box<Conditional>),
+	Integral(Box<Integral>),
+}
+
+/// Replace a constant sub-expression with its value.
+pub(crate) fn fold(expr: Expression) -> Expression {
+	if let Expression::Number(_) | Expression::Constant(_) | Expression::Variable(_) = expr {
+		return expr;
+	}
+
+	match expr.constant_value() {
+		Some(v) if v.is_finite() => {
+			if v < 0.0 {
+				neg!(num!(-v)).into()
+			} else {
+				// adding 0 turns -0 into 0, which prints without the sign
+				num!(v + 0.0).into()
+			}
+		}
+		_ => expr,
+	}
+}
+
+/// `expr + d`, or `expr - |d|` if `d` is negative.
+fn offset(expr: Expression, d: f64) -> Expression {
+	if d < 0.0 {
+		Binary::Sub((expr, num!(-d).into())).into()
+	} else {
+		Binary::Add((expr, num!(d).into())).into()
+	}
+}
+
+/// LaTeX of an operand, enclosed by brackets if `paren`.
+pub(crate) fn latex_operand(expr: &Expression, paren: bool) -> String {
+	if paren {
+		format!("\\left({}\\right)", expr.to_latex())
+	} else {
+		expr.to_latex()
+	}
+}
+
+/// `MathML` of an operand, enclosed by brackets if `paren`.
+pub(crate) fn mathml_operand(expr: &Expression, paren: bool) -> String {
+	if paren {
+		format!("<mrow><mo>(</mo>{}<mo>)</mo></mrow>", expr.to_mathml())
+	} else {
+		expr.to_mathml()
+	}
+}
+
+/// Write an operand, enclosed by parenthesis if `paren`.
+pub(crate) fn fmt_operand(
+	f: &mut std::fmt::Formatter<'_>,
+	expr: &Expression,
+	paren: bool,
+) -> std::fmt::Result {
+	if paren {
+		write!(f, "({expr})")
+	} else {
+		write!(f, "{expr}")
+	}
+}
+
+impl Expression {
+	/// Precedence of the expression when printed as an operand.
+	#[must_use]
+	pub fn precedence(&self) -> u8 {
+		match self {
+			Expression::Binary(e) => e.operator().precedence(),
+			Expression::Unary(e) => e.precedence(),
+			// negative numbers are printed with a leading `-`
+			Expression::Number(n) if n.value().is_sign_negative() => NEG_PRECEDENCE,
+			_ => ATOM_PRECEDENCE,
+		}
+	}
+
+	/// Precedence of the expression when typeset as an operand, where fraction bars group like
+	/// brackets.
+	pub(crate) fn typeset_precedence(&self) -> u8 {
+		match self {
+			Expression::Binary(e) if e.operator() == Operator::Div => ATOM_PRECEDENCE,
+			Expression::Parenthesis(e) => e.inner().typeset_precedence(),
+			_ => self.precedence(),
+		}
+	}
+
+	/// Evaluate the expression if it does not depend on x.
+	#[must_use]
+	pub fn constant_value(&self) -> Option<f64> {
+		if self.is_constant() {
+			self.eval(0.0f64).ok()
+		} else {
+			None
+		}
+	}
+
+	/// The direct sub-expressions, in the order they are printed.
+	///
+	/// The arguments of a [`Call`] are its children, since its definition only uses the variables
+	/// bound to those. The integrand of an [`Integral`] is a child although it binds x.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use rsap::expression::{parse_with, Expression};
+	/// fn size(expr: &Expression) -> usize {
+	/// 	1 + expr.children().into_iter().map(size).sum::<usize>()
+	/// }
+	///
+	/// let f = parse_with("sin(x) + 2 * y", &["x", "y"]).unwrap();
+	/// assert_eq!(f.children().len(), 2);
+	/// assert_eq!(size(&f), 6);
+	/// ```
+	#[must_use]
+	pub fn children(&self) -> Vec<&Expression> {
+		match self {
+			Expression::Number(_) | Expression::Constant(_) | Expression::Variable(_) => vec![],
+			Expression::Factorial(e) => vec![e.arg()],
+			Expression::Unary(e) => vec![e.operand()],
+			Expression::Parenthesis(e) => vec![e.inner()],
+			Expression::Binary(e) => {
+				let (lhs, rhs) = e.operands();
+				vec![lhs, rhs]
+			}
+			Expression::Func(e) => vec![e.arg()],
+			Expression::Func2(e) => {
+				let (lhs, rhs) = e.args();
+				vec![lhs, rhs]
+			}
+			Expression::Call(e) => e.args().iter().collect(),
+			Expression::Custom(e) => e.args().iter().collect(),
+			Expression::Conditional(e) => [Some(e.condition()), Some(e.then()), e.otherwise()]
+				.into_iter()
+				.flatten()
+				.collect(),
+			Expression::Integral(e) => {
+				let (lower, upper) = e.bounds();
+				vec![e.integrand(), lower, upper]
+			}
+		}
+	}
+
+	/// Create the expression of the same kind with the children mapped by `f`, in the order of
+	/// [`Expression::children`].
+	///
+	/// The definition of a [`Call`] is kept, since it only uses its parameters.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use rsap::{expression::{parse_with, Expression}, var};
+	/// fn rename(expr: &Expression) -> Expression {
+	/// 	match expr {
+	/// 		Expression::Variable(v) if v.name() == "y" => var!("z").into(),
+	/// 		_ => expr.map_children(rename),
+	/// 	}
+	/// }
+	///
+	/// let f = parse_with("sin(y) + 2 * y", &["y"]).unwrap();
+	/// assert_eq!(rename(&f).to_string(), "sin(z) + 2 * z");
+	/// ```
+	#[must_use]
+	pub fn map_children<F: FnMut(&Expression) -> Expression>(&self, mut f: F) -> Expression {
+		match self {
+			Expression::Number(_) | Expression::Constant(_) | Expression::Variable(_) => {
+				self.clone()
+			}
+			Expression::Factorial(e) => crate::factorial!(f(e.arg())).into(),
+			Expression::Unary(e) => match e.as_ref() {
+				Unary::Neg(expr) => neg!(f(expr)).into(),
+				Unary::Not(expr) => crate::not!(f(expr)).into(),
+			},
+			Expression::Parenthesis(e) => paren!(f(e.inner())).into(),
+			Expression::Binary(e) => {
+				let (lhs, rhs) = e.operands();
+				let lhs = f(lhs);
+				e.with_operands(lhs, f(rhs)).into()
+			}
+			Expression::Func(e) => e.map(f).into(),
+			Expression::Func2(e) => e.map(f).into(),
+			Expression::Call(e) => e.map(f).into(),
+			Expression::Custom(e) => e.map_args(f).into(),
+			Expression::Conditional(e) => e.map(f).into(),
+			Expression::Integral(e) => e.map(f).into(),
+		}
+	}
+
+	/// Does the variable occur free, where x is bound in integrands.
+	pub(crate) fn uses(&self, name: &str) -> bool {
+		match self {
+			Expression::Variable(e) => e.name() == name,
+			Expression::Integral(e) => {
+				let (lower, upper) = e.bounds();
+				(name != "x" && e.integrand().uses(name)) || lower.uses(name) || upper.uses(name)
+			}
+			_ => self.children().into_iter().any(|expr| expr.uses(name)),
+		}
+	}
+
+	/// Replace the variables with the expressions bound to their names.
+	///
+	/// Return `None` if x of a replacement would be bound by an integral.
+	pub(crate) fn substitute_all(&self, bindings: &[(String, Expression)]) -> Option<Expression> {
+		match self {
+			Expression::Variable(e) => Some(
+				bindings
+					.iter()
+					.find(|(name, _)| name == e.name())
+					.map_or_else(|| self.clone(), |(_, expr)| expr.clone()),
+			),
+			Expression::Integral(e) => e.substitute_all(bindings).map(Into::into),
+			_ => {
+				let mut captured = false;
+				let expr = self.map_children(|expr| {
+					expr.substitute_all(bindings).unwrap_or_else(|| {
+						captured = true;
+						expr.clone()
+					})
+				});
+				(!captured).then_some(expr)
+			}
+		}
+	}
+
+	/// Replace the variable `var` with `replacement`, except where it is bound by an integral.
+	///
+	/// Return `None` if `var` is used in the integrand of an integral and `replacement` uses x,
+	/// which the integral would bind as its variable of integration.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use rsap::expression::{parse, parse_with, Function};
+	/// let f = parse("x ** 2 + integral(x, 0, x)").unwrap();
+	/// let g = f.substitute("x", &parse("x - 1").unwrap()).unwrap();
+	/// assert_eq!(g.to_string(), "(x - 1) ** 2 + integral(x, 0, x - 1)");
+	/// assert!((g.eval(3.0f64).unwrap() - 6.0).abs() < 1e-10);
+	///
+	/// let f = parse_with("integral(t * x, 0, 1)", &["x", "t"]).unwrap();
+	/// assert!(f.substitute("t", &parse("x").unwrap()).is_none());
+	/// ```
+	#[must_use]
+	pub fn substitute(&self, var: &str, replacement: &Expression) -> Option<Expression> {
+		self.substitute_all(&[(var.to_string(), replacement.clone())])
+	}
+
+	/// Composition `f(g(x))` of the function `f` with `inner` as `g`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use rsap::expression::parse;
+	/// let f = parse("sin(x)").unwrap();
+	/// let g = parse("2 * x").unwrap();
+	/// assert_eq!(f.compose(&g).to_string(), "sin(2 * x)");
+	/// assert_eq!(g.compose(&f).to_string(), "2 * sin(x)");
+	/// ```
+	#[must_use]
+	pub fn compose(&self, inner: &Expression) -> Expression {
+		match self {
+			Expression::Variable(e) if e.name() == "x" => inner.clone(),
+			// x in the integrand is the variable of integration
+			Expression::Integral(e) => e.map_bounds(|bound| bound.compose(inner)).into(),
+			_ => self.map_children(|expr| expr.compose(inner)),
+		}
+	}
+
+	/// Shift the graph horizontally by `h`, which is `f(x - h)`.
+	#[must_use]
+	pub fn shift_x(&self, h: f64) -> Expression {
+		self.compose(&offset(var!().into(), -h))
+	}
+
+	/// Scale the graph horizontally by `k`, which is `f(x / k)`.
+	#[must_use]
+	pub fn scale_x(&self, k: f64) -> Expression {
+		self.compose(&Binary::Div((var!().into(), num!(k).into())).into())
+	}
+
+	/// Shift the graph vertically by `k`, which is `f(x) + k`.
+	#[must_use]
+	pub fn shift_y(&self, k: f64) -> Expression {
+		offset(self.clone(), k)
+	}
+
+	/// Scale the graph vertically by `k`, which is `k * f(x)`.
+	#[must_use]
+	pub fn scale_y(&self, k: f64) -> Expression {
+		Binary::Mul((num!(k).into(), self.clone())).into()
+	}
+
+	/// Canonical form, where parentheses are removed and the operands of sums and products are
+	/// sorted, so the expressions that differ in the order of those are equal, e.g., `(x + 1) * 2`
+	/// and `2 * (1 + x)`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use rsap::expression::parse;
+	/// let f = parse("(x + 1) * 2").unwrap();
+	/// let g = parse("2 * (1 + x)").unwrap();
+	/// assert_ne!(f, g);
+	/// assert_eq!(f.canonical(), g.canonical());
+	/// assert_eq!(f.canonical().to_string(), "2 * (1 + x)");
+	/// ```
+	#[must_use]
+	pub fn canonical(&self) -> Expression {
+		match self {
+			Expression::Parenthesis(e) => e.inner().canonical(),
+			Expression::Binary(e) => {
+				let op = e.operator();
+				if let Operator::Add | Operator::Mul = op {
+					let mut operands = Vec::new();
+					self.collect_operands(op, &mut operands);
+					operands.sort();
+					operands
+						.into_iter()
+						.reduce(|lhs, rhs| e.with_operands(lhs, rhs).into())
+						.unwrap_or_else(|| self.clone())
+				} else {
+					let (lhs, rhs) = e.operands();
+					e.with_operands(lhs.canonical(), rhs.canonical()).into()
+				}
+			}
+			_ => self.map_children(Expression::canonical),
+		}
+	}
+
+	/// Push the canonical operands of the chain of the associative operator `op`, e.g., `x`, `y`
+	/// and `z` of `x + (y + z)`.
+	fn collect_operands(&self, op: Operator, out: &mut Vec<Expression>) {
+		match self {
+			Expression::Parenthesis(e) => e.inner().collect_operands(op, out),
+			Expression::Binary(e) if e.operator() == op => {
+				let (lhs, rhs) = e.operands();
+				lhs.collect_operands(op, out);
+				rhs.collect_operands(op, out);
+			}
+			_ => out.push(self.canonical()),
+		}
+	}
+
+	/// Is the expression a constant equal to `n`.
+	#[allow(clippy::float_cmp)]
+	pub(crate) fn is_number(&self, n: f64) -> bool {
+		self.constant_value().is_some_and(|v| v == n)
+	}
 }
 
 impl std::fmt::Display for Expression {
@@ -52,33 +429,323 @@ impl std::fmt::Display for Expression {
 			Expression::Binary(e) => e.fmt(f),
 			Expression::Func(e) => e.fmt(f),
 			Expression::Func2(e) => e.fmt(f),
+			Expression::Call(e) => e.fmt(f),
+			Expression::Custom(e) => e.fmt(f),
+			Expression::Conditional(e) => e.fmt(f),
+			Expression::Integral(e) => e.fmt(f),
 		}
 	}
 }
 
 impl Function for Expression {
-	fn is_x_valid(&self, x: f32) -> bool {
+	fn eval_with<T: Scalar>(&self, env: &Env<T>) -> Result<T, EvalError> {
+		match self {
+			Expression::Number(e) => e.eval_with(env),
+			Expression::Factorial(e) => e.eval_with(env),
+			Expression::Variable(e) => e.eval_with(env),
+			Expression::Constant(e) => e.eval_with(env),
+			Expression::Unary(e) => e.eval_with(env),
+			Expression::Parenthesis(e) => e.eval_with(env),
+			Expression::Binary(e) => e.eval_with(env),
+			Expression::Func(e) => e.eval_with(env),
+			Expression::Func2(e) => e.eval_with(env),
+			Expression::Call(e) => e.eval_with(env),
+			Expression::Custom(e) => e.eval_with(env),
+			Expression::Conditional(e) => e.eval_with(env),
+			Expression::Integral(e) => e.eval_with(env),
+		}
+	}
+}
+
+impl ComplexFunction for Expression {
+	fn eval_complex_with(&self, env: &Env<Complex>) -> Result<Complex, EvalError> {
+		match self {
+			Expression::Number(e) => e.eval_complex_with(env),
+			Expression::Factorial(e) => e.eval_complex_with(env),
+			Expression::Variable(e) => e.eval_complex_with(env),
+			Expression::Constant(e) => e.eval_complex_with(env),
+			Expression::Unary(e) => e.eval_complex_with(env),
+			Expression::Parenthesis(e) => e.eval_complex_with(env),
+			Expression::Binary(e) => e.eval_complex_with(env),
+			Expression::Func(e) => e.eval_complex_with(env),
+			Expression::Func2(e) => e.eval_complex_with(env),
+			Expression::Call(e) => e.eval_complex_with(env),
+			Expression::Custom(e) => e.eval_complex_with(env),
+			Expression::Conditional(e) => e.eval_complex_with(env),
+			Expression::Integral(e) => e.eval_complex_with(env),
+		}
+	}
+}
+
+impl IntervalFunction for Expression {
+	fn eval_interval_with(&self, env: &Env<Interval>) -> Result<Interval, EvalError> {
+		match self {
+			Expression::Number(e) => e.eval_interval_with(env),
+			Expression::Factorial(e) => e.eval_interval_with(env),
+			Expression::Variable(e) => e.eval_interval_with(env),
+			Expression::Constant(e) => e.eval_interval_with(env),
+			Expression::Unary(e) => e.eval_interval_with(env),
+			Expression::Parenthesis(e) => e.eval_interval_with(env),
+			Expression::Binary(e) => e.eval_interval_with(env),
+			Expression::Func(e) => e.eval_interval_with(env),
+			Expression::Func2(e) => e.eval_interval_with(env),
+			Expression::Call(e) => e.eval_interval_with(env),
+			Expression::Custom(e) => e.eval_interval_with(env),
+			Expression::Conditional(e) => e.eval_interval_with(env),
+			Expression::Integral(e) => e.eval_interval_with(env),
+		}
+	}
+}
+
+impl Derivative for Expression {
+	fn is_constant(&self) -> bool {
 		match self {
-			Expression::Unary(e) => e.is_x_valid(x),
-			Expression::Parenthesis(e) => e.is_x_valid(x),
-			Expression::Binary(e) => e.is_x_valid(x),
-			Expression::Func(e) => e.is_x_valid(x),
-			Expression::Func2(e) => e.is_x_valid(x),
-			_ => true,
+			Expression::Number(e) => e.is_constant(),
+			Expression::Factorial(e) => e.is_constant(),
+			Expression::Variable(e) => e.is_constant(),
+			Expression::Constant(e) => e.is_constant(),
+			Expression::Unary(e) => e.is_constant(),
+			Expression::Parenthesis(e) => e.is_constant(),
+			Expression::Binary(e) => e.is_constant(),
+			Expression::Func(e) => e.is_constant(),
+			Expression::Func2(e) => e.is_constant(),
+			Expression::Call(e) => e.is_constant(),
+			Expression::Custom(e) => e.is_constant(),
+			Expression::Conditional(e) => e.is_constant(),
+			Expression::Integral(e) => e.is_constant(),
 		}
 	}
 
-	fn eval(&self, x: f32) -> Option<f32> {
+	fn derivative(&self) -> Expression {
 		match self {
-			Expression::Number(e) => e.eval(x),
-			Expression::Factorial(e) => e.eval(x),
-			Expression::Variable(e) => e.eval(x),
-			Expression::Constant(e) => e.eval(x),
-			Expression::Unary(e) => e.eval(x),
-			Expression::Parenthesis(e) => e.eval(x),
-			Expression::Binary(e) => e.eval(x),
-			Expression::Func(e) => e.eval(x),
-			Expression::Func2(e) => e.eval(x),
+			Expression::Number(e) => e.derivative(),
+			Expression::Factorial(e) => e.derivative(),
+			Expression::Variable(e) => e.derivative(),
+			Expression::Constant(e) => e.derivative(),
+			Expression::Unary(e) => e.derivative(),
+			Expression::Parenthesis(e) => e.derivative(),
+			Expression::Binary(e) => e.derivative(),
+			Expression::Func(e) => e.derivative(),
+			Expression::Func2(e) => e.derivative(),
+			Expression::Call(e) => e.derivative(),
+			Expression::Custom(e) => e.derivative(),
+			Expression::Conditional(e) => e.derivative(),
+			Expression::Integral(e) => e.derivative(),
 		}
 	}
 }
+
+impl Simplify for Expression {
+	fn simplify(&self) -> Expression {
+		match self {
+			Expression::Number(e) => e.simplify(),
+			Expression::Factorial(e) => e.simplify(),
+			Expression::Variable(e) => e.simplify(),
+			Expression::Constant(e) => e.simplify(),
+			Expression::Unary(e) => e.simplify(),
+			Expression::Parenthesis(e) => e.simplify(),
+			Expression::Binary(e) => e.simplify(),
+			Expression::Func(e) => e.simplify(),
+			Expression::Func2(e) => e.simplify(),
+			Expression::Call(e) => e.simplify(),
+			Expression::Custom(e) => e.simplify(),
+			Expression::Conditional(e) => e.simplify(),
+			Expression::Integral(e) => e.simplify(),
+		}
+	}
+}
+
+impl Typeset for Expression {
+	fn to_latex(&self) -> String {
+		match self {
+			Expression::Number(e) => e.to_latex(),
+			Expression::Factorial(e) => e.to_latex(),
+			Expression::Variable(e) => e.to_latex(),
+			Expression::Constant(e) => e.to_latex(),
+			Expression::Unary(e) => e.to_latex(),
+			Expression::Parenthesis(e) => e.to_latex(),
+			Expression::Binary(e) => e.to_latex(),
+			Expression::Func(e) => e.to_latex(),
+			Expression::Func2(e) => e.to_latex(),
+			Expression::Call(e) => e.to_latex(),
+			Expression::Custom(e) => e.to_latex(),
+			Expression::Conditional(e) => e.to_latex(),
+			Expression::Integral(e) => e.to_latex(),
+		}
+	}
+
+	fn to_mathml(&self) -> String {
+		match self {
+			Expression::Number(e) => e.to_mathml(),
+			Expression::Factorial(e) => e.to_mathml(),
+			Expression::Variable(e) => e.to_mathml(),
+			Expression::Constant(e) => e.to_mathml(),
+			Expression::Unary(e) => e.to_mathml(),
+			Expression::Parenthesis(e) => e.to_mathml(),
+			Expression::Binary(e) => e.to_mathml(),
+			Expression::Func(e) => e.to_mathml(),
+			Expression::Func2(e) => e.to_mathml(),
+			Expression::Call(e) => e.to_mathml(),
+			Expression::Custom(e) => e.to_mathml(),
+			Expression::Conditional(e) => e.to_mathml(),
+			Expression::Integral(e) => e.to_mathml(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::HashSet;
+
+	#[test]
+	fn test_eq() {
+		let f = parse("sin(x) + 1").unwrap();
+		assert_eq!(f, parse("sin(x)+1").unwrap());
+		assert_ne!(f, parse("1 + sin(x)").unwrap());
+		assert_ne!(f, parse("sin(x) + 1.5").unwrap());
+		assert_ne!(parse("x").unwrap(), parse("(x)").unwrap());
+	}
+
+	#[test]
+	fn test_canonical() {
+		let cases = [
+			("x + 1", "1 + x"),
+			("(x * 2) * sin(x)", "sin(x) * x * 2"),
+			("x + (y + z)", "(z + x) + y"),
+			("(x - 1) ** (2 + e)", "(x - 1) ** (e + 2)"),
+			("if(y * x, -(1 + x))", "if(x * y, -(x + 1))"),
+		];
+		for (lhs, rhs) in cases {
+			let (f, g) = (
+				parse_with(lhs, &["x", "y", "z"]).unwrap(),
+				parse_with(rhs, &["x", "y", "z"]).unwrap(),
+			);
+			assert_eq!(f.canonical(), g.canonical(), "{lhs} and {rhs}");
+		}
+
+		// subtraction is not commutative
+		let f = parse("x - 1").unwrap();
+		assert_ne!(f.canonical(), parse("1 - x").unwrap().canonical());
+	}
+
+	#[test]
+	fn test_hash() {
+		let set: HashSet<_> = ["x + 1", "1 + x", "(x) + 1", "x * 1"]
+			.into_iter()
+			.map(|f| parse(f).unwrap().canonical())
+			.collect();
+		assert_eq!(set.len(), 2);
+	}
+
+	/// The free variables, where x is bound in integrands.
+	fn variables(expr: &Expression, out: &mut Vec<String>) {
+		match expr {
+			Expression::Variable(v) => out.push(v.name().to_string()),
+			Expression::Integral(e) => {
+				let mut inner = Vec::new();
+				variables(e.integrand(), &mut inner);
+				out.extend(inner.into_iter().filter(|name| name != "x"));
+				let (lower, upper) = e.bounds();
+				variables(lower, out);
+				variables(upper, out);
+			}
+			_ => expr.children().into_iter().for_each(|e| variables(e, out)),
+		}
+	}
+
+	fn depth(expr: &Expression) -> usize {
+		1 + expr.children().into_iter().map(depth).max().unwrap_or(0)
+	}
+
+	#[test]
+	fn test_fold() {
+		for f in ["-0", "-(0)", "sin(-0)", "tan(-(0))"] {
+			let g = parse(f).unwrap().simplify();
+			assert_eq!(g, Expression::from(num!(0.0)), "{f}");
+			assert_eq!(parse(g.to_string()).unwrap(), g);
+		}
+	}
+
+	#[test]
+	fn test_children() {
+		let f = parse_with(
+			"if(y < 0, -y!, integral(x * z, 0, y)) + 2",
+			&["x", "y", "z"],
+		)
+		.unwrap();
+		let mut names = Vec::new();
+		variables(&f, &mut names);
+		assert_eq!(names, ["y", "y", "z", "y"]);
+		assert_eq!(depth(&f), 5);
+
+		let f = parse_program("f(t) = t ** 2; f(x + 1) * log(2, x)").unwrap();
+		let f = Expression::from(f);
+		let (call, log) = (f.children()[0], f.children()[1]);
+		assert_eq!(call.children().len(), 1);
+		assert_eq!(log.children().len(), 2);
+		assert!(Expression::from(num!(1.0)).children().is_empty());
+	}
+
+	#[test]
+	fn test_map_children() {
+		fn double(expr: &Expression) -> Expression {
+			match expr {
+				Expression::Number(n) => num!(2.0 * n.value()).into(),
+				_ => expr.map_children(double),
+			}
+		}
+
+		for (f, g) in [
+			("sin(x + 1)", "sin(x + 2)"),
+			("-(3!)", "-(6!)"),
+			("if(x < 1, 2)", "if(x < 2, 4)"),
+			("integral(x ** 2, 0, 1)", "integral(x ** 4, 0, 2)"),
+		] {
+			assert_eq!(double(&parse(f).unwrap()), parse(g).unwrap());
+		}
+
+		// the arguments of a call are mapped
+		let f = Expression::from(parse_program("f(t) = t + 1; f(x)").unwrap());
+		let g = f.map_children(|e| e.compose(&num!(2.0).into()));
+		assert_eq!(g.to_string(), "f(2)");
+		assert_eq!(g.eval(0.0).unwrap(), 3.0);
+	}
+
+	#[test]
+	fn test_substitute() {
+		let f = parse_with("x * y + y", &["x", "y"]).unwrap();
+		let g = f.substitute("y", &parse("x + 1").unwrap()).unwrap();
+		assert_eq!(g.to_string(), "x * (x + 1) + (x + 1)");
+		assert_eq!(f.substitute("z", &num!(1.0).into()), Some(f));
+
+		// x of the replacement would be bound by the integral
+		let f = parse_with("integral(y * x, 0, y)", &["x", "y"]).unwrap();
+		assert!(f.substitute("y", &parse("x").unwrap()).is_none());
+		let g = f.substitute("y", &num!(2.0).into()).unwrap();
+		assert_eq!(g.to_string(), "integral(2 * x, 0, 2)");
+		let g = f.substitute("z", &parse("x").unwrap()).unwrap();
+		assert_eq!(g, f);
+
+		// calls and conditionals
+		let f = Expression::from(parse_program("f(t) = t ** 2; if(x < 0, f(x))").unwrap());
+		let g = f.compose(&parse("x - 1").unwrap());
+		assert_eq!(g.to_string(), "if(x - 1 < 0, f(x - 1))");
+		assert_eq!(g.eval(-1.0).unwrap(), 4.0);
+		assert!(g.eval(2.0).is_err());
+	}
+
+	#[test]
+	fn test_transform() {
+		let f = parse("x ** 2").unwrap();
+		assert_eq!(f.shift_x(1.0).to_string(), "(x - 1) ** 2");
+		assert_eq!(f.shift_x(-1.0).to_string(), "(x + 1) ** 2");
+		assert_eq!(f.scale_x(2.0).to_string(), "(x / 2) ** 2");
+		assert_eq!(f.shift_y(-3.0).to_string(), "x ** 2 - 3");
+		assert_eq!(f.scale_y(0.5).to_string(), "0.5 * x ** 2");
+
+		let g = f.shift_x(1.0).scale_x(2.0).shift_y(1.0);
+		assert_eq!(g.to_string(), "(x / 2 - 1) ** 2 + 1");
+		assert_eq!(g.eval(6.0).unwrap(), 5.0);
+	}
+}
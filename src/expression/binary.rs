@@ -1,21 +1,43 @@
 #[macro_use]
 mod macros;
 
-use super::{Expression, Function};
+use super::{
+	fmt_operand, fold, latex_operand, mathml_operand, Associativity, ComplexFunction, Derivative,
+	Env, EvalError, Expression, Function, IntervalFunction, Operator, Scalar, Simplify, Typeset,
+	ATOM_PRECEDENCE, NEG_PRECEDENCE,
+};
+use crate::{
+	ln,
+	math::{Complex, Interval},
+	neg, num,
+};
 
 /// Binary expression.
 ///
 /// ```bnf
 /// bop_rhs ::= b_op primary
-/// b_op ::= "+" | "-" | "*" | "/" | "**"
+/// b_op ::= "+" | "-" | "*" | "/" | "**" | "^"
+///        | "<" | "<=" | "==" | "!=" | ">=" | ">" | "and" | "or"
 /// ```
-#[derive(Debug, Clone)]
+///
+/// Comparisons and logical operators evaluate to 1 for true and 0 for false, where any non-zero
+/// operand is true.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Binary {
 	Add((Expression, Expression)),
 	Sub((Expression, Expression)),
 	Mul((Expression, Expression)),
 	Div((Expression, Expression)),
 	Pow((Expression, Expression)),
+	Lt((Expression, Expression)),
+	Le((Expression, Expression)),
+	Eq((Expression, Expression)),
+	Ne((Expression, Expression)),
+	Ge((Expression, Expression)),
+	Gt((Expression, Expression)),
+	And((Expression, Expression)),
+	Or((Expression, Expression)),
 }
 
 impl Binary {
@@ -24,74 +46,384 @@ impl Binary {
 		lhs: L,
 		rhs: R,
 	) -> Option<Self> {
-		match op.as_ref() {
-			"+" => Some(Self::Add((lhs.into(), rhs.into()))),
-			"-" => Some(Self::Sub((lhs.into(), rhs.into()))),
-			"*" => Some(Self::Mul((lhs.into(), rhs.into()))),
-			"/" => Some(Self::Div((lhs.into(), rhs.into()))),
-			"**" => Some(Self::Pow((lhs.into(), rhs.into()))),
-			_ => None,
+		let operands = (lhs.into(), rhs.into());
+		Some(match Operator::from_symbol(op.as_ref())? {
+			Operator::Add => Self::Add(operands),
+			Operator::Sub => Self::Sub(operands),
+			Operator::Mul => Self::Mul(operands),
+			Operator::Div => Self::Div(operands),
+			Operator::Pow => Self::Pow(operands),
+			Operator::Lt => Self::Lt(operands),
+			Operator::Le => Self::Le(operands),
+			Operator::Eq => Self::Eq(operands),
+			Operator::Ne => Self::Ne(operands),
+			Operator::Ge => Self::Ge(operands),
+			Operator::Gt => Self::Gt(operands),
+			Operator::And => Self::And(operands),
+			Operator::Or => Self::Or(operands),
+		})
+	}
+}
+
+impl Binary {
+	#[must_use]
+	pub fn operator(&self) -> Operator {
+		match self {
+			Binary::Add(_) => Operator::Add,
+			Binary::Sub(_) => Operator::Sub,
+			Binary::Mul(_) => Operator::Mul,
+			Binary::Div(_) => Operator::Div,
+			Binary::Pow(_) => Operator::Pow,
+			Binary::Lt(_) => Operator::Lt,
+			Binary::Le(_) => Operator::Le,
+			Binary::Eq(_) => Operator::Eq,
+			Binary::Ne(_) => Operator::Ne,
+			Binary::Ge(_) => Operator::Ge,
+			Binary::Gt(_) => Operator::Gt,
+			Binary::And(_) => Operator::And,
+			Binary::Or(_) => Operator::Or,
+		}
+	}
+
+	/// The left and right operands.
+	#[must_use]
+	pub fn operands(&self) -> (&Expression, &Expression) {
+		match self {
+			Binary::Add((lhs, rhs))
+			| Binary::Sub((lhs, rhs))
+			| Binary::Mul((lhs, rhs))
+			| Binary::Div((lhs, rhs))
+			| Binary::Pow((lhs, rhs))
+			| Binary::Lt((lhs, rhs))
+			| Binary::Le((lhs, rhs))
+			| Binary::Eq((lhs, rhs))
+			| Binary::Ne((lhs, rhs))
+			| Binary::Ge((lhs, rhs))
+			| Binary::Gt((lhs, rhs))
+			| Binary::And((lhs, rhs))
+			| Binary::Or((lhs, rhs)) => (lhs, rhs),
+		}
+	}
+
+	/// Create the operation of the same operator on other operands.
+	pub(crate) fn with_operands(&self, lhs: Expression, rhs: Expression) -> Self {
+		Self::new(self.operator().symbol(), lhs, rhs).unwrap()
+	}
+
+	/// The operation on the operand values.
+	///
+	/// The function returns an error if the values are not in the definition domain.
+	pub(crate) fn scalar_fn<T: Scalar>(&self) -> fn(T, T) -> Result<T, EvalError> {
+		match self {
+			Binary::Add(_) => |lhs, rhs| Ok(lhs + rhs),
+			Binary::Sub(_) => |lhs, rhs| Ok(lhs - rhs),
+			Binary::Mul(_) => |lhs, rhs| Ok(lhs * rhs),
+			Binary::Div(_) => |lhs, rhs| {
+				if rhs == T::ZERO {
+					Err(EvalError::DivisionByZero)
+				} else {
+					Ok(lhs / rhs)
+				}
+			},
+			Binary::Pow(_) => |lhs, rhs| {
+				if lhs == T::ZERO && rhs < T::ZERO {
+					return Err(EvalError::ZeroToNegativePower(rhs.to_f64()));
+				}
+
+				// we consider complex number invalid
+				let y = lhs.powf(rhs);
+				if y.is_nan() {
+					Err(EvalError::ComplexPower(lhs.to_f64(), rhs.to_f64()))
+				} else {
+					Ok(y)
+				}
+			},
+			Binary::Lt(_) => |lhs, rhs| Ok(T::from_bool(lhs < rhs)),
+			Binary::Le(_) => |lhs, rhs| Ok(T::from_bool(lhs <= rhs)),
+			Binary::Eq(_) => |lhs, rhs| Ok(T::from_bool(lhs == rhs)),
+			Binary::Ne(_) => |lhs, rhs| Ok(T::from_bool(lhs != rhs)),
+			Binary::Ge(_) => |lhs, rhs| Ok(T::from_bool(lhs >= rhs)),
+			Binary::Gt(_) => |lhs, rhs| Ok(T::from_bool(lhs > rhs)),
+			Binary::And(_) => |lhs, rhs| Ok(T::from_bool(lhs.is_true() && rhs.is_true())),
+			Binary::Or(_) => |lhs, rhs| Ok(T::from_bool(lhs.is_true() || rhs.is_true())),
+		}
+	}
+
+	/// Whether the operands need brackets when typeset.
+	fn typeset_parens(&self) -> (bool, bool) {
+		let op = self.operator();
+		let (lhs, rhs) = self.operands();
+
+		match op {
+			// the fraction bar groups both operands
+			Operator::Div => (false, false),
+			// the superscript groups the exponent
+			Operator::Pow => (lhs.typeset_precedence() < ATOM_PRECEDENCE, false),
+			// a negative operand on the right side is enclosed, e.g., x - (-2)
+			_ => (
+				lhs.typeset_precedence() < op.precedence(),
+				rhs.typeset_precedence() <= op.precedence()
+					|| rhs.typeset_precedence() == NEG_PRECEDENCE,
+			),
 		}
 	}
 }
 
 impl std::fmt::Display for Binary {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let op = self.operator();
+		let (lhs, rhs) = self.operands();
+
+		// operands binding looser than the operator need parenthesis, and so do operands binding
+		// as tight on the opposite side of the associativity
+		let lhs_paren = lhs.precedence() < op.precedence()
+			|| (lhs.precedence() == op.precedence() && op.associativity() == Associativity::Right);
+		// a unary operand on the right side is parsed as a primary, e.g., `x ** -2`
+		let rhs_paren = rhs.precedence() != NEG_PRECEDENCE
+			&& (rhs.precedence() < op.precedence()
+				|| (rhs.precedence() == op.precedence()
+					&& op.associativity() == Associativity::Left));
+
+		fmt_operand(f, lhs, lhs_paren)?;
+		write!(f, " {op} ")?;
+		fmt_operand(f, rhs, rhs_paren)
+	}
+}
+
+impl Function for Binary {
+	fn eval_with<T: Scalar>(&self, env: &Env<T>) -> Result<T, EvalError> {
+		let (lhs, rhs) = self.operands();
+		self.scalar_fn()(lhs.eval_with(env)?, rhs.eval_with(env)?)
+	}
+}
+
+impl ComplexFunction for Binary {
+	fn eval_complex_with(&self, env: &Env<Complex>) -> Result<Complex, EvalError> {
+		let (lhs, rhs) = self.operands();
+		let (lhs, rhs) = (lhs.eval_complex_with(env)?, rhs.eval_complex_with(env)?);
+
 		match self {
-			Binary::Add((lhs, rhs)) => write!(f, "{lhs} + {rhs}"),
-			Binary::Sub((lhs, rhs)) => write!(f, "{lhs} - {rhs}"),
-			Binary::Mul((lhs, rhs)) => write!(f, "{lhs} * {rhs}"),
-			Binary::Div((lhs, rhs)) => write!(f, "{lhs} / {rhs}"),
-			Binary::Pow((lhs, rhs)) => write!(f, "{lhs} ** {rhs}"),
+			Binary::Add(_) => Ok(lhs + rhs),
+			Binary::Sub(_) => Ok(lhs - rhs),
+			Binary::Mul(_) => Ok(lhs * rhs),
+			Binary::Div(_) => {
+				if rhs.is_zero() {
+					Err(EvalError::DivisionByZero)
+				} else {
+					Ok(lhs / rhs)
+				}
+			}
+			Binary::Pow(_) => {
+				if lhs.is_zero() && !rhs.is_zero() && rhs.re <= 0.0 {
+					Err(EvalError::ZeroToNegativePower(rhs.re))
+				} else {
+					Ok(lhs.powc(rhs))
+				}
+			}
+			Binary::Eq(_) => Ok(Complex::from(f64::from_bool(lhs == rhs))),
+			Binary::Ne(_) => Ok(Complex::from(f64::from_bool(lhs != rhs))),
+			Binary::And(_) => Ok(Complex::from(f64::from_bool(
+				!lhs.is_zero() && !rhs.is_zero(),
+			))),
+			Binary::Or(_) => Ok(Complex::from(f64::from_bool(
+				!lhs.is_zero() || !rhs.is_zero(),
+			))),
+			// complex numbers are not ordered
+			_ => {
+				if lhs.im == 0.0 && rhs.im == 0.0 {
+					self.scalar_fn()(lhs.re, rhs.re).map(Complex::from)
+				} else {
+					Err(EvalError::ComplexArgument(self.operator().to_string()))
+				}
+			}
 		}
 	}
 }
 
-impl Function for Binary {
-	fn is_x_valid(&self, x: f32) -> bool {
+impl IntervalFunction for Binary {
+	fn eval_interval_with(&self, env: &Env<Interval>) -> Result<Interval, EvalError> {
+		let (lhs, rhs) = self.operands();
+		let (lhs, rhs) = (lhs.eval_interval_with(env)?, rhs.eval_interval_with(env)?);
+
+		Ok(match self {
+			Binary::Add(_) => lhs + rhs,
+			Binary::Sub(_) => lhs - rhs,
+			Binary::Mul(_) => lhs * rhs,
+			Binary::Div(_) => lhs / rhs,
+			Binary::Pow(_) => lhs.pow(&rhs),
+			Binary::Lt(_) => lhs.cmp_lt(&rhs),
+			Binary::Le(_) => lhs.cmp_le(&rhs),
+			Binary::Eq(_) => lhs.cmp_eq(&rhs),
+			Binary::Ne(_) => lhs.cmp_eq(&rhs).logical_not(),
+			Binary::Ge(_) => rhs.cmp_le(&lhs),
+			Binary::Gt(_) => rhs.cmp_lt(&lhs),
+			Binary::And(_) => lhs.logical_and(&rhs),
+			Binary::Or(_) => lhs.logical_or(&rhs),
+		})
+	}
+}
+
+impl Derivative for Binary {
+	fn is_constant(&self) -> bool {
+		let (lhs, rhs) = self.operands();
+		lhs.is_constant() && rhs.is_constant()
+	}
+
+	fn derivative(&self) -> Expression {
 		match self {
-			Binary::Add((lhs, rhs)) => lhs.is_x_valid(x) && rhs.is_x_valid(x),
-			Binary::Sub((lhs, rhs)) => lhs.is_x_valid(x) && rhs.is_x_valid(x),
-			Binary::Mul((lhs, rhs)) => lhs.is_x_valid(x) && rhs.is_x_valid(x),
-			Binary::Div((lhs, rhs)) => lhs.is_x_valid(x) && rhs.eval(x).is_some_and(|v| v != 0.0),
+			Binary::Add((lhs, rhs)) => add!(lhs.derivative(), rhs.derivative()).into(),
+			Binary::Sub((lhs, rhs)) => sub!(lhs.derivative(), rhs.derivative()).into(),
+			// (f * g)' = f' * g + f * g'
+			Binary::Mul((lhs, rhs)) => add!(
+				mul!(lhs.derivative(), rhs.clone()),
+				mul!(lhs.clone(), rhs.derivative())
+			)
+			.into(),
+			// (f / g)' = (f' * g - f * g') / g ** 2
+			Binary::Div((lhs, rhs)) => div!(
+				sub!(
+					mul!(lhs.derivative(), rhs.clone()),
+					mul!(lhs.clone(), rhs.derivative())
+				),
+				pow!(rhs.clone(), num!(2.0))
+			)
+			.into(),
 			Binary::Pow((lhs, rhs)) => {
-				let Some(lhs) = lhs.eval(x) else {
-					return false;
-				};
-				let Some(rhs) = rhs.eval(x) else {
-					return false;
-				};
-
-				// 0.0 cannot be raised to a negative power
-				if lhs == 0.0 && rhs < 0.0 {
-					return false;
+				if rhs.is_constant() {
+					// (f ** c)' = c * f ** (c - 1) * f'
+					mul!(
+						mul!(rhs.clone(), pow!(lhs.clone(), sub!(rhs.clone(), num!(1.0)))),
+						lhs.derivative()
+					)
+					.into()
+				} else if lhs.is_constant() {
+					// (c ** g)' = c ** g * ln(c) * g'
+					mul!(mul!(self.clone(), ln!(lhs.clone())), rhs.derivative()).into()
+				} else {
+					// (f ** g)' = f ** g * (g' * ln(f) + g * f' / f)
+					mul!(
+						self.clone(),
+						add!(
+							mul!(rhs.derivative(), ln!(lhs.clone())),
+							div!(mul!(rhs.clone(), lhs.derivative()), lhs.clone())
+						)
+					)
+					.into()
 				}
-
-				// we consider complex number invalid
-				!lhs.powf(rhs).is_nan()
 			}
+			// truth values are piecewise constant
+			_ => num!(0.0).into(),
 		}
 	}
+}
 
-	fn eval(&self, x: f32) -> Option<f32> {
-		match self {
-			Binary::Add((lhs, rhs)) => Some(lhs.eval(x)? + rhs.eval(x)?),
-			Binary::Sub((lhs, rhs)) => Some(lhs.eval(x)? - rhs.eval(x)?),
-			Binary::Mul((lhs, rhs)) => Some(lhs.eval(x)? * rhs.eval(x)?),
-			Binary::Div((lhs, rhs)) => {
-				if self.is_x_valid(x) {
-					Some(lhs.eval(x).unwrap() / rhs.eval(x).unwrap())
+impl Simplify for Binary {
+	fn simplify(&self) -> Expression {
+		let (lhs, rhs) = self.operands();
+		let (lhs, rhs) = (lhs.simplify(), rhs.simplify());
+
+		let expr = match self {
+			Binary::Add(_) => {
+				if lhs.is_number(0.0) {
+					rhs
+				} else if rhs.is_number(0.0) {
+					lhs
 				} else {
-					None
+					add!(lhs, rhs).into()
 				}
 			}
-			Binary::Pow((lhs, rhs)) => {
-				if self.is_x_valid(x) {
-					Some(lhs.eval(x).unwrap().powf(rhs.eval(x).unwrap()))
+			Binary::Sub(_) => {
+				if rhs.is_number(0.0) {
+					lhs
+				} else if lhs.is_number(0.0) {
+					neg!(rhs).into()
+				} else {
+					sub!(lhs, rhs).into()
+				}
+			}
+			Binary::Mul(_) => {
+				if lhs.is_number(0.0) || rhs.is_number(0.0) {
+					num!(0.0).into()
+				} else if lhs.is_number(1.0) {
+					rhs
+				} else if rhs.is_number(1.0) {
+					lhs
+				} else {
+					mul!(lhs, rhs).into()
+				}
+			}
+			Binary::Div(_) => {
+				if rhs.is_number(1.0) {
+					lhs
+				} else if lhs.is_number(0.0) && !rhs.is_number(0.0) {
+					num!(0.0).into()
+				} else {
+					div!(lhs, rhs).into()
+				}
+			}
+			Binary::Pow(_) => {
+				if rhs.is_number(1.0) {
+					lhs
+				} else if rhs.is_number(0.0) || lhs.is_number(1.0) {
+					num!(1.0).into()
 				} else {
-					None
+					pow!(lhs, rhs).into()
 				}
 			}
+			_ => binary_expr!(self.operator().symbol(), lhs, rhs)
+				.unwrap()
+				.into(),
+		};
+
+		fold(expr)
+	}
+}
+
+impl Typeset for Binary {
+	fn to_latex(&self) -> String {
+		let (lhs, rhs) = self.operands();
+		let (lhs_paren, rhs_paren) = self.typeset_parens();
+		let (lhs, rhs) = (latex_operand(lhs, lhs_paren), latex_operand(rhs, rhs_paren));
+
+		match self {
+			Binary::Add(_) => format!("{lhs} + {rhs}"),
+			Binary::Sub(_) => format!("{lhs} - {rhs}"),
+			Binary::Mul(_) => format!("{lhs} \\cdot {rhs}"),
+			Binary::Div(_) => format!("\\frac{{{lhs}}}{{{rhs}}}"),
+			Binary::Pow(_) => format!("{lhs}^{{{rhs}}}"),
+			Binary::Lt(_) => format!("{lhs} < {rhs}"),
+			Binary::Le(_) => format!("{lhs} \\le {rhs}"),
+			Binary::Eq(_) => format!("{lhs} = {rhs}"),
+			Binary::Ne(_) => format!("{lhs} \\ne {rhs}"),
+			Binary::Ge(_) => format!("{lhs} \\ge {rhs}"),
+			Binary::Gt(_) => format!("{lhs} > {rhs}"),
+			Binary::And(_) => format!("{lhs} \\land {rhs}"),
+			Binary::Or(_) => format!("{lhs} \\lor {rhs}"),
+		}
+	}
+
+	fn to_mathml(&self) -> String {
+		let (lhs, rhs) = self.operands();
+		let (lhs_paren, rhs_paren) = self.typeset_parens();
+		let (lhs, rhs) = (
+			mathml_operand(lhs, lhs_paren),
+			mathml_operand(rhs, rhs_paren),
+		);
+
+		match self {
+			Binary::Add(_) => format!("<mrow>{lhs}<mo>+</mo>{rhs}</mrow>"),
+			Binary::Sub(_) => format!("<mrow>{lhs}<mo>-</mo>{rhs}</mrow>"),
+			Binary::Mul(_) => format!("<mrow>{lhs}<mo>⋅</mo>{rhs}</mrow>"),
+			Binary::Div(_) => format!("<mfrac>{lhs}{rhs}</mfrac>"),
+			Binary::Pow(_) => format!("<msup>{lhs}{rhs}</msup>"),
+			Binary::Lt(_) => format!("<mrow>{lhs}<mo>&lt;</mo>{rhs}</mrow>"),
+			Binary::Le(_) => format!("<mrow>{lhs}<mo>≤</mo>{rhs}</mrow>"),
+			Binary::Eq(_) => format!("<mrow>{lhs}<mo>=</mo>{rhs}</mrow>"),
+			Binary::Ne(_) => format!("<mrow>{lhs}<mo>≠</mo>{rhs}</mrow>"),
+			Binary::Ge(_) => format!("<mrow>{lhs}<mo>≥</mo>{rhs}</mrow>"),
+			Binary::Gt(_) => format!("<mrow>{lhs}<mo>&gt;</mo>{rhs}</mrow>"),
+			Binary::And(_) => format!("<mrow>{lhs}<mo>∧</mo>{rhs}</mrow>"),
+			Binary::Or(_) => format!("<mrow>{lhs}<mo>∨</mo>{rhs}</mrow>"),
 		}
 	}
 }
@@ -105,7 +437,7 @@ impl From<Binary> for Expression {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::{num, var};
+	use crate::{num, paren, var};
 
 	#[test]
 	fn test_add() {
@@ -132,7 +464,7 @@ mod tests {
 	fn test_div() {
 		let f = div!(num!(1.0), var!());
 		assert_eq!(f.eval(2.0).unwrap(), 0.5);
-		assert!(f.eval(0.0).is_none());
+		assert_eq!(f.eval(0.0), Err(EvalError::DivisionByZero));
 	}
 
 	#[test]
@@ -148,13 +480,161 @@ mod tests {
 		assert_eq!(f.eval(9.0).unwrap(), 1.0);
 
 		let f = pow!(var!(), num!(0.5));
-		assert!(f.eval(-9.0).is_none());
+		assert_eq!(f.eval(-9.0), Err(EvalError::ComplexPower(-9.0, 0.5)));
 		assert_eq!(f.eval(0.0).unwrap(), 0.0);
 		assert_eq!(f.eval(9.0).unwrap(), 3.0);
 
 		let f = pow!(var!(), num!(-2.0));
 		assert_eq!(f.eval(-9.0).unwrap(), (-9.0f32).powf(-2.0));
-		assert!(f.eval(0.0).is_none());
+		assert_eq!(f.eval(0.0), Err(EvalError::ZeroToNegativePower(-2.0)));
 		assert_eq!(f.eval(09.0).unwrap(), (9.0f32).powf(-2.0));
 	}
+
+	#[test]
+	fn test_derivative() {
+		let f = add!(var!(), num!(1.0)).derivative();
+		assert_eq!(f.eval(3.0).unwrap(), 1.0);
+
+		let f = sub!(num!(1.0), var!()).derivative();
+		assert_eq!(f.eval(3.0).unwrap(), -1.0);
+
+		let f = mul!(var!(), var!()).derivative();
+		assert_eq!(f.eval(3.0).unwrap(), 6.0);
+
+		let f = div!(num!(1.0), var!()).derivative();
+		assert_eq!(f.eval(2.0).unwrap(), -0.25);
+		assert_eq!(f.eval(0.0), Err(EvalError::DivisionByZero));
+
+		let f = pow!(var!(), num!(3.0)).derivative();
+		assert_eq!(f.to_string(), "3 * x ** (3 - 1) * 1");
+		assert_eq!(f.eval(-2.0).unwrap(), 12.0);
+
+		let f = pow!(num!(2.0), var!()).derivative();
+		assert_eq!(f.eval(3.0).unwrap(), 8.0 * 2.0f32.ln());
+
+		let f = pow!(var!(), var!()).derivative();
+		assert_eq!(f.eval(1.0).unwrap(), 1.0);
+	}
+
+	#[test]
+	fn test_simplify() {
+		let f = mul!(num!(2.0), num!(3.0)).simplify();
+		assert_eq!(f.to_string(), "6");
+
+		let f = add!(var!(), num!(0.0)).simplify();
+		assert_eq!(f.to_string(), "x");
+
+		let f = sub!(num!(0.0), var!()).simplify();
+		assert_eq!(f.to_string(), "-x");
+
+		let f = mul!(num!(1.0), var!()).simplify();
+		assert_eq!(f.to_string(), "x");
+
+		let f = div!(var!(), num!(1.0)).simplify();
+		assert_eq!(f.to_string(), "x");
+
+		let f = pow!(var!(), num!(1.0)).simplify();
+		assert_eq!(f.to_string(), "x");
+
+		let f = pow!(var!(), num!(0.0)).simplify();
+		assert_eq!(f.to_string(), "1");
+
+		// division by zero is not folded
+		let f = div!(num!(1.0), num!(0.0)).simplify();
+		assert_eq!(f.to_string(), "1 / 0");
+
+		let f = pow!(var!(), num!(3.0)).derivative().simplify();
+		assert_eq!(f.to_string(), "3 * x ** 2");
+	}
+
+	#[test]
+	fn test_latex() {
+		let f = div!(add!(var!(), num!(1.0)), pow!(var!(), num!(2.0)));
+		assert_eq!(f.to_latex(), "\\frac{x + 1}{x^{2}}");
+
+		let f = mul!(paren!(add!(var!(), num!(1.0))), div!(num!(1.0), var!()));
+		assert_eq!(f.to_latex(), "\\left(x + 1\\right) \\cdot \\frac{1}{x}");
+
+		let f = sub!(var!(), neg!(num!(2.0)));
+		assert_eq!(f.to_latex(), "x - \\left(-2\\right)");
+
+		let f = pow!(pow!(var!(), num!(2.0)), add!(var!(), num!(1.0)));
+		assert_eq!(f.to_latex(), "\\left(x^{2}\\right)^{x + 1}");
+	}
+
+	#[test]
+	fn test_mathml() {
+		let f = div!(num!(1.0), pow!(var!(), num!(2.0)));
+		assert_eq!(
+			f.to_mathml(),
+			"<mfrac><mn>1</mn><msup><mi>x</mi><mn>2</mn></msup></mfrac>"
+		);
+
+		let f = mul!(num!(2.0), paren!(sub!(var!(), num!(1.0))));
+		assert_eq!(
+			f.to_mathml(),
+			"<mrow><mn>2</mn><mo>⋅</mo><mrow><mo>(</mo><mrow><mi>x</mi><mo>-</mo><mn>1</mn></mrow><mo>)</mo></mrow></mrow>"
+		);
+	}
+
+	#[test]
+	fn test_complex_pow() {
+		// principal cube root
+		let f = pow!(var!(), div!(num!(1.0), num!(3.0)));
+		assert!(f.eval(-8.0f64).is_err());
+		let z = f.eval_complex(Complex::from(-8.0)).unwrap();
+		assert!((z - Complex::new(1.0, 3f64.sqrt())).abs() < 1e-12);
+
+		let f = pow!(num!(0.0), var!());
+		assert_eq!(
+			f.eval_complex(Complex::new(-1.0, 1.0)),
+			Err(EvalError::ZeroToNegativePower(-1.0))
+		);
+	}
+
+	#[test]
+	fn test_comparison() {
+		let cases = [
+			(lt!(var!(), num!(1.0)), [1.0, 0.0, 0.0]),
+			(le!(var!(), num!(1.0)), [1.0, 1.0, 0.0]),
+			(eq!(var!(), num!(1.0)), [0.0, 1.0, 0.0]),
+			(ne!(var!(), num!(1.0)), [1.0, 0.0, 1.0]),
+			(ge!(var!(), num!(1.0)), [0.0, 1.0, 1.0]),
+			(gt!(var!(), num!(1.0)), [0.0, 0.0, 1.0]),
+		];
+		for (f, expected) in cases {
+			for (x, y) in [0.0, 1.0, 2.0].into_iter().zip(expected) {
+				assert_eq!(f.eval(x).unwrap(), y, "{f} at {x}");
+			}
+			assert_eq!(f.derivative().eval(0.0).unwrap(), 0.0);
+		}
+
+		assert_eq!(
+			lt!(var!(), num!(1.0)).eval_complex(Complex::I),
+			Err(EvalError::ComplexArgument("<".to_string()))
+		);
+		let y = lt!(var!(), num!(1.0)).eval_interval(0.0, 2.0).unwrap();
+		assert!(y.is_split() && y.contains(0.0) && y.contains(1.0));
+	}
+
+	#[test]
+	fn test_logical() {
+		let f = and!(gt!(var!(), num!(0.0)), lt!(var!(), num!(2.0)));
+		assert_eq!(f.to_string(), "x > 0 and x < 2");
+		assert_eq!(f.eval(1.0).unwrap(), 1.0);
+		assert_eq!(f.eval(3.0).unwrap(), 0.0);
+
+		let f = or!(var!(), num!(0.0));
+		assert_eq!(f.eval(-2.0).unwrap(), 1.0);
+		assert_eq!(f.eval(0.0).unwrap(), 0.0);
+
+		let f = and!(le!(var!(), num!(1.0)), ne!(var!(), num!(0.0)));
+		assert_eq!(f.to_latex(), "x \\le 1 \\land x \\ne 0");
+		assert_eq!(
+			f.to_mathml(),
+			"<mrow><mrow><mi>x</mi><mo>≤</mo><mn>1</mn></mrow><mo>∧</mo><mrow><mi>x</mi><mo>≠</mo><mn>0</mn></mrow></mrow>"
+		);
+		assert_eq!(f.simplify().to_string(), "x <= 1 and x != 0");
+		assert_eq!(lt!(num!(1.0), num!(2.0)).simplify().to_string(), "1");
+	}
 }
@@ -39,3 +39,59 @@ macro_rules! pow {
 		$crate::binary_expr!("**", $lhs, $rhs).unwrap()
 	};
 }
+
+#[macro_export]
+macro_rules! lt {
+	($lhs:expr, $rhs:expr) => {
+		$crate::binary_expr!("<", $lhs, $rhs).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! le {
+	($lhs:expr, $rhs:expr) => {
+		$crate::binary_expr!("<=", $lhs, $rhs).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! eq {
+	($lhs:expr, $rhs:expr) => {
+		$crate::binary_expr!("==", $lhs, $rhs).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! ne {
+	($lhs:expr, $rhs:expr) => {
+		$crate::binary_expr!("!=", $lhs, $rhs).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! ge {
+	($lhs:expr, $rhs:expr) => {
+		$crate::binary_expr!(">=", $lhs, $rhs).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! gt {
+	($lhs:expr, $rhs:expr) => {
+		$crate::binary_expr!(">", $lhs, $rhs).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! and {
+	($lhs:expr, $rhs:expr) => {
+		$crate::binary_expr!("and", $lhs, $rhs).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! or {
+	($lhs:expr, $rhs:expr) => {
+		$crate::binary_expr!("or", $lhs, $rhs).unwrap()
+	};
+}
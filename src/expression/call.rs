@@ -0,0 +1,260 @@
+use super::{
+	fold, ComplexFunction, Definition, Derivative, Env, EvalError, Expression, Function,
+	IntervalFunction, Scalar, Simplify, Typeset,
+};
+use crate::{
+	div,
+	math::{Complex, Interval},
+	num,
+};
+use std::sync::Arc;
+
+/// Call of a user-defined function.
+///
+/// The call evaluates the body of the definition with the parameters bound to the values of the
+/// arguments, so the definition is shared by the calls instead of being expanded in each.
+///
+/// ```bnf
+/// call ::= id "(" [sub_expr ("," sub_expr)*] ")"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Call {
+	definition: Arc<Definition>,
+	args: Vec<Expression>,
+}
+
+impl Call {
+	/// Create a call of the function.
+	///
+	/// Return `None` if the number of arguments is not that of the parameters.
+	#[must_use]
+	pub fn new(definition: &Arc<Definition>, args: Vec<Expression>) -> Option<Self> {
+		if args.len() != definition.params().len() {
+			return None;
+		}
+
+		Some(Self {
+			definition: Arc::clone(definition),
+			args,
+		})
+	}
+
+	/// The function name.
+	#[must_use]
+	pub fn name(&self) -> &str {
+		self.definition.name()
+	}
+
+	/// The definition of the function.
+	#[must_use]
+	pub fn definition(&self) -> &Arc<Definition> {
+		&self.definition
+	}
+
+	/// The argument expressions.
+	#[must_use]
+	pub fn args(&self) -> &[Expression] {
+		&self.args
+	}
+
+	/// The body with the parameters replaced by the arguments, which is built on each call, so
+	/// its size grows with the nesting of calls.
+	///
+	/// Return `None` if an argument uses x and its parameter is used in an integrand, where x is
+	/// bound as the variable of integration.
+	#[must_use]
+	pub fn expansion(&self) -> Option<Expression> {
+		let bindings: Vec<_> = self
+			.definition
+			.params()
+			.iter()
+			.cloned()
+			.zip(self.args.iter().cloned())
+			.collect();
+		self.definition.body().substitute_all(&bindings)
+	}
+
+	/// Create the call of the same function with the arguments mapped by `f`.
+	pub(crate) fn map<F: FnMut(&Expression) -> Expression>(&self, f: F) -> Self {
+		Self {
+			definition: Arc::clone(&self.definition),
+			args: self.args.iter().map(f).collect(),
+		}
+	}
+
+	/// Evaluate the body with the parameters bound to the arguments evaluated by `eval`.
+	fn eval_body<T: Clone, F, G>(&self, mut eval: F, body: G) -> Result<T, EvalError>
+	where
+		F: FnMut(&Expression) -> Result<T, EvalError>,
+		G: FnOnce(&Expression, &Env<T>) -> Result<T, EvalError>,
+	{
+		let mut env = Env::new();
+		for (param, arg) in self.definition.params().iter().zip(&self.args) {
+			env.set(param, eval(arg)?);
+		}
+		body(self.definition.body(), &env)
+	}
+}
+
+impl std::fmt::Display for Call {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}(", self.name())?;
+		for (i, arg) in self.args.iter().enumerate() {
+			if i > 0 {
+				write!(f, ", ")?;
+			}
+			write!(f, "{arg}")?;
+		}
+		write!(f, ")")
+	}
+}
+
+impl Function for Call {
+	fn eval_with<T: Scalar>(&self, env: &Env<T>) -> Result<T, EvalError> {
+		self.eval_body(|arg| arg.eval_with(env), Expression::eval_with)
+	}
+}
+
+impl ComplexFunction for Call {
+	fn eval_complex_with(&self, env: &Env<Complex>) -> Result<Complex, EvalError> {
+		self.eval_body(
+			|arg| arg.eval_complex_with(env),
+			Expression::eval_complex_with,
+		)
+	}
+}
+
+impl IntervalFunction for Call {
+	fn eval_interval_with(&self, env: &Env<Interval>) -> Result<Interval, EvalError> {
+		self.eval_body(
+			|arg| arg.eval_interval_with(env),
+			Expression::eval_interval_with,
+		)
+	}
+}
+
+impl Derivative for Call {
+	/// The arguments of the parameters that the body uses do not depend on x.
+	fn is_constant(&self) -> bool {
+		let body = self.definition.body();
+		self.definition
+			.params()
+			.iter()
+			.zip(&self.args)
+			.all(|(param, arg)| !body.uses(param) || arg.is_constant())
+	}
+
+	/// Derivative of the expansion, which is built on demand.
+	///
+	/// The derivative is undefined, i.e., `0 / 0`, if the call cannot be expanded, since
+	/// differentiation under the integral sign is not supported.
+	fn derivative(&self) -> Expression {
+		self.expansion().map_or_else(
+			|| div!(num!(0.0), num!(0.0)).into(),
+			|expansion| expansion.derivative(),
+		)
+	}
+}
+
+impl Simplify for Call {
+	/// Keep the call with the arguments simplified, or fold it if constant.
+	fn simplify(&self) -> Expression {
+		fold(self.map(Simplify::simplify).into())
+	}
+}
+
+impl Typeset for Call {
+	fn to_latex(&self) -> String {
+		let args: Vec<_> = self.args.iter().map(Typeset::to_latex).collect();
+		// single letters are italic in math mode, longer names are set as operators
+		if self.name().len() == 1 {
+			format!("{}\\left({}\\right)", self.name(), args.join(", "))
+		} else {
+			format!(
+				"\\operatorname{{{}}}\\left({}\\right)",
+				self.name().replace('_', "\\_"),
+				args.join(", ")
+			)
+		}
+	}
+
+	fn to_mathml(&self) -> String {
+		let args: Vec<_> = self.args.iter().map(Typeset::to_mathml).collect();
+		format!(
+			"<mrow><mi>{}</mi><mo>&#x2061;</mo><mrow><mo>(</mo>{}<mo>)</mo></mrow></mrow>",
+			self.name(),
+			args.join("<mo>,</mo>")
+		)
+	}
+}
+
+impl From<Call> for Expression {
+	fn from(val: Call) -> Self {
+		Expression::Call(val.into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		add,
+		expression::{parse_program, CompiledExpr},
+		pow, var,
+	};
+
+	#[test]
+	fn test_call() {
+		// f(x) = x ** 2 + 1
+		let f = Arc::new(Definition::new(
+			"f",
+			&["x"],
+			add!(pow!(var!(), num!(2.0)), num!(1.0)),
+		));
+		let call = Call::new(&f, vec![add!(var!(), num!(1.0)).into()]).unwrap();
+		assert_eq!(call.to_string(), "f(x + 1)");
+		assert_eq!(call.expansion().unwrap().to_string(), "(x + 1) ** 2 + 1");
+		assert_eq!(call.eval(1.0).unwrap(), 5.0);
+		assert_eq!(call.derivative().simplify().eval(1.0).unwrap(), 4.0);
+		assert_eq!(call.to_latex(), "f\\left(x + 1\\right)");
+
+		assert!(Call::new(&f, Vec::new()).is_none());
+	}
+
+	#[test]
+	fn test_nested_call() {
+		let depth = 30;
+		let program = format!("f(t) = t + t; {}x{}", "f(".repeat(depth), ")".repeat(depth));
+		let f = Expression::from(parse_program(program).unwrap());
+		assert_eq!(f.eval(1.0f64).unwrap(), 2f64.powi(30));
+
+		// the definition is shared by the calls
+		assert!(format!("{f:?}").len() < 200 * depth);
+		let Expression::Call(outer) = &f else {
+			panic!("{f:?} is not a call");
+		};
+		let Expression::Call(inner) = &outer.args()[0] else {
+			panic!("{f:?} is not nested");
+		};
+		assert!(Arc::ptr_eq(outer.definition(), inner.definition()));
+		assert!(!f.is_constant());
+	}
+
+	#[test]
+	fn test_captured_argument() {
+		let f = Expression::from(parse_program("f(t) = integral(t * x, 0, 1); f(x)").unwrap());
+		assert!((f.eval(2.0f64).unwrap() - 1.0).abs() < 1e-10);
+		assert!((CompiledExpr::<f64>::new(&f).eval(2.0).unwrap() - 1.0).abs() < 1e-10);
+
+		// x of the argument would be bound by the integral
+		let Expression::Call(call) = &f else {
+			panic!("{f:?} is not a call");
+		};
+		assert!(call.expansion().is_none());
+		assert!(f.derivative().eval(2.0f64).is_err());
+
+		let f = Expression::from(parse_program("f(t) = integral(t * x, 0, t); f(2)").unwrap());
+		assert_eq!(f.derivative().simplify().to_string(), "0");
+	}
+}
@@ -0,0 +1,443 @@
+//! Compilation to a flat stack-machine program for batch evaluation.
+
+use super::{
+	integral::quadrature, Conditional, Custom, Definition, Env, EvalError, Expression, Factorial,
+	Function, Scalar, Unary,
+};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy)]
+enum Instruction<T> {
+	/// Push a constant.
+	Push(T),
+	/// Push the input of the index, e.g., x.
+	Load(usize),
+	/// Stop with the error of the index, e.g., for a variable bound by neither the input nor the
+	/// environment.
+	Fail(usize),
+	/// Negate the top value.
+	Neg,
+	/// Replace the top value with its logical negation.
+	Not,
+	/// Pop the condition and jump to the index if it is false.
+	JumpIfFalse(usize),
+	/// Jump to the index.
+	Jump(usize),
+	/// Replace the top value with the function of it.
+	Call(fn(T) -> Result<T, EvalError>),
+	/// Replace the top two values with the function of them.
+	Call2(fn(T, T) -> Result<T, EvalError>),
+	/// Replace the top values with the registered function of the index.
+	CallCustom(usize),
+	/// Replace the top two values, i.e., the bounds, with the integral of the index.
+	Integrate(usize),
+	/// Replace the top values, i.e., the arguments, with the user function of the index.
+	Invoke(usize),
+}
+
+/// Expression compiled to a flat program, which evaluates in the scalar type `T` without walking
+/// the tree.
+///
+/// ```
+/// use rsap::expression::{parse, CompiledExpr};
+///
+/// let f = CompiledExpr::new(&parse("1 / x").unwrap());
+/// let mut ys = [None; 3];
+/// f.eval_many(&[-2.0, 0.0, 2.0], &mut ys);
+/// assert_eq!(ys, [Some(-0.5), None, Some(0.5)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompiledExpr<T = f32> {
+	program: Vec<Instruction<T>>,
+	/// names of the inputs, which are at the bottom of the stack
+	inputs: Vec<String>,
+	/// max stack height while running the program
+	depth: usize,
+	/// errors of the nodes that are undefined for any input
+	errors: Vec<EvalError>,
+	/// calls of registered functions
+	customs: Vec<Custom>,
+	/// integrands of the integrals in f64, whose inputs are the variable of integration x followed
+	/// by the inputs of this program
+	integrals: Vec<CompiledExpr<f64>>,
+	/// bodies of the user functions, whose inputs are the parameters
+	bodies: Vec<(Arc<Definition>, CompiledExpr<T>)>,
+}
+
+impl<T: Scalar> CompiledExpr<T> {
+	#[must_use]
+	pub fn new(expr: &Expression) -> Self {
+		Self::with_env(expr, &Env::new())
+	}
+
+	/// Compile with the variables other than x bound by the environment.
+	#[must_use]
+	pub fn with_env(expr: &Expression, env: &Env<T>) -> Self {
+		Self::with_inputs(expr, env, vec!["x".to_string()])
+	}
+
+	/// Compile with the named inputs, where the other variables are bound by the environment.
+	fn with_inputs(expr: &Expression, env: &Env<T>, inputs: Vec<String>) -> Self {
+		let mut compiled = Self {
+			program: Vec::new(),
+			depth: inputs.len(),
+			inputs,
+			errors: Vec::new(),
+			customs: Vec::new(),
+			integrals: Vec::new(),
+			bodies: Vec::new(),
+		};
+		compiled.compile(expr, env, compiled.inputs.len());
+		compiled
+	}
+
+	/// Emit the program of the expression, where `height` is the stack height before it runs.
+	fn compile(&mut self, expr: &Expression, env: &Env<T>, height: usize) {
+		self.depth = self.depth.max(height + 1);
+
+		let instruction = match expr {
+			Expression::Number(e) => Instruction::Push(T::from_f64(e.value())),
+			Expression::Variable(e) if self.inputs.iter().any(|name| name == e.name()) => {
+				Instruction::Load(
+					self.inputs
+						.iter()
+						.position(|name| name == e.name())
+						.unwrap(),
+				)
+			}
+			Expression::Constant(_) | Expression::Variable(_) => match expr.eval_with(env) {
+				Ok(val) => Instruction::Push(val),
+				Err(err) => {
+					self.errors.push(err);
+					Instruction::Fail(self.errors.len() - 1)
+				}
+			},
+			Expression::Unary(e) => match e.as_ref() {
+				Unary::Neg(expr) => {
+					self.compile(expr, env, height);
+					Instruction::Neg
+				}
+				Unary::Not(expr) => {
+					self.compile(expr, env, height);
+					Instruction::Not
+				}
+			},
+			Expression::Parenthesis(e) => return self.compile(e.inner(), env, height),
+			Expression::Call(e) => {
+				for (i, arg) in e.args().iter().enumerate() {
+					self.compile(arg, env, height + i);
+				}
+				let i = self.body(e.definition());
+				self.depth = self.depth.max(height + self.bodies[i].1.depth);
+				Instruction::Invoke(i)
+			}
+			Expression::Conditional(e) => return self.compile_conditional(e, env, height),
+			Expression::Binary(e) => {
+				let (lhs, rhs) = e.operands();
+				self.compile(lhs, env, height);
+				self.compile(rhs, env, height + 1);
+				Instruction::Call2(e.scalar_fn())
+			}
+			Expression::Factorial(e) => {
+				self.compile(e.arg(), env, height);
+				Instruction::Call(Factorial::value)
+			}
+			Expression::Func(e) => {
+				self.compile(e.arg(), env, height);
+				Instruction::Call(e.scalar_fn())
+			}
+			Expression::Func2(e) => {
+				let (lhs, rhs) = e.args();
+				self.compile(lhs, env, height);
+				self.compile(rhs, env, height + 1);
+				Instruction::Call2(e.scalar_fn())
+			}
+			Expression::Custom(e) => {
+				for (i, arg) in e.args().iter().enumerate() {
+					self.compile(arg, env, height + i);
+				}
+				self.customs.push(e.as_ref().clone());
+				Instruction::CallCustom(self.customs.len() - 1)
+			}
+			Expression::Integral(e) => {
+				let (lower, upper) = e.bounds();
+				self.compile(lower, env, height);
+				self.compile(upper, env, height + 1);
+				let inputs = std::iter::once("x".to_string())
+					.chain(self.inputs.iter().cloned())
+					.collect();
+				self.integrals.push(CompiledExpr::with_inputs(
+					e.integrand(),
+					&env.map(|v| v.to_f64()),
+					inputs,
+				));
+				Instruction::Integrate(self.integrals.len() - 1)
+			}
+		};
+
+		self.program.push(instruction);
+	}
+
+	/// The index of the compiled body of the user function, which is compiled once per definition.
+	fn body(&mut self, definition: &Arc<Definition>) -> usize {
+		if let Some(i) = self
+			.bodies
+			.iter()
+			.position(|(other, _)| Arc::ptr_eq(other, definition))
+		{
+			return i;
+		}
+
+		// the body only uses the parameters
+		let body =
+			CompiledExpr::with_inputs(definition.body(), &Env::new(), definition.params().to_vec());
+		self.bodies.push((Arc::clone(definition), body));
+		self.bodies.len() - 1
+	}
+
+	/// Emit the program of the conditional, which only runs the chosen branch.
+	fn compile_conditional(&mut self, expr: &Conditional, env: &Env<T>, height: usize) {
+		self.compile(expr.condition(), env, height);
+		let branch = self.program.len();
+		self.program.push(Instruction::JumpIfFalse(0));
+
+		self.compile(expr.then(), env, height);
+		let end = self.program.len();
+		self.program.push(Instruction::Jump(0));
+
+		// patch the jumps once their targets are known
+		self.program[branch] = Instruction::JumpIfFalse(self.program.len());
+		if let Some(otherwise) = expr.otherwise() {
+			self.compile(otherwise, env, height);
+		} else {
+			self.errors.push(EvalError::FalseCondition);
+			self.program.push(Instruction::Fail(self.errors.len() - 1));
+		}
+		self.program[end] = Instruction::Jump(self.program.len());
+	}
+
+	/// Evaluate the program for the input x.
+	///
+	/// # Errors
+	///
+	/// Return the error of the first undefined node if x is not in the definition domain or a
+	/// variable is unbound.
+	pub fn eval(&self, x: T) -> Result<T, EvalError> {
+		self.run(x, &mut Vec::with_capacity(self.depth))
+	}
+
+	/// Evaluate the program for each input in `xs`, writing the results to `ys`, where `None`
+	/// means the input is not in the definition domain.
+	///
+	/// # Panics
+	///
+	/// Panics if `xs` and `ys` have different lengths.
+	pub fn eval_many(&self, xs: &[T], ys: &mut [Option<T>]) {
+		assert_eq!(xs.len(), ys.len(), "input and output lengths differ");
+
+		let mut stack = Vec::with_capacity(self.depth);
+		for (x, y) in xs.iter().zip(ys) {
+			*y = self.run(*x, &mut stack).ok();
+		}
+	}
+
+	fn run(&self, x: T, stack: &mut Vec<T>) -> Result<T, EvalError> {
+		stack.clear();
+		stack.push(x);
+		self.run_frame(stack, 0)
+	}
+
+	/// Run the program on the stack, whose inputs start at `base`, and pop the result.
+	fn run_frame(&self, stack: &mut Vec<T>, base: usize) -> Result<T, EvalError> {
+		let mut pc = 0;
+		while let Some(instruction) = self.program.get(pc) {
+			pc += 1;
+			match *instruction {
+				Instruction::Push(val) => stack.push(val),
+				Instruction::Load(i) => stack.push(stack[base + i]),
+				Instruction::Fail(i) => return Err(self.errors[i].clone()),
+				// the program is well-formed, so the operands are on the stack
+				Instruction::Neg => {
+					let val = stack.last_mut().unwrap();
+					*val = -*val;
+				}
+				Instruction::Not => {
+					let val = stack.last_mut().unwrap();
+					*val = T::from_bool(!val.is_true());
+				}
+				Instruction::JumpIfFalse(target) => {
+					if !stack.pop().unwrap().is_true() {
+						pc = target;
+					}
+				}
+				Instruction::Jump(target) => pc = target,
+				Instruction::Call(f) => {
+					let val = stack.last_mut().unwrap();
+					*val = f(*val)?;
+				}
+				Instruction::Call2(f) => {
+					let rhs = stack.pop().unwrap();
+					let lhs = stack.last_mut().unwrap();
+					*lhs = f(*lhs, rhs)?;
+				}
+				Instruction::CallCustom(i) => {
+					let custom = &self.customs[i];
+					let args: Vec<_> = stack
+						.drain(stack.len() - custom.args().len()..)
+						.map(Scalar::to_f64)
+						.collect();
+					stack.push(T::from_f64(custom.apply(&args)?));
+				}
+				Instruction::Integrate(i) => {
+					let integrand = &self.integrals[i];
+					let upper = stack.pop().unwrap().to_f64();
+					let lower = stack.pop().unwrap().to_f64();
+
+					let frame = &stack[base..base + self.inputs.len()];
+					let mut inputs: Vec<_> = std::iter::once(0.0)
+						.chain(frame.iter().map(|v| v.to_f64()))
+						.collect();
+					let mut inner = Vec::with_capacity(integrand.depth);
+					let value = quadrature(
+						|t| {
+							inputs[0] = t;
+							inner.clear();
+							inner.extend_from_slice(&inputs);
+							integrand
+								.run_frame(&mut inner, 0)
+								.ok()
+								.filter(|y| y.is_finite())
+						},
+						lower,
+						upper,
+					)?;
+					stack.push(T::from_f64(value));
+				}
+				Instruction::Invoke(i) => {
+					let body = &self.bodies[i].1;
+					let args = stack.len() - body.inputs.len();
+					let value = body.run_frame(stack, args)?;
+					stack.truncate(args);
+					stack.push(value);
+				}
+			}
+		}
+
+		Ok(stack.pop().unwrap())
+	}
+}
+
+impl<T: Scalar> From<&Expression> for CompiledExpr<T> {
+	fn from(expr: &Expression) -> Self {
+		Self::new(expr)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::expression::parse_with;
+
+	/// Compare with the tree walker on a grid over [-10, 10].
+	fn assert_same<T: Scalar>(expr: &str) {
+		let f = parse_with(expr, &["x", "t"]).unwrap();
+		let env = Env::new().with("t", T::from_f64(0.5));
+		let compiled = CompiledExpr::with_env(&f, &env);
+
+		let xs: Vec<T> = (-1000..=1000)
+			.map(|i| T::from_f64(f64::from(i) * 0.01))
+			.collect();
+		let mut ys = vec![None; xs.len()];
+		compiled.eval_many(&xs, &mut ys);
+
+		for (x, y) in xs.into_iter().zip(ys) {
+			let expected = f.eval_with(&env.clone().with("x", x));
+			assert_eq!(
+				compiled.eval(x).map(|v| v.to_f64().to_bits()),
+				expected.clone().map(|v| v.to_f64().to_bits()),
+				"{expr} at x = {x}"
+			);
+			assert_eq!(
+				y.map(|v| v.to_f64().to_bits()),
+				expected.ok().map(|v| v.to_f64().to_bits())
+			);
+		}
+	}
+
+	#[test]
+	fn test_same_as_tree() {
+		for expr in [
+			"1 + 2 * x - 3 / x",
+			"-x ** 2 + 2 ** -x",
+			"(x - 1) ** 0.5 * t",
+			"sin(x) + cos(x) * tan(x) - cot(x) / sec(x) ** csc(x)",
+			"arcsin(x / 10) + arccos(x / 10) + arctan(x) + arccot(x)",
+			"ln(x) - sqrt(pi - x) + e * 3!",
+			"log(x, 8) + log(2, -x)",
+			"0 ** x + x ** 0 + (-8) ** (1 / 3)",
+			"abs(x) + floor(x) * ceil(x) - round(x) + sign(x) + exp(x)",
+			"sinh(x) - cosh(x) * tanh(x) + arcsinh(x) + arccosh(x) + arctanh(x)",
+			"log10(x) - log2(x) + min(x, t) + max(x, t) + atan2(x, t) + hypot(x, t)",
+			"mod(x, t) + mod(t, x) + root(3, x) - root(x, t)",
+			"(x < t) + (x <= 0) * (x == 1) - (x != t) + (x >= 2 or x > 3 and not x < 4)",
+			"if(x < 0, -x, x ** 2) + if(x > 1, sqrt(x - 1)) * if(x, 1 / x, t)",
+			"integral(sin(x) * t, x, 1) - integral(sqrt(x), 0, x)",
+			"x! + (x / 2 - t)!! - polygamma(0, x) * polygamma(1, t - x)",
+		] {
+			assert_same::<f32>(expr);
+			assert_same::<f64>(expr);
+		}
+	}
+
+	#[test]
+	fn test_call() {
+		use crate::expression::parse_program;
+
+		let f = Expression::from(
+			parse_program(
+				"f(a, b) = a * b - integral(a * x, 0, b); g(t) = f(t, t + 1) / 2; g(g(x)) + f(2, x)",
+			)
+			.unwrap(),
+		);
+		let compiled = CompiledExpr::<f64>::new(&f);
+		for x in [-2.0, -0.5, 0.0, 1.0, 3.0] {
+			let expected = f.eval(x).unwrap();
+			assert!(
+				(compiled.eval(x).unwrap() - expected).abs() < 1e-9,
+				"at x = {x}"
+			);
+		}
+	}
+
+	#[test]
+	fn test_registered() {
+		use crate::expression::{CustomFunction, FunctionRegistry, Parser};
+
+		let registry = FunctionRegistry::new().with(
+			CustomFunction::new("f", 2, |args| args[0] / args[1], |_| Vec::new())
+				.with_domain(|args| args[1] != 0.0),
+		);
+		let f = Parser::new("1 + f(x, x - 1) * 2")
+			.with_registry(&registry)
+			.parse()
+			.unwrap();
+		let compiled = CompiledExpr::<f64>::new(&f);
+		assert_eq!(compiled.eval(2.0), Ok(5.0));
+		assert_eq!(
+			compiled.eval(1.0),
+			Err(EvalError::OutOfDomain("f".to_string(), vec![1.0, 0.0]))
+		);
+	}
+
+	#[test]
+	fn test_unbound() {
+		let f = parse_with("x + t", &["x", "t"]).unwrap();
+		assert_eq!(
+			CompiledExpr::new(&f).eval(1.0f32),
+			Err(EvalError::UnboundVariable("t".to_string()))
+		);
+
+		let compiled = CompiledExpr::with_env(&f, &Env::new().with("t", 2.0));
+		assert_eq!(compiled.eval(1.0), Ok(3.0));
+	}
+}
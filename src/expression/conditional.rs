@@ -0,0 +1,252 @@
+use super::{
+	ComplexFunction, Derivative, Env, EvalError, Expression, Function, IntervalFunction, Scalar,
+	Simplify, Typeset,
+};
+use crate::math::{Complex, Interval};
+
+/// Name of the conditional expression.
+pub const IF: &str = "if";
+
+/// Conditional expression, which evaluates only the branch chosen by the condition.
+///
+/// Without the else branch, the expression is undefined where the condition is false, e.g.,
+/// `if(x > 0, x ** 2)` restricts `x ** 2` to the positive numbers. Nested conditionals express
+/// functions of more pieces, e.g., `if(x < 0, -x, if(x < 1, x ** 2, 1))`.
+///
+/// ```bnf
+/// conditional ::= "if" "(" sub_expr "," sub_expr ["," sub_expr] ")"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Conditional {
+	condition: Expression,
+	then: Expression,
+	otherwise: Option<Expression>,
+}
+
+impl Conditional {
+	pub fn new<C: Into<Expression>, T: Into<Expression>, O: Into<Expression>>(
+		condition: C,
+		then: T,
+		otherwise: Option<O>,
+	) -> Self {
+		Self {
+			condition: condition.into(),
+			then: then.into(),
+			otherwise: otherwise.map(Into::into),
+		}
+	}
+
+	#[must_use]
+	pub fn condition(&self) -> &Expression {
+		&self.condition
+	}
+
+	/// The branch where the condition is true, i.e., non-zero.
+	#[must_use]
+	pub fn then(&self) -> &Expression {
+		&self.then
+	}
+
+	/// The branch where the condition is false, if any.
+	#[must_use]
+	pub fn otherwise(&self) -> Option<&Expression> {
+		self.otherwise.as_ref()
+	}
+
+	/// Create the conditional with each part mapped by `f`.
+	pub(crate) fn map<F: FnMut(&Expression) -> Expression>(&self, mut f: F) -> Self {
+		Self {
+			condition: f(&self.condition),
+			then: f(&self.then),
+			otherwise: self.otherwise.as_ref().map(f),
+		}
+	}
+}
+
+#[macro_export]
+macro_rules! conditional {
+	($condition:expr, $then:expr) => {
+		$crate::expression::Conditional::new(
+			$condition,
+			$then,
+			None::<$crate::expression::Expression>,
+		)
+	};
+	($condition:expr, $then:expr, $otherwise:expr) => {
+		$crate::expression::Conditional::new($condition, $then, Some($otherwise))
+	};
+}
+
+impl std::fmt::Display for Conditional {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{IF}({}, {}", self.condition, self.then)?;
+		if let Some(otherwise) = &self.otherwise {
+			write!(f, ", {otherwise}")?;
+		}
+		write!(f, ")")
+	}
+}
+
+impl Function for Conditional {
+	fn eval_with<T: Scalar>(&self, env: &Env<T>) -> Result<T, EvalError> {
+		if self.condition.eval_with(env)?.is_true() {
+			self.then.eval_with(env)
+		} else if let Some(otherwise) = &self.otherwise {
+			otherwise.eval_with(env)
+		} else {
+			Err(EvalError::FalseCondition)
+		}
+	}
+}
+
+impl ComplexFunction for Conditional {
+	fn eval_complex_with(&self, env: &Env<Complex>) -> Result<Complex, EvalError> {
+		if !self.condition.eval_complex_with(env)?.is_zero() {
+			self.then.eval_complex_with(env)
+		} else if let Some(otherwise) = &self.otherwise {
+			otherwise.eval_complex_with(env)
+		} else {
+			Err(EvalError::FalseCondition)
+		}
+	}
+}
+
+impl IntervalFunction for Conditional {
+	fn eval_interval_with(&self, env: &Env<Interval>) -> Result<Interval, EvalError> {
+		let condition = self.condition.eval_interval_with(env)?;
+
+		// the branches that may be taken
+		let mut result = Interval::empty();
+		if condition.can_be_true() {
+			result = result.union(&self.then.eval_interval_with(env)?);
+		}
+		if let Some(otherwise) = self.otherwise.as_ref().filter(|_| condition.can_be_false()) {
+			result = result.union(&otherwise.eval_interval_with(env)?);
+		}
+		Ok(result)
+	}
+}
+
+impl Derivative for Conditional {
+	fn is_constant(&self) -> bool {
+		self.condition.is_constant()
+			&& self.then.is_constant()
+			&& self.otherwise.as_ref().is_none_or(Derivative::is_constant)
+	}
+
+	/// Derivative of each branch, which is not defined at the jumps.
+	fn derivative(&self) -> Expression {
+		Self {
+			condition: self.condition.clone(),
+			then: self.then.derivative(),
+			otherwise: self.otherwise.as_ref().map(Derivative::derivative),
+		}
+		.into()
+	}
+}
+
+impl Simplify for Conditional {
+	fn simplify(&self) -> Expression {
+		let simplified = self.map(Simplify::simplify);
+
+		// a constant condition chooses the branch
+		match simplified.condition.constant_value() {
+			Some(v) if v != 0.0 => simplified.then,
+			Some(_) if simplified.otherwise.is_some() => simplified.otherwise.unwrap(),
+			_ => simplified.into(),
+		}
+	}
+}
+
+impl Typeset for Conditional {
+	fn to_latex(&self) -> String {
+		let otherwise = self.otherwise.as_ref().map_or(String::new(), |otherwise| {
+			format!(" \\\\ {} & \\text{{otherwise}}", otherwise.to_latex())
+		});
+		format!(
+			"\\begin{{cases}} {} & \\text{{if }} {}{otherwise} \\end{{cases}}",
+			self.then.to_latex(),
+			self.condition.to_latex()
+		)
+	}
+
+	fn to_mathml(&self) -> String {
+		let otherwise = self.otherwise.as_ref().map_or(String::new(), |otherwise| {
+			format!(
+				"<mtr><mtd>{}</mtd><mtd><mtext>otherwise</mtext></mtd></mtr>",
+				otherwise.to_mathml()
+			)
+		});
+		format!(
+			"<mrow><mo>{{</mo><mtable><mtr><mtd>{}</mtd><mtd><mtext>if </mtext>{}</mtd></mtr>{otherwise}</mtable></mrow>",
+			self.then.to_mathml(),
+			self.condition.to_mathml()
+		)
+	}
+}
+
+impl From<Conditional> for Expression {
+	fn from(val: Conditional) -> Self {
+		Expression::Conditional(val.into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{gt, lt, neg, num, pow, sqrt, var};
+
+	#[test]
+	fn test_conditional() {
+		// |x| with a parabola on the right
+		let f = conditional!(
+			lt!(var!(), num!(0.0)),
+			neg!(var!()),
+			pow!(var!(), num!(2.0))
+		);
+		assert_eq!(f.to_string(), "if(x < 0, -x, x ** 2)");
+		assert_eq!(f.eval(-2.0).unwrap(), 2.0);
+		assert_eq!(f.eval(3.0).unwrap(), 9.0);
+		assert_eq!(f.derivative().eval(-2.0).unwrap(), -1.0);
+		assert_eq!(f.derivative().eval(3.0).unwrap(), 6.0);
+
+		// the other branch is not evaluated
+		let f = conditional!(gt!(var!(), num!(0.0)), sqrt!(var!()));
+		assert_eq!(f.eval(4.0).unwrap(), 2.0);
+		assert_eq!(f.eval(-4.0), Err(EvalError::FalseCondition));
+		assert!(f.eval_interval(-2.0, -1.0).unwrap().is_empty());
+		let y = f.eval_interval(-1.0, 4.0).unwrap();
+		assert!(y.contains(0.0) && y.contains(2.0));
+	}
+
+	#[test]
+	fn test_conditional_interval() {
+		// a jump at 0
+		let f = conditional!(lt!(var!(), num!(0.0)), num!(-1.0), num!(1.0));
+		let y = f.eval_interval(-1.0, 1.0).unwrap();
+		assert!(y.is_split());
+		assert_eq!(f.eval_interval(1.0, 2.0).unwrap(), Interval::point(1.0));
+	}
+
+	#[test]
+	fn test_conditional_simplify() {
+		let f = conditional!(lt!(num!(1.0), num!(2.0)), var!(), num!(0.0));
+		assert_eq!(f.simplify().to_string(), "x");
+		let f = conditional!(gt!(num!(1.0), num!(2.0)), var!());
+		assert_eq!(f.simplify().to_string(), "if(0, x)");
+	}
+
+	#[test]
+	fn test_conditional_typeset() {
+		let f = conditional!(lt!(var!(), num!(0.0)), neg!(var!()), var!());
+		assert_eq!(
+			f.to_latex(),
+			"\\begin{cases} -x & \\text{if } x < 0 \\\\ x & \\text{otherwise} \\end{cases}"
+		);
+		assert_eq!(
+			f.to_mathml(),
+			"<mrow><mo>{</mo><mtable><mtr><mtd><mrow><mo>-</mo><mi>x</mi></mrow></mtd><mtd><mtext>if </mtext><mrow><mi>x</mi><mo>&lt;</mo><mn>0</mn></mrow></mtd></mtr><mtr><mtd><mi>x</mi></mtd><mtd><mtext>otherwise</mtext></mtd></mtr></mtable></mrow>"
+		);
+	}
+}
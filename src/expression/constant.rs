@@ -1,15 +1,24 @@
-use super::{Expression, Function};
-use std::f32::consts::{E, PI};
+use super::{
+	ComplexFunction, Derivative, Env, EvalError, Expression, Function, IntervalFunction, Scalar,
+	Simplify, Typeset,
+};
+use crate::{
+	math::{Complex, Interval},
+	num,
+};
 
 /// Constant expression.
 ///
 /// ```bnf
-/// constant ::= "e" | "pi"
+/// constant ::= "e" | "pi" | "i"
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Constant {
 	E,
 	PI,
+	/// Imaginary unit, which is only defined in complex evaluation.
+	I,
 }
 
 impl Constant {
@@ -17,6 +26,7 @@ impl Constant {
 		Some(match s.as_ref() {
 			"e" => Self::E,
 			"pi" => Self::PI,
+			"i" => Self::I,
 			_ => return None,
 		})
 	}
@@ -37,20 +47,72 @@ impl std::fmt::Display for Constant {
 			match self {
 				Self::E => "e",
 				Self::PI => "pi",
+				Self::I => "i",
 			}
 		)
 	}
 }
 
 impl Function for Constant {
-	fn eval(&self, _x: f32) -> Option<f32> {
-		Some(match self {
-			Self::E => E,
-			Self::PI => PI,
+	fn eval_with<T: Scalar>(&self, _env: &Env<T>) -> Result<T, EvalError> {
+		match self {
+			Self::E => Ok(T::E),
+			Self::PI => Ok(T::PI),
+			Self::I => Err(EvalError::ImaginaryUnit),
+		}
+	}
+}
+
+impl ComplexFunction for Constant {
+	fn eval_complex_with(&self, _env: &Env<Complex>) -> Result<Complex, EvalError> {
+		Ok(match self {
+			Self::E => Complex::from(std::f64::consts::E),
+			Self::PI => Complex::from(std::f64::consts::PI),
+			Self::I => Complex::I,
 		})
 	}
 }
 
+impl IntervalFunction for Constant {
+	fn eval_interval_with(&self, _env: &Env<Interval>) -> Result<Interval, EvalError> {
+		// the exact value is within an ulp of the rounded one
+		let val = self.eval_with(&Env::<f64>::new())?;
+		Ok(Interval::new(val.next_down(), val.next_up()))
+	}
+}
+
+impl Derivative for Constant {
+	fn derivative(&self) -> Expression {
+		num!(0.0).into()
+	}
+}
+
+impl Simplify for Constant {
+	fn simplify(&self) -> Expression {
+		self.clone().into()
+	}
+}
+
+impl Typeset for Constant {
+	fn to_latex(&self) -> String {
+		match self {
+			Self::E => "e",
+			Self::PI => "\\pi",
+			Self::I => "i",
+		}
+		.to_string()
+	}
+
+	fn to_mathml(&self) -> String {
+		match self {
+			Self::E => "<mi>e</mi>",
+			Self::PI => "<mi>π</mi>",
+			Self::I => "<mi>i</mi>",
+		}
+		.to_string()
+	}
+}
+
 impl From<Constant> for Expression {
 	fn from(val: Constant) -> Self {
 		Expression::Constant(val)
@@ -60,6 +122,7 @@ impl From<Constant> for Expression {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use std::f32::consts::{E, PI};
 
 	#[test]
 	fn test_constant() {
@@ -71,4 +134,18 @@ mod tests {
 		assert_eq!(pi.eval(0.0).unwrap(), PI);
 		assert_eq!(pi.eval(1.0).unwrap(), PI);
 	}
+
+	#[test]
+	fn test_constant_typeset() {
+		let pi = constant!("pi").unwrap();
+		assert_eq!(pi.to_latex(), "\\pi");
+		assert_eq!(pi.to_mathml(), "<mi>π</mi>");
+	}
+
+	#[test]
+	fn test_imaginary_unit() {
+		let i = constant!("i").unwrap();
+		assert_eq!(i.eval(0.0f32), Err(EvalError::ImaginaryUnit));
+		assert_eq!(i.eval_complex(Complex::ZERO).unwrap(), Complex::I);
+	}
 }
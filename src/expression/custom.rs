@@ -0,0 +1,291 @@
+use super::{
+	fold, ComplexFunction, CustomFunction, Derivative, Env, EvalError, Expression, Function,
+	IntervalFunction, Scalar, Simplify, Typeset,
+};
+use crate::{
+	add,
+	math::{Complex, Interval},
+	mul, num,
+};
+use std::sync::Arc;
+
+/// Call of a function in a [`FunctionRegistry`](super::FunctionRegistry).
+///
+/// ```bnf
+/// custom ::= id "(" [sub_expr ("," sub_expr)*] ")"
+/// ```
+#[derive(Debug, Clone)]
+pub struct Custom {
+	function: Arc<CustomFunction>,
+	args: Vec<Expression>,
+}
+
+impl Custom {
+	/// Create a call of the function.
+	///
+	/// Return `None` if the number of arguments is not the arity.
+	#[must_use]
+	pub fn new(function: &Arc<CustomFunction>, args: Vec<Expression>) -> Option<Self> {
+		(args.len() == function.arity()).then(|| Self {
+			function: Arc::clone(function),
+			args,
+		})
+	}
+
+	/// The called function.
+	#[must_use]
+	pub fn function(&self) -> &CustomFunction {
+		&self.function
+	}
+
+	/// The function name.
+	#[must_use]
+	pub fn name(&self) -> &str {
+		self.function.name()
+	}
+
+	/// The argument expressions.
+	#[must_use]
+	pub fn args(&self) -> &[Expression] {
+		&self.args
+	}
+
+	/// The value of the function, or an error if the values are not in the domain.
+	pub(crate) fn apply(&self, args: &[f64]) -> Result<f64, EvalError> {
+		if self.function.is_defined(args) {
+			Ok(self.function.value(args))
+		} else {
+			Err(EvalError::OutOfDomain(
+				self.name().to_string(),
+				args.to_vec(),
+			))
+		}
+	}
+
+	/// Create the call of the same function with the arguments mapped by `f`.
+	pub(crate) fn map_args<F: FnMut(&Expression) -> Expression>(&self, f: F) -> Self {
+		Self {
+			function: Arc::clone(&self.function),
+			args: self.args.iter().map(f).collect(),
+		}
+	}
+}
+
+impl std::fmt::Display for Custom {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}(", self.name())?;
+		for (i, arg) in self.args.iter().enumerate() {
+			if i > 0 {
+				write!(f, ", ")?;
+			}
+			write!(f, "{arg}")?;
+		}
+		write!(f, ")")
+	}
+}
+
+/// Calls are compared by the function name, since the functions of a registry have distinct
+/// names.
+impl PartialEq for Custom {
+	fn eq(&self, other: &Self) -> bool {
+		self.name() == other.name() && self.args == other.args
+	}
+}
+
+impl Eq for Custom {}
+
+impl std::hash::Hash for Custom {
+	fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+		self.name().hash(state);
+		self.args.hash(state);
+	}
+}
+
+impl PartialOrd for Custom {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for Custom {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		(self.name(), &self.args).cmp(&(other.name(), &other.args))
+	}
+}
+
+/// The call refers to the function by name, which is resolved in the registry passed to
+/// [`FunctionRegistry::deserialize_expression`](super::FunctionRegistry::deserialize_expression).
+#[cfg(feature = "serde")]
+impl serde::Serialize for Custom {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		use serde::ser::SerializeStruct;
+
+		let mut state = serializer.serialize_struct("Custom", 2)?;
+		state.serialize_field("name", self.name())?;
+		state.serialize_field("args", &self.args)?;
+		state.end()
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Custom {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		use serde::de::Error;
+
+		#[derive(serde::Deserialize)]
+		#[serde(rename = "Custom")]
+		struct Repr {
+			name: String,
+			args: Vec<Expression>,
+		}
+
+		let Repr { name, args } = Repr::deserialize(deserializer)?;
+		let function =
+			super::FunctionRegistry::deserializing(|registry| registry.get(&name).cloned())
+				.ok_or_else(|| D::Error::custom(format!("unknown function `{name}`")))?;
+		let found = args.len();
+		Self::new(&function, args).ok_or_else(|| {
+			D::Error::custom(format!(
+				"function `{name}` takes {} argument(s), but {found} given",
+				function.arity()
+			))
+		})
+	}
+}
+
+impl Function for Custom {
+	fn eval_with<T: Scalar>(&self, env: &Env<T>) -> Result<T, EvalError> {
+		let args = self
+			.args
+			.iter()
+			.map(|arg| arg.eval_with(env).map(Scalar::to_f64))
+			.collect::<Result<Vec<_>, _>>()?;
+		self.apply(&args).map(T::from_f64)
+	}
+}
+
+impl ComplexFunction for Custom {
+	fn eval_complex_with(&self, env: &Env<Complex>) -> Result<Complex, EvalError> {
+		let args = self
+			.args
+			.iter()
+			.map(|arg| arg.eval_complex_with(env))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		// the evaluator is only defined for real numbers
+		if args.iter().any(|z| z.im != 0.0) {
+			return Err(EvalError::ComplexArgument(self.name().to_string()));
+		}
+		let args: Vec<_> = args.iter().map(|z| z.re).collect();
+		self.apply(&args).map(Complex::from)
+	}
+}
+
+impl IntervalFunction for Custom {
+	fn eval_interval_with(&self, env: &Env<Interval>) -> Result<Interval, EvalError> {
+		let args = self
+			.args
+			.iter()
+			.map(|arg| arg.eval_interval_with(env))
+			.collect::<Result<Vec<_>, _>>()?;
+		self.function
+			.interval(&args)
+			.ok_or_else(|| EvalError::NoIntervalEvaluation(self.name().to_string()))
+	}
+}
+
+impl Derivative for Custom {
+	fn is_constant(&self) -> bool {
+		self.args.iter().all(Derivative::is_constant)
+	}
+
+	/// Chain rule of the partial derivatives.
+	fn derivative(&self) -> Expression {
+		self.function
+			.partials(self)
+			.into_iter()
+			.zip(&self.args)
+			.map(|(partial, arg)| -> Expression { mul!(partial, arg.derivative()).into() })
+			.reduce(|sum, term| add!(sum, term).into())
+			.unwrap_or_else(|| num!(0.0).into())
+	}
+}
+
+impl Simplify for Custom {
+	fn simplify(&self) -> Expression {
+		fold(self.map_args(Simplify::simplify).into())
+	}
+}
+
+impl Typeset for Custom {
+	fn to_latex(&self) -> String {
+		let args: Vec<_> = self.args.iter().map(Typeset::to_latex).collect();
+		format!(
+			"\\operatorname{{{}}}\\left({}\\right)",
+			self.name().replace('_', "\\_"),
+			args.join(", ")
+		)
+	}
+
+	fn to_mathml(&self) -> String {
+		let args: Vec<_> = self.args.iter().map(Typeset::to_mathml).collect();
+		format!(
+			"<mrow><mi>{}</mi><mo>&#x2061;</mo><mrow><mo>(</mo>{}<mo>)</mo></mrow></mrow>",
+			self.name(),
+			args.join("<mo>,</mo>")
+		)
+	}
+}
+
+impl From<Custom> for Expression {
+	fn from(val: Custom) -> Self {
+		Expression::Custom(val.into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{mul, var};
+
+	#[test]
+	fn test_custom() {
+		// a ** 2 + b, defined for non-negative a
+		let f = Arc::new(
+			CustomFunction::new(
+				"f",
+				2,
+				|args| args[0].powi(2) + args[1],
+				|call| {
+					vec![
+						mul!(num!(2.0), call.args()[0].clone()).into(),
+						num!(1.0).into(),
+					]
+				},
+			)
+			.with_domain(|args| args[0] >= 0.0),
+		);
+		let call = Custom::new(&f, vec![var!().into(), mul!(num!(3.0), var!()).into()]).unwrap();
+		assert_eq!(call.to_string(), "f(x, 3 * x)");
+		assert_eq!(call.eval(2.0).unwrap(), 10.0);
+		assert_eq!(
+			call.eval(-1.0),
+			Err(EvalError::OutOfDomain("f".to_string(), vec![-1.0, -3.0]))
+		);
+		assert_eq!(call.derivative().eval(2.0).unwrap(), 7.0);
+		assert_eq!(
+			call.eval_complex(Complex::from(1.0)).unwrap(),
+			Complex::from(4.0)
+		);
+		assert_eq!(
+			call.eval_interval(0.0, 1.0),
+			Err(EvalError::NoIntervalEvaluation("f".to_string()))
+		);
+		assert_eq!(
+			call.to_latex(),
+			"\\operatorname{f}\\left(x, 3 \\cdot x\\right)"
+		);
+
+		assert!(Custom::new(&f, Vec::new()).is_none());
+	}
+}
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+/// Evaluation environment, i.e., values of the variables.
+#[derive(Debug, Clone)]
+pub struct Env<T = f32>(HashMap<String, T>);
+
+impl<T> Default for Env<T> {
+	fn default() -> Self {
+		Self(HashMap::new())
+	}
+}
+
+impl<T: Clone> Env<T> {
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Create an environment where only x is bound.
+	#[must_use]
+	pub fn from_x(x: T) -> Self {
+		Self::new().with("x", x)
+	}
+
+	/// Bind a variable and return the environment.
+	#[must_use]
+	pub fn with<S: AsRef<str>>(mut self, name: S, value: T) -> Self {
+		self.set(name, value);
+		self
+	}
+
+	/// Bind a variable, replacing the previous value if any.
+	pub fn set<S: AsRef<str>>(&mut self, name: S, value: T) {
+		let name = name.as_ref();
+		if let Some(v) = self.0.get_mut(name) {
+			*v = value;
+		} else {
+			self.0.insert(name.to_string(), value);
+		}
+	}
+
+	/// Get the value of a variable.
+	///
+	/// Return `None` if the variable is unbound.
+	#[must_use]
+	pub fn get<S: AsRef<str>>(&self, name: S) -> Option<T> {
+		self.0.get(name.as_ref()).cloned()
+	}
+
+	/// Is the variable bound.
+	#[must_use]
+	pub fn contains<S: AsRef<str>>(&self, name: S) -> bool {
+		self.0.contains_key(name.as_ref())
+	}
+
+	/// Create the environment of the values converted by `f`, e.g., to another scalar type.
+	#[must_use]
+	pub fn map<U, F: Fn(&T) -> U>(&self, f: F) -> Env<U> {
+		Env(self
+			.0
+			.iter()
+			.map(|(name, v)| (name.clone(), f(v)))
+			.collect())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_env() {
+		let mut env = Env::from_x(1.0).with("t", 2.0);
+		assert_eq!(env.get("x"), Some(1.0));
+		assert_eq!(env.get("t"), Some(2.0));
+		assert_eq!(env.get("y"), None);
+
+		env.set("x", 3.0);
+		assert_eq!(env.get("x"), Some(3.0));
+		assert!(env.contains("t"));
+
+		let env = env.map(|v| v * 2.0);
+		assert_eq!(env.get("t"), Some(4.0));
+	}
+}
@@ -0,0 +1,297 @@
+//! Errors of the expression language.
+
+use super::{Span, Token};
+use std::ops::RangeInclusive;
+
+/// Kind of parse errors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+	/// The token cannot start or continue an expression.
+	UnexpectedToken(Token),
+	/// Expected something else, e.g., a `)`.
+	Expected { expected: String, found: Token },
+	/// Only `-` and `not` are allowed as unary operators.
+	InvalidUnaryOperator(String),
+	/// Call of an unknown function.
+	UnknownFunction(String),
+	/// Use of a variable that is not bound.
+	UnboundVariable(String),
+	/// Definition of a function that calls itself.
+	RecursiveDefinition(String),
+	/// Definition of a name that is already used, e.g., a built-in function or a parameter.
+	Redefinition(String),
+	/// Call of a function with a wrong number of arguments, where `expected` is the range of the
+	/// numbers it takes.
+	ArgumentCount {
+		name: String,
+		expected: RangeInclusive<usize>,
+		found: usize,
+	},
+}
+
+/// Quote a token for messages.
+fn quote(token: &Token) -> String {
+	if token.is_eof() {
+		"end of input".to_string()
+	} else {
+		format!("`{token}`")
+	}
+}
+
+impl std::fmt::Display for ParseErrorKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ParseErrorKind::UnexpectedToken(token) => write!(f, "unexpected {}", quote(token)),
+			ParseErrorKind::Expected { expected, found } => {
+				write!(f, "expected {expected}, but found {}", quote(found))
+			}
+			ParseErrorKind::InvalidUnaryOperator(op) => {
+				write!(
+					f,
+					"invalid unary operator `{op}`, only `-` and `not` are allowed"
+				)
+			}
+			ParseErrorKind::UnknownFunction(name) => write!(f, "unknown function `{name}`"),
+			ParseErrorKind::UnboundVariable(name) => write!(f, "unbound variable `{name}`"),
+			ParseErrorKind::RecursiveDefinition(name) => {
+				write!(f, "function `{name}` cannot call itself")
+			}
+			ParseErrorKind::Redefinition(name) => write!(f, "`{name}` is already defined"),
+			ParseErrorKind::ArgumentCount {
+				name,
+				expected,
+				found,
+			} => {
+				let (min, max) = (expected.start(), expected.end());
+				if min == max {
+					write!(
+						f,
+						"function `{name}` takes {min} argument(s), but {found} given"
+					)
+				} else {
+					write!(
+						f,
+						"function `{name}` takes {min} to {max} arguments, but {found} given"
+					)
+				}
+			}
+		}
+	}
+}
+
+/// Parse error with the location in the source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+	pub kind: ParseErrorKind,
+	pub span: Span,
+}
+
+impl ParseError {
+	#[must_use]
+	pub fn new(kind: ParseErrorKind, span: Span) -> Self {
+		Self { kind, span }
+	}
+
+	/// Render the source line with a caret under the error location.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use rsap::expression::parse;
+	/// let err = parse("(x + 1").unwrap_err();
+	/// assert_eq!(
+	/// 	err.render("(x + 1"),
+	/// 	"(x + 1\n      ^ expected `)`, but found end of input"
+	/// );
+	/// ```
+	#[must_use]
+	pub fn render(&self, source: &str) -> String {
+		let start = self.span.start.min(source.len());
+		let end = self.span.end.clamp(start, source.len());
+		let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+		let line_end = source[start..]
+			.find('\n')
+			.map_or(source.len(), |i| start + i);
+
+		let line = &source[line_start..line_end];
+		let column = source[line_start..start].chars().count();
+		let width = source[start..end.min(line_end)].chars().count().max(1);
+
+		format!(
+			"{line}\n{}{} {}",
+			" ".repeat(column),
+			"^".repeat(width),
+			self.kind
+		)
+	}
+}
+
+impl std::fmt::Display for ParseError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.kind)
+	}
+}
+
+impl std::error::Error for ParseError {}
+
+/// Evaluation error, telling which node is undefined and why.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+	/// Division by zero.
+	DivisionByZero,
+	/// `0` raised to the negative power.
+	ZeroToNegativePower(f64),
+	/// Power with a complex result, e.g., `(-8) ** (1 / 3)`.
+	ComplexPower(f64, f64),
+	/// `tan` at a pole.
+	TanAtPole(f64),
+	/// `cot` at a pole.
+	CotAtPole(f64),
+	/// `sec` at a pole.
+	SecAtPole(f64),
+	/// `csc` at a pole.
+	CscAtPole(f64),
+	/// `arcsin` out of [-1, 1].
+	ArcsinOutOfRange(f64),
+	/// `arccos` out of [-1, 1].
+	ArccosOutOfRange(f64),
+	/// `arctan` at a pole `±i` in complex numbers, with the imaginary part.
+	ArctanAtPole(f64),
+	/// `arccot` at a pole `±i` in complex numbers, with the imaginary part.
+	ArccotAtPole(f64),
+	/// `ln` of a non-positive number.
+	LnOfNonPositive(f64),
+	/// `sqrt` of a negative number.
+	SqrtOfNegative(f64),
+	/// `log` with a non-positive base or base 1.
+	InvalidLogBase(f64),
+	/// `log` of a non-positive number.
+	LogOfNonPositive(f64),
+	/// `tanh` at a pole `±i * pi / 2` in complex numbers, with the imaginary part.
+	TanhAtPole(f64),
+	/// `arccosh` below 1.
+	ArccoshOutOfRange(f64),
+	/// `arctanh` out of (-1, 1).
+	ArctanhOutOfRange(f64),
+	/// `root` with index 0 or an infinite index.
+	InvalidRootIndex(f64),
+	/// `root` of a negative number with an index that is not an odd integer, with the index and
+	/// the number.
+	RootOfNegative(f64, f64),
+	/// Factorial of a negative integer.
+	FactorialAtPole(f64),
+	/// `polygamma` with an order that is not an integer in [0, 170].
+	InvalidPolygammaOrder(f64),
+	/// `polygamma` at a pole, i.e., 0 or a negative integer.
+	PolygammaAtPole(f64),
+	/// Function of real numbers only, e.g., `min`, with a non-real argument.
+	ComplexArgument(String),
+	/// Conditional without the else branch where the condition is false.
+	FalseCondition,
+	/// Registered function out of its domain, with the argument values.
+	OutOfDomain(String, Vec<f64>),
+	/// Registered function or integral that cannot be evaluated over intervals.
+	NoIntervalEvaluation(String),
+	/// Integral of an integrand that is undefined over a part of the interval, with a point there.
+	UndefinedIntegrand(f64),
+	/// Integral that does not converge, e.g., across a pole.
+	DivergentIntegral,
+	/// Use of a variable that is not bound.
+	UnboundVariable(String),
+	/// Use of the imaginary unit in real numbers.
+	ImaginaryUnit,
+}
+
+impl std::fmt::Display for EvalError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			EvalError::DivisionByZero => write!(f, "division by zero"),
+			EvalError::ZeroToNegativePower(y) => write!(f, "0 raised to the negative power {y}"),
+			EvalError::ComplexPower(x, y) => write!(f, "{x} ** {y} is complex"),
+			EvalError::TanAtPole(x) => write!(f, "tan is undefined at the pole {x}"),
+			EvalError::CotAtPole(x) => write!(f, "cot is undefined at the pole {x}"),
+			EvalError::SecAtPole(x) => write!(f, "sec is undefined at the pole {x}"),
+			EvalError::CscAtPole(x) => write!(f, "csc is undefined at the pole {x}"),
+			EvalError::ArcsinOutOfRange(x) => write!(f, "arcsin of {x} is out of [-1, 1]"),
+			EvalError::ArccosOutOfRange(x) => write!(f, "arccos of {x} is out of [-1, 1]"),
+			EvalError::ArctanAtPole(y) => write!(f, "arctan is undefined at the pole {y}i"),
+			EvalError::ArccotAtPole(y) => write!(f, "arccot is undefined at the pole {y}i"),
+			EvalError::LnOfNonPositive(x) => write!(f, "ln of the non-positive number {x}"),
+			EvalError::SqrtOfNegative(x) => write!(f, "sqrt of the negative number {x}"),
+			EvalError::InvalidLogBase(a) => {
+				write!(f, "log base {a} is invalid, it must be positive and not 1")
+			}
+			EvalError::LogOfNonPositive(x) => write!(f, "log of the non-positive number {x}"),
+			EvalError::TanhAtPole(y) => write!(f, "tanh is undefined at the pole {y}i"),
+			EvalError::ArccoshOutOfRange(x) => write!(f, "arccosh of {x} is less than 1"),
+			EvalError::ArctanhOutOfRange(x) => write!(f, "arctanh of {x} is out of (-1, 1)"),
+			EvalError::InvalidRootIndex(n) => write!(f, "root index {n} is invalid"),
+			EvalError::RootOfNegative(n, x) => {
+				write!(
+					f,
+					"root of the negative number {x} with index {n} is complex"
+				)
+			}
+			EvalError::FactorialAtPole(x) => {
+				write!(f, "factorial of the negative integer {x} is undefined")
+			}
+			EvalError::InvalidPolygammaOrder(n) => {
+				write!(
+					f,
+					"polygamma order {n} is invalid, it must be an integer in [0, 170]"
+				)
+			}
+			EvalError::PolygammaAtPole(x) => write!(f, "polygamma is undefined at the pole {x}"),
+			EvalError::ComplexArgument(name) => {
+				write!(f, "{name} is only defined for real arguments")
+			}
+			EvalError::FalseCondition => {
+				write!(f, "the condition is false and there is no else branch")
+			}
+			EvalError::OutOfDomain(name, args) => {
+				let args: Vec<_> = args.iter().map(ToString::to_string).collect();
+				write!(f, "{name} is undefined at ({})", args.join(", "))
+			}
+			EvalError::NoIntervalEvaluation(name) => {
+				write!(f, "{name} cannot be evaluated over intervals")
+			}
+			EvalError::UndefinedIntegrand(x) => write!(f, "the integrand is undefined at {x}"),
+			EvalError::DivergentIntegral => write!(f, "the integral does not converge"),
+			EvalError::UnboundVariable(name) => write!(f, "unbound variable `{name}`"),
+			EvalError::ImaginaryUnit => write!(f, "imaginary unit `i` is not a real number"),
+		}
+	}
+}
+
+impl std::error::Error for EvalError {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_render() {
+		let err = ParseError::new(
+			ParseErrorKind::UnboundVariable("yy".to_string()),
+			Span::new(4, 6),
+		);
+		assert_eq!(err.to_string(), "unbound variable `yy`");
+		assert_eq!(err.render("x + yy"), "x + yy\n    ^^ unbound variable `yy`");
+
+		// only the line of the error is rendered
+		let err = ParseError::new(
+			ParseErrorKind::UnexpectedToken(Token::CloseParenthesis),
+			Span::new(7, 8),
+		);
+		assert_eq!(err.render("x\nπ + )"), "π + )\n    ^ unexpected `)`");
+	}
+
+	#[test]
+	fn test_eval_error() {
+		assert_eq!(EvalError::DivisionByZero.to_string(), "division by zero");
+		assert_eq!(
+			EvalError::LnOfNonPositive(-1.0).to_string(),
+			"ln of the non-positive number -1"
+		);
+	}
+}
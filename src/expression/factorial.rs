@@ -1,55 +1,205 @@
-use super::{Expression, Function};
-use crate::math::factorial;
+use super::{
+	fmt_operand, fold, latex_operand, mathml_operand, ComplexFunction, Derivative, Env, EvalError,
+	Expression, Function, IntervalFunction, Scalar, Simplify, Typeset, ATOM_PRECEDENCE,
+};
+use crate::{
+	add,
+	math::{self, Complex, Interval},
+	mul, num, polygamma,
+};
 
-/// Factorial expression.
+/// Factorial expression, which is `gamma(x + 1)` for non-integers.
 ///
 /// ```bnf
-/// factorial ::= (digit)+ "!"
-/// digit ::= "0"..."9"
+/// factorial ::= primary "!"
 /// ```
-#[derive(Debug, Clone)]
-pub struct Factorial(u32);
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Factorial(Expression);
 
 impl Factorial {
+	pub fn new<E: Into<Expression>>(arg: E) -> Self {
+		Self(arg.into())
+	}
+
 	#[must_use]
-	pub fn new(n: u32) -> Self {
-		Self(n)
+	pub fn arg(&self) -> &Expression {
+		&self.0
+	}
+
+	/// Factorial of the value, which is undefined at the negative integers.
+	pub(crate) fn value<T: Scalar>(x: T) -> Result<T, EvalError> {
+		let x = x.to_f64();
+		if math::is_gamma_pole(x + 1.0) {
+			Err(EvalError::FactorialAtPole(x))
+		} else {
+			Ok(T::from_f64(math::factorial(x)))
+		}
 	}
 }
 
 #[macro_export]
 macro_rules! factorial {
-	($n:expr) => {
-		$crate::expression::Factorial::new($n)
+	($arg:expr) => {
+		$crate::expression::Factorial::new($arg)
 	};
 }
 
 impl std::fmt::Display for Factorial {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		write!(f, "{}!", self.0)
+		fmt_operand(f, &self.0, self.0.precedence() < ATOM_PRECEDENCE)?;
+		write!(f, "!")
 	}
 }
 
 impl Function for Factorial {
-	fn eval(&self, _x: f32) -> Option<f32> {
-		Some(factorial(self.0) as f32)
+	fn eval_with<T: Scalar>(&self, env: &Env<T>) -> Result<T, EvalError> {
+		Self::value(self.0.eval_with(env)?)
+	}
+}
+
+impl ComplexFunction for Factorial {
+	fn eval_complex_with(&self, env: &Env<Complex>) -> Result<Complex, EvalError> {
+		let z = self.0.eval_complex_with(env)?;
+		if z.im == 0.0 {
+			Self::value(z.re).map(Complex::from)
+		} else {
+			Err(EvalError::ComplexArgument("factorial".to_string()))
+		}
+	}
+}
+
+impl IntervalFunction for Factorial {
+	fn eval_interval_with(&self, env: &Env<Interval>) -> Result<Interval, EvalError> {
+		Ok((self.0.eval_interval_with(env)? + Interval::point(1.0)).gamma())
+	}
+}
+
+impl Derivative for Factorial {
+	fn is_constant(&self) -> bool {
+		self.0.is_constant()
+	}
+
+	/// `x!' = x! * polygamma(0, x + 1) * x'`
+	fn derivative(&self) -> Expression {
+		mul!(
+			mul!(
+				self.clone(),
+				polygamma!(num!(0.0), add!(self.0.clone(), num!(1.0)))
+			),
+			self.0.derivative()
+		)
+		.into()
+	}
+}
+
+impl Simplify for Factorial {
+	fn simplify(&self) -> Expression {
+		fold(Self(self.0.simplify()).into())
+	}
+}
+
+impl Typeset for Factorial {
+	fn to_latex(&self) -> String {
+		let paren = self.0.typeset_precedence() < ATOM_PRECEDENCE;
+		format!("{}!", latex_operand(&self.0, paren))
+	}
+
+	fn to_mathml(&self) -> String {
+		let paren = self.0.typeset_precedence() < ATOM_PRECEDENCE;
+		format!("<mrow>{}<mo>!</mo></mrow>", mathml_operand(&self.0, paren))
 	}
 }
 
 impl From<Factorial> for Expression {
 	fn from(val: Factorial) -> Self {
-		Expression::Factorial(val)
+		Expression::Factorial(val.into())
 	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::var;
 
 	#[test]
 	fn test_factorial() {
-		let f = factorial!(5);
+		let f = factorial!(num!(5.0));
+		assert_eq!(f.to_string(), "5!");
 		assert_eq!(f.eval(0.0).unwrap(), 120.0);
 		assert_eq!(f.eval(1.0).unwrap(), 120.0);
+
+		let f = factorial!(var!());
+		assert_eq!(f.eval(3.0).unwrap(), 6.0);
+		assert!((f.eval(0.5f64).unwrap() - std::f64::consts::PI.sqrt() / 2.0).abs() < 1e-14);
+		assert!((f.eval(-0.5f64).unwrap() - std::f64::consts::PI.sqrt()).abs() < 1e-14);
+		assert_eq!(f.eval(-2.0), Err(EvalError::FactorialAtPole(-2.0)));
+		assert_eq!(
+			f.eval_complex(Complex::I),
+			Err(EvalError::ComplexArgument("factorial".to_string()))
+		);
+	}
+
+	#[test]
+	fn test_factorial_f64() {
+		let f = factorial!(num!(20.0));
+		assert_eq!(f.eval(0.0f64).unwrap(), 2_432_902_008_176_640_000.0);
+		let f = factorial!(var!());
+		assert_eq!(f.eval(20.0f64).unwrap(), 2_432_902_008_176_640_000.0);
+		assert!(f.eval(200.0f64).unwrap().is_infinite());
+	}
+
+	#[test]
+	fn test_factorial_interval() {
+		let f = factorial!(var!());
+		let y = f.eval_interval(2.0, 3.0).unwrap();
+		assert!(y.contains(2.0) && y.contains(6.0) && !y.is_split());
+		// the minimum between 0 and 1
+		let y = f.eval_interval(0.0, 1.0).unwrap();
+		assert!(y.contains(0.9) && !y.contains(0.88));
+		// poles at the negative integers
+		let y = f.eval_interval(-1.5, -0.5).unwrap();
+		assert!(!y.is_bounded());
+	}
+
+	#[test]
+	fn test_factorial_derivative() {
+		let f = factorial!(var!());
+		assert!(!f.is_constant());
+		let df = f.derivative();
+		// 1! * (1 - euler_gamma)
+		assert!((df.eval(1.0f64).unwrap() - 0.422_784_335_098_467_1).abs() < 1e-12);
+		assert_eq!(df.to_string(), "x! * polygamma(0, x + 1) * 1");
+		assert_eq!(crate::expression::parse(df.to_string()).unwrap(), df);
+
+		// 1! * ((1 - euler_gamma) ** 2 + pi ** 2 / 6 - 1)
+		let ddf = df.derivative();
+		let pi2 = std::f64::consts::PI.powi(2);
+		let expected = 0.422_784_335_098_467_1f64.powi(2) + pi2 / 6.0 - 1.0;
+		assert!((ddf.eval(1.0f64).unwrap() - expected).abs() < 1e-12);
+		assert!(ddf.eval_interval(0.9, 1.1).unwrap().contains(expected));
+	}
+
+	#[test]
+	fn test_factorial_simplify() {
+		let f = factorial!(num!(5.0)).simplify();
+		assert_eq!(f.to_string(), "120");
+		let f = factorial!(add!(num!(1.0), num!(2.0))).simplify();
+		assert_eq!(f.to_string(), "6");
+	}
+
+	#[test]
+	fn test_factorial_typeset() {
+		let f = factorial!(num!(5.0));
+		assert_eq!(f.to_latex(), "5!");
+		assert_eq!(f.to_mathml(), "<mrow><mn>5</mn><mo>!</mo></mrow>");
+
+		let f = factorial!(add!(var!(), num!(1.0)));
+		assert_eq!(f.to_string(), "(x + 1)!");
+		assert_eq!(f.to_latex(), "\\left(x + 1\\right)!");
+		assert_eq!(
+			f.to_mathml(),
+			"<mrow><mrow><mo>(</mo><mrow><mi>x</mi><mo>+</mo><mn>1</mn></mrow><mo>)</mo></mrow><mo>!</mo></mrow>"
+		);
 	}
 }
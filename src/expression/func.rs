@@ -3,14 +3,25 @@
 #[macro_use]
 mod macros;
 
-use super::{Expression, Function};
+use super::{
+	fold, ComplexFunction, Derivative, Env, EvalError, Expression, Function, IntervalFunction,
+	Scalar, Simplify, Typeset,
+};
+use crate::{
+	add, div,
+	math::{Complex, Interval},
+	mul, neg, num, pow, sub,
+};
 
-pub const FUNCTION_NAMES: [&str; 12] = [
+pub const FUNCTION_NAMES: [&str; 26] = [
 	"sin", "cos", "tan", "cot", "sec", "csc", "arcsin", "arccos", "arctan", "arccot", "ln", "sqrt",
+	"abs", "exp", "floor", "ceil", "round", "sign", "sinh", "cosh", "tanh", "arcsinh", "arccosh",
+	"arctanh", "log10", "log2",
 ];
 
 /// Function expression.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Func {
 	Sin(Expression),
 	Cos(Expression),
@@ -24,6 +35,20 @@ pub enum Func {
 	Arccot(Expression),
 	Ln(Expression),
 	Sqrt(Expression),
+	Abs(Expression),
+	Exp(Expression),
+	Floor(Expression),
+	Ceil(Expression),
+	Round(Expression),
+	Sign(Expression),
+	Sinh(Expression),
+	Cosh(Expression),
+	Tanh(Expression),
+	Arcsinh(Expression),
+	Arccosh(Expression),
+	Arctanh(Expression),
+	Log10(Expression),
+	Log2(Expression),
 }
 
 impl Func {
@@ -41,66 +66,369 @@ impl Func {
 			"arccot" => Some(Self::Arccot(expr.into())),
 			"ln" => Some(Self::Ln(expr.into())),
 			"sqrt" => Some(Self::Sqrt(expr.into())),
+			"abs" => Some(Self::Abs(expr.into())),
+			"exp" => Some(Self::Exp(expr.into())),
+			"floor" => Some(Self::Floor(expr.into())),
+			"ceil" => Some(Self::Ceil(expr.into())),
+			"round" => Some(Self::Round(expr.into())),
+			"sign" => Some(Self::Sign(expr.into())),
+			"sinh" => Some(Self::Sinh(expr.into())),
+			"cosh" => Some(Self::Cosh(expr.into())),
+			"tanh" => Some(Self::Tanh(expr.into())),
+			"arcsinh" => Some(Self::Arcsinh(expr.into())),
+			"arccosh" => Some(Self::Arccosh(expr.into())),
+			"arctanh" => Some(Self::Arctanh(expr.into())),
+			"log10" => Some(Self::Log10(expr.into())),
+			"log2" => Some(Self::Log2(expr.into())),
 			_ => None,
 		}
 	}
 }
 
-impl std::fmt::Display for Func {
-	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Func {
+	/// The function name.
+	#[must_use]
+	pub fn name(&self) -> &'static str {
+		match self {
+			Func::Sin(_) => "sin",
+			Func::Cos(_) => "cos",
+			Func::Tan(_) => "tan",
+			Func::Cot(_) => "cot",
+			Func::Sec(_) => "sec",
+			Func::Csc(_) => "csc",
+			Func::Arcsin(_) => "arcsin",
+			Func::Arccos(_) => "arccos",
+			Func::Arctan(_) => "arctan",
+			Func::Arccot(_) => "arccot",
+			Func::Ln(_) => "ln",
+			Func::Sqrt(_) => "sqrt",
+			Func::Abs(_) => "abs",
+			Func::Exp(_) => "exp",
+			Func::Floor(_) => "floor",
+			Func::Ceil(_) => "ceil",
+			Func::Round(_) => "round",
+			Func::Sign(_) => "sign",
+			Func::Sinh(_) => "sinh",
+			Func::Cosh(_) => "cosh",
+			Func::Tanh(_) => "tanh",
+			Func::Arcsinh(_) => "arcsinh",
+			Func::Arccosh(_) => "arccosh",
+			Func::Arctanh(_) => "arctanh",
+			Func::Log10(_) => "log10",
+			Func::Log2(_) => "log2",
+		}
+	}
+
+	/// The argument expression.
+	#[must_use]
+	pub fn arg(&self) -> &Expression {
+		match self {
+			Func::Sin(expr)
+			| Func::Cos(expr)
+			| Func::Tan(expr)
+			| Func::Cot(expr)
+			| Func::Sec(expr)
+			| Func::Csc(expr)
+			| Func::Arcsin(expr)
+			| Func::Arccos(expr)
+			| Func::Arctan(expr)
+			| Func::Arccot(expr)
+			| Func::Ln(expr)
+			| Func::Sqrt(expr)
+			| Func::Abs(expr)
+			| Func::Exp(expr)
+			| Func::Floor(expr)
+			| Func::Ceil(expr)
+			| Func::Round(expr)
+			| Func::Sign(expr)
+			| Func::Sinh(expr)
+			| Func::Cosh(expr)
+			| Func::Tanh(expr)
+			| Func::Arcsinh(expr)
+			| Func::Arccosh(expr)
+			| Func::Arctanh(expr)
+			| Func::Log10(expr)
+			| Func::Log2(expr) => expr,
+		}
+	}
+
+	/// Create the same function of the argument mapped by `f`.
+	pub(crate) fn map<F: FnOnce(&Expression) -> Expression>(&self, f: F) -> Self {
+		Self::new(self.name(), f(self.arg())).unwrap()
+	}
+
+	/// The function of the argument value.
+	///
+	/// The function returns an error if the value is not in the definition domain.
+	pub(crate) fn scalar_fn<T: Scalar>(&self) -> fn(T) -> Result<T, EvalError> {
 		match self {
-			Func::Sin(expr) => write!(f, "sin({expr})"),
-			Func::Cos(expr) => write!(f, "cos({expr})"),
-			Func::Tan(expr) => write!(f, "tan({expr})"),
-			Func::Cot(expr) => write!(f, "cot({expr})"),
-			Func::Sec(expr) => write!(f, "sec({expr})"),
-			Func::Csc(expr) => write!(f, "csc({expr})"),
-			Func::Arcsin(expr) => write!(f, "arcsin({expr})"),
-			Func::Arccos(expr) => write!(f, "arccos({expr})"),
-			Func::Arctan(expr) => write!(f, "arctan({expr})"),
-			Func::Arccot(expr) => write!(f, "arccot({expr})"),
-			Func::Ln(expr) => write!(f, "ln({expr})"),
-			Func::Sqrt(expr) => write!(f, "sqrt({expr})"),
+			Func::Sin(_) => |val| Ok(val.sin()),
+			Func::Cos(_) => |val| Ok(val.cos()),
+			Func::Tan(_) => |val| {
+				Some(val.tan())
+					.filter(|y| y.is_finite())
+					.ok_or(EvalError::TanAtPole(val.to_f64()))
+			},
+			Func::Cot(_) => |val| {
+				Some(T::ONE / val.tan())
+					.filter(|y| y.is_finite())
+					.ok_or(EvalError::CotAtPole(val.to_f64()))
+			},
+			Func::Sec(_) => |val| {
+				(val.cos() != T::ZERO)
+					.then(|| T::ONE / val.cos())
+					.ok_or(EvalError::SecAtPole(val.to_f64()))
+			},
+			Func::Csc(_) => |val| {
+				(val.sin() != T::ZERO)
+					.then(|| T::ONE / val.sin())
+					.ok_or(EvalError::CscAtPole(val.to_f64()))
+			},
+			Func::Arcsin(_) => |val| {
+				(-T::ONE..=T::ONE)
+					.contains(&val)
+					.then(|| val.asin())
+					.ok_or(EvalError::ArcsinOutOfRange(val.to_f64()))
+			},
+			Func::Arccos(_) => |val| {
+				(-T::ONE..=T::ONE)
+					.contains(&val)
+					.then(|| val.acos())
+					.ok_or(EvalError::ArccosOutOfRange(val.to_f64()))
+			},
+			Func::Arctan(_) => |val| Ok(val.atan()),
+			Func::Arccot(_) => |val| Ok(T::FRAC_PI_2 - val.atan()),
+			Func::Ln(_) => |val| {
+				(val > T::ZERO)
+					.then(|| val.ln())
+					.ok_or(EvalError::LnOfNonPositive(val.to_f64()))
+			},
+			Func::Sqrt(_) => |val| {
+				(val >= T::ZERO)
+					.then(|| val.sqrt())
+					.ok_or(EvalError::SqrtOfNegative(val.to_f64()))
+			},
+			Func::Abs(_) => |val| Ok(val.abs()),
+			Func::Exp(_) => |val| Ok(val.exp()),
+			Func::Floor(_) => |val| Ok(val.floor()),
+			Func::Ceil(_) => |val| Ok(val.ceil()),
+			Func::Round(_) => |val| Ok(val.round()),
+			// the sign of zero is zero
+			Func::Sign(_) => |val| Ok(if val == T::ZERO { val } else { val.signum() }),
+			Func::Sinh(_) => |val| Ok(val.sinh()),
+			Func::Cosh(_) => |val| Ok(val.cosh()),
+			Func::Tanh(_) => |val| Ok(val.tanh()),
+			Func::Arcsinh(_) => |val| Ok(val.asinh()),
+			Func::Arccosh(_) => |val| {
+				(val >= T::ONE)
+					.then(|| val.acosh())
+					.ok_or(EvalError::ArccoshOutOfRange(val.to_f64()))
+			},
+			Func::Arctanh(_) => |val| {
+				(val.abs() < T::ONE)
+					.then(|| val.atanh())
+					.ok_or(EvalError::ArctanhOutOfRange(val.to_f64()))
+			},
+			Func::Log10(_) => |val| {
+				(val > T::ZERO)
+					.then(|| val.log10())
+					.ok_or(EvalError::LogOfNonPositive(val.to_f64()))
+			},
+			Func::Log2(_) => |val| {
+				(val > T::ZERO)
+					.then(|| val.log2())
+					.ok_or(EvalError::LogOfNonPositive(val.to_f64()))
+			},
 		}
 	}
 }
 
+impl std::fmt::Display for Func {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}({})", self.name(), self.arg())
+	}
+}
+
 impl Function for Func {
-	fn is_x_valid(&self, x: f32) -> bool {
+	fn eval_with<T: Scalar>(&self, env: &Env<T>) -> Result<T, EvalError> {
+		self.scalar_fn()(self.arg().eval_with(env)?)
+	}
+}
+
+impl ComplexFunction for Func {
+	fn eval_complex_with(&self, env: &Env<Complex>) -> Result<Complex, EvalError> {
+		let z = self.arg().eval_complex_with(env)?;
+		let half_pi = Complex::from(std::f64::consts::FRAC_PI_2);
+
+		// the poles are on the real axis, except for arctan, arccot, and tanh
+		let (w, pole) = match self {
+			Func::Sin(_) => return Ok(z.sin()),
+			Func::Cos(_) => return Ok(z.cos()),
+			Func::Tan(_) => (z.sin() / z.cos(), EvalError::TanAtPole(z.re)),
+			Func::Cot(_) => (z.cos() / z.sin(), EvalError::CotAtPole(z.re)),
+			Func::Sec(_) => (Complex::ONE / z.cos(), EvalError::SecAtPole(z.re)),
+			Func::Csc(_) => (Complex::ONE / z.sin(), EvalError::CscAtPole(z.re)),
+			Func::Arcsin(_) => return Ok(z.asin()),
+			Func::Arccos(_) => return Ok(z.acos()),
+			Func::Arctan(_) => (z.atan(), EvalError::ArctanAtPole(z.im)),
+			Func::Arccot(_) => (half_pi - z.atan(), EvalError::ArccotAtPole(z.im)),
+			Func::Ln(_) => (z.ln(), EvalError::LnOfNonPositive(z.re)),
+			Func::Sqrt(_) => return Ok(z.sqrt()),
+			Func::Abs(_) => return Ok(Complex::from(z.abs())),
+			Func::Exp(_) => return Ok(z.exp()),
+			// rounding is applied to both parts
+			Func::Floor(_) => return Ok(Complex::new(z.re.floor(), z.im.floor())),
+			Func::Ceil(_) => return Ok(Complex::new(z.re.ceil(), z.im.ceil())),
+			Func::Round(_) => return Ok(Complex::new(z.re.round(), z.im.round())),
+			// z / |z| on the unit circle
+			Func::Sign(_) if z.is_zero() => return Ok(Complex::ZERO),
+			Func::Sign(_) => return Ok(z / Complex::from(z.abs())),
+			Func::Sinh(_) => return Ok(z.sinh()),
+			Func::Cosh(_) => return Ok(z.cosh()),
+			Func::Tanh(_) => (z.tanh(), EvalError::TanhAtPole(z.im)),
+			Func::Arcsinh(_) => return Ok(z.asinh()),
+			Func::Arccosh(_) => return Ok(z.acosh()),
+			Func::Arctanh(_) => (z.atanh(), EvalError::ArctanhOutOfRange(z.re)),
+			Func::Log10(_) => (
+				z.ln() / Complex::from(std::f64::consts::LN_10),
+				EvalError::LogOfNonPositive(z.re),
+			),
+			Func::Log2(_) => (
+				z.ln() / Complex::from(std::f64::consts::LN_2),
+				EvalError::LogOfNonPositive(z.re),
+			),
+		};
+
+		if w.is_finite() {
+			Ok(w)
+		} else {
+			Err(pole)
+		}
+	}
+}
+
+impl IntervalFunction for Func {
+	fn eval_interval_with(&self, env: &Env<Interval>) -> Result<Interval, EvalError> {
+		let val = self.arg().eval_interval_with(env)?;
+
+		Ok(match self {
+			Func::Sin(_) => val.sin(),
+			Func::Cos(_) => val.cos(),
+			Func::Tan(_) => val.tan(),
+			Func::Cot(_) => val.tan().recip(),
+			Func::Sec(_) => val.cos().recip(),
+			Func::Csc(_) => val.sin().recip(),
+			Func::Arcsin(_) => val.asin(),
+			Func::Arccos(_) => val.acos(),
+			Func::Arctan(_) => val.atan(),
+			Func::Arccot(_) => Interval::point(std::f64::consts::FRAC_PI_2) - val.atan(),
+			Func::Ln(_) => val.ln(),
+			Func::Sqrt(_) => val.sqrt(),
+			Func::Abs(_) => val.abs(),
+			Func::Exp(_) => val.exp(),
+			Func::Floor(_) => val.floor(),
+			Func::Ceil(_) => val.ceil(),
+			Func::Round(_) => val.round(),
+			Func::Sign(_) => val.signum(),
+			Func::Sinh(_) => val.sinh(),
+			Func::Cosh(_) => val.cosh(),
+			Func::Tanh(_) => val.tanh(),
+			Func::Arcsinh(_) => val.asinh(),
+			Func::Arccosh(_) => val.acosh(),
+			Func::Arctanh(_) => val.atanh(),
+			Func::Log10(_) => val.log10(),
+			Func::Log2(_) => val.log2(),
+		})
+	}
+}
+
+impl Derivative for Func {
+	fn is_constant(&self) -> bool {
+		self.arg().is_constant()
+	}
+
+	fn derivative(&self) -> Expression {
+		let u = self.arg().clone();
+
+		// derivative of the outer function at u
+		let outer: Expression = match self {
+			Func::Sin(_) => cos!(u).into(),
+			Func::Cos(_) => neg!(sin!(u)).into(),
+			Func::Tan(_) => pow!(sec!(u), num!(2.0)).into(),
+			Func::Cot(_) => neg!(pow!(csc!(u), num!(2.0))).into(),
+			Func::Sec(_) => mul!(sec!(u.clone()), tan!(u)).into(),
+			Func::Csc(_) => neg!(mul!(csc!(u.clone()), cot!(u))).into(),
+			Func::Arcsin(_) => div!(num!(1.0), sqrt!(sub!(num!(1.0), pow!(u, num!(2.0))))).into(),
+			Func::Arccos(_) => {
+				neg!(div!(num!(1.0), sqrt!(sub!(num!(1.0), pow!(u, num!(2.0)))))).into()
+			}
+			Func::Arctan(_) => div!(num!(1.0), add!(num!(1.0), pow!(u, num!(2.0)))).into(),
+			Func::Arccot(_) => neg!(div!(num!(1.0), add!(num!(1.0), pow!(u, num!(2.0))))).into(),
+			Func::Ln(_) => div!(num!(1.0), u).into(),
+			Func::Sqrt(_) => div!(num!(1.0), mul!(num!(2.0), sqrt!(u))).into(),
+			Func::Abs(_) => sign!(u).into(),
+			Func::Exp(_) => exp!(u).into(),
+			// the steps are flat except at the jumps
+			Func::Floor(_) | Func::Ceil(_) | Func::Round(_) | Func::Sign(_) => num!(0.0).into(),
+			Func::Sinh(_) => cosh!(u).into(),
+			Func::Cosh(_) => sinh!(u).into(),
+			Func::Tanh(_) => div!(num!(1.0), pow!(cosh!(u), num!(2.0))).into(),
+			Func::Arcsinh(_) => div!(num!(1.0), sqrt!(add!(pow!(u, num!(2.0)), num!(1.0)))).into(),
+			Func::Arccosh(_) => div!(num!(1.0), sqrt!(sub!(pow!(u, num!(2.0)), num!(1.0)))).into(),
+			Func::Arctanh(_) => div!(num!(1.0), sub!(num!(1.0), pow!(u, num!(2.0)))).into(),
+			Func::Log10(_) => div!(num!(1.0), mul!(u, ln!(num!(10.0)))).into(),
+			Func::Log2(_) => div!(num!(1.0), mul!(u, ln!(num!(2.0)))).into(),
+		};
+
+		// chain rule
+		mul!(outer, self.arg().derivative()).into()
+	}
+}
+
+impl Simplify for Func {
+	fn simplify(&self) -> Expression {
+		fold(func!(self.name(), self.arg().simplify()).unwrap().into())
+	}
+}
+
+impl Typeset for Func {
+	fn to_latex(&self) -> String {
+		let arg = self.arg().to_latex();
+
 		match self {
-			Func::Sin(expr) => expr.is_x_valid(x),
-			Func::Cos(expr) => expr.is_x_valid(x),
-			Func::Tan(expr) => expr.eval(x).is_some_and(|val| val.tan().is_finite()),
-			Func::Cot(expr) => expr
-				.eval(x)
-				.is_some_and(|val| (1.0 / val.tan()).is_finite()),
-			Func::Sec(expr) => expr.eval(x).is_some_and(|val| val.cos() != 0.0),
-			Func::Csc(expr) => expr.eval(x).is_some_and(|val| val.sin() != 0.0),
-			Func::Arcsin(expr) => expr.eval(x).is_some_and(|val| (-1.0..=1.0).contains(&val)),
-			Func::Arccos(expr) => expr.eval(x).is_some_and(|val| (-1.0..=1.0).contains(&val)),
-			Func::Arctan(expr) => expr.is_x_valid(x),
-			Func::Arccot(expr) => expr.is_x_valid(x),
-			Func::Ln(expr) => expr.eval(x).is_some_and(|val| val > 0.0),
-			Func::Sqrt(expr) => expr.eval(x).is_some_and(|val| val >= 0.0),
+			Func::Sqrt(_) => format!("\\sqrt{{{arg}}}"),
+			Func::Abs(_) => format!("\\left|{arg}\\right|"),
+			Func::Floor(_) => format!("\\left\\lfloor {arg}\\right\\rfloor"),
+			Func::Ceil(_) => format!("\\left\\lceil {arg}\\right\\rceil"),
+			Func::Sign(_) => format!("\\operatorname{{sgn}}\\left({arg}\\right)"),
+			Func::Log10(_) => format!("\\log_{{10}}\\left({arg}\\right)"),
+			Func::Log2(_) => format!("\\log_{{2}}\\left({arg}\\right)"),
+			// no predefined operator in LaTeX
+			Func::Arccot(_)
+			| Func::Round(_)
+			| Func::Arcsinh(_)
+			| Func::Arccosh(_)
+			| Func::Arctanh(_) => format!("\\operatorname{{{}}}\\left({arg}\\right)", self.name()),
+			_ => format!("\\{}\\left({arg}\\right)", self.name()),
 		}
 	}
 
-	fn eval(&self, x: f32) -> Option<f32> {
+	fn to_mathml(&self) -> String {
+		let arg = self.arg().to_mathml();
+
 		match self {
-			Func::Sin(expr) => Some(expr.eval(x)?.sin()),
-			Func::Cos(expr) => Some(expr.eval(x)?.cos()),
-			Func::Tan(expr) => self.is_x_valid(x).then_some(expr.eval(x)?.tan()),
-			Func::Cot(expr) => self.is_x_valid(x).then_some(1.0 / expr.eval(x)?.tan()),
-			Func::Sec(expr) => self.is_x_valid(x).then_some(1.0 / expr.eval(x)?.cos()),
-			Func::Csc(expr) => self.is_x_valid(x).then_some(1.0 / expr.eval(x)?.sin()),
-			Func::Arcsin(expr) => self.is_x_valid(x).then_some(expr.eval(x)?.asin()),
-			Func::Arccos(expr) => self.is_x_valid(x).then_some(expr.eval(x)?.acos()),
-			Func::Arctan(expr) => expr.eval(x).map(f32::atan),
-			Func::Arccot(expr) => expr
-				.eval(x)
-				.map(|val| std::f32::consts::FRAC_PI_2 - val.atan()),
-			Func::Ln(expr) => self.is_x_valid(x).then_some(expr.eval(x)?.ln()),
-			Func::Sqrt(expr) => self.is_x_valid(x).then_some(expr.eval(x)?.sqrt()),
+			Func::Sqrt(_) => format!("<msqrt>{arg}</msqrt>"),
+			Func::Abs(_) => format!("<mrow><mo>|</mo>{arg}<mo>|</mo></mrow>"),
+			Func::Floor(_) => format!("<mrow><mo>&#x230A;</mo>{arg}<mo>&#x230B;</mo></mrow>"),
+			Func::Ceil(_) => format!("<mrow><mo>&#x2308;</mo>{arg}<mo>&#x2309;</mo></mrow>"),
+			Func::Log10(_) | Func::Log2(_) => format!(
+				"<mrow><msub><mi>log</mi><mn>{}</mn></msub><mo>&#x2061;</mo><mrow><mo>(</mo>{arg}<mo>)</mo></mrow></mrow>",
+				&self.name()[3..]
+			),
+			_ => format!(
+				"<mrow><mi>{}</mi><mo>&#x2061;</mo><mrow><mo>(</mo>{arg}<mo>)</mo></mrow></mrow>",
+				self.name()
+			),
 		}
 	}
 }
@@ -114,7 +442,7 @@ impl From<Func> for Expression {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::var;
+	use crate::{add, mul, num, paren, var};
 
 	#[test]
 	fn test_sin() {
@@ -140,7 +468,7 @@ mod tests {
 	#[test]
 	fn test_cot() {
 		let f = cot!(var!());
-		assert!(f.eval(0.0).is_none());
+		assert_eq!(f.eval(0.0), Err(EvalError::CotAtPole(0.0)));
 		assert_eq!(f.eval(1.0).unwrap(), 1.0 / 1.0f32.tan());
 	}
 
@@ -154,7 +482,7 @@ mod tests {
 	#[test]
 	fn test_csc() {
 		let f = csc!(var!());
-		assert!(f.eval(0.0).is_none());
+		assert_eq!(f.eval(0.0), Err(EvalError::CscAtPole(0.0)));
 		assert_eq!(f.eval(1.0).unwrap(), 1.0 / 1.0f32.sin());
 	}
 
@@ -163,6 +491,7 @@ mod tests {
 		let f = arcsin!(var!());
 		assert_eq!(f.eval(0.0).unwrap(), 0.0);
 		assert_eq!(f.eval(1.0).unwrap(), 1.0f32.asin());
+		assert_eq!(f.eval(2.0), Err(EvalError::ArcsinOutOfRange(2.0)));
 	}
 
 	#[test]
@@ -192,7 +521,7 @@ mod tests {
 	#[test]
 	fn test_ln() {
 		let f = ln!(var!());
-		assert!(f.eval(0.0).is_none());
+		assert_eq!(f.eval(0.0), Err(EvalError::LnOfNonPositive(0.0)));
 		assert_eq!(f.eval(1.0).unwrap(), 0.0);
 	}
 
@@ -201,5 +530,193 @@ mod tests {
 		let f = sqrt!(var!());
 		assert_eq!(f.eval(0.0).unwrap(), 0.0);
 		assert_eq!(f.eval(1.0).unwrap(), 1.0);
+
+		// the innermost undefined node is reported
+		let f = sqrt!(ln!(sqrt!(var!())));
+		assert_eq!(f.eval(0.25), Err(EvalError::SqrtOfNegative(0.5f64.ln())));
+		assert_eq!(f.eval(-1.0), Err(EvalError::SqrtOfNegative(-1.0)));
+	}
+
+	#[test]
+	fn test_rounding() {
+		let cases = [
+			(abs!(var!()), -1.5, 1.5),
+			(floor!(var!()), -1.5, -2.0),
+			(ceil!(var!()), -1.5, -1.0),
+			(round!(var!()), 2.5, 3.0),
+			(sign!(var!()), -3.0, -1.0),
+			(sign!(var!()), 0.0, 0.0),
+		];
+		for (f, x, expected) in cases {
+			assert_eq!(f.eval(x).unwrap(), expected, "{f} at {x}");
+		}
+	}
+
+	#[test]
+	fn test_hyperbolic() {
+		let f = tanh!(var!());
+		assert_eq!(f.eval(0.0).unwrap(), 0.0);
+		let f = arccosh!(var!());
+		assert_eq!(f.eval(1.0).unwrap(), 0.0);
+		assert_eq!(f.eval(0.5), Err(EvalError::ArccoshOutOfRange(0.5)));
+		let f = arctanh!(var!());
+		assert_eq!(f.eval(1.0), Err(EvalError::ArctanhOutOfRange(1.0)));
+		let f = arcsinh!(sinh!(var!()));
+		assert!((f.eval(2.0).unwrap() - 2.0).abs() < 1e-6);
+	}
+
+	#[test]
+	fn test_exp_log() {
+		assert_eq!(exp!(var!()).eval(0.0).unwrap(), 1.0);
+		assert_eq!(log10!(var!()).eval(1000.0).unwrap(), 3.0);
+		assert_eq!(log2!(var!()).eval(8.0).unwrap(), 3.0);
+		assert_eq!(
+			log2!(var!()).eval(0.0),
+			Err(EvalError::LogOfNonPositive(0.0))
+		);
+	}
+
+	#[test]
+	fn test_derivative() {
+		let x = 0.5f32;
+		let cases = [
+			(sin!(var!()), x.cos()),
+			(cos!(var!()), -x.sin()),
+			(tan!(var!()), 1.0 / x.cos().powi(2)),
+			(cot!(var!()), -1.0 / x.sin().powi(2)),
+			(sec!(var!()), x.tan() / x.cos()),
+			(csc!(var!()), -1.0 / (x.tan() * x.sin())),
+			(arcsin!(var!()), 1.0 / (1.0 - x * x).sqrt()),
+			(arccos!(var!()), -1.0 / (1.0 - x * x).sqrt()),
+			(arctan!(var!()), 1.0 / (1.0 + x * x)),
+			(arccot!(var!()), -1.0 / (1.0 + x * x)),
+			(ln!(var!()), 1.0 / x),
+			(sqrt!(var!()), 0.5 / x.sqrt()),
+			(abs!(var!()), 1.0),
+			(exp!(var!()), x.exp()),
+			(floor!(var!()), 0.0),
+			(sinh!(var!()), x.cosh()),
+			(cosh!(var!()), x.sinh()),
+			(tanh!(var!()), 1.0 / x.cosh().powi(2)),
+			(arcsinh!(var!()), 1.0 / (x * x + 1.0).sqrt()),
+			(
+				arccosh!(mul!(num!(4.0), var!())),
+				4.0 / (16.0 * x * x - 1.0).sqrt(),
+			),
+			(arctanh!(var!()), 1.0 / (1.0 - x * x)),
+			(log10!(var!()), 1.0 / (x * 10.0f32.ln())),
+			(log2!(var!()), 1.0 / (x * 2.0f32.ln())),
+		];
+
+		for (f, expected) in cases {
+			let df = f.derivative();
+			assert!(
+				(df.eval(x).unwrap() - expected).abs() < 1e-5,
+				"d/dx {f} = {df}"
+			);
+		}
+
+		// chain rule
+		let f = sin!(mul!(num!(2.0), var!())).derivative();
+		assert_eq!(f.to_string(), "cos(2 * x) * (0 * x + 2 * 1)");
+		assert_eq!(f.eval(0.0).unwrap(), 2.0);
+	}
+
+	#[test]
+	fn test_simplify() {
+		let f = sin!(paren!(add!(var!(), num!(0.0)))).simplify();
+		assert_eq!(f.to_string(), "sin(x)");
+
+		let f = sqrt!(add!(num!(1.0), num!(3.0))).simplify();
+		assert_eq!(f.to_string(), "2");
+	}
+
+	#[test]
+	fn test_typeset() {
+		let f = sin!(mul!(num!(2.0), var!()));
+		assert_eq!(f.to_latex(), "\\sin\\left(2 \\cdot x\\right)");
+		assert_eq!(
+			f.to_mathml(),
+			"<mrow><mi>sin</mi><mo>&#x2061;</mo><mrow><mo>(</mo><mrow><mn>2</mn><mo>⋅</mo><mi>x</mi></mrow><mo>)</mo></mrow></mrow>"
+		);
+
+		let f = sqrt!(paren!(add!(var!(), num!(1.0))));
+		assert_eq!(f.to_latex(), "\\sqrt{x + 1}");
+		assert_eq!(
+			f.to_mathml(),
+			"<msqrt><mrow><mi>x</mi><mo>+</mo><mn>1</mn></mrow></msqrt>"
+		);
+
+		let f = arccot!(var!());
+		assert_eq!(f.to_latex(), "\\operatorname{arccot}\\left(x\\right)");
+
+		let f = abs!(var!());
+		assert_eq!(f.to_latex(), "\\left|x\\right|");
+		assert_eq!(f.to_mathml(), "<mrow><mo>|</mo><mi>x</mi><mo>|</mo></mrow>");
+		let f = floor!(var!());
+		assert_eq!(f.to_latex(), "\\left\\lfloor x\\right\\rfloor");
+		let f = log10!(var!());
+		assert_eq!(f.to_latex(), "\\log_{10}\\left(x\\right)");
+	}
+
+	#[test]
+	fn test_complex() {
+		let z = sqrt!(var!()).eval_complex(Complex::from(-4.0)).unwrap();
+		assert!((z - Complex::new(0.0, 2.0)).abs() < 1e-12);
+
+		let z = ln!(var!()).eval_complex(Complex::from(-1.0)).unwrap();
+		assert!((z - Complex::new(0.0, std::f64::consts::PI)).abs() < 1e-12);
+
+		let z = sin!(arcsin!(var!()))
+			.eval_complex(Complex::from(2.0))
+			.unwrap();
+		assert!((z - Complex::from(2.0)).abs() < 1e-12);
+
+		assert_eq!(
+			ln!(var!()).eval_complex(Complex::ZERO),
+			Err(EvalError::LnOfNonPositive(0.0))
+		);
+		assert_eq!(
+			arctan!(var!()).eval_complex(Complex::I),
+			Err(EvalError::ArctanAtPole(1.0))
+		);
+	}
+
+	#[test]
+	fn test_interval() {
+		let y = tan!(var!()).eval_interval(1.0, 2.0).unwrap();
+		assert!(y.is_split() && !y.is_bounded());
+		let y = tan!(var!()).eval_interval(-1.0, 1.0).unwrap();
+		assert!(!y.is_split() && y.is_bounded());
+		let y = cot!(var!()).eval_interval(1.0, 2.0).unwrap();
+		assert!(!y.is_split() && y.is_bounded());
+		assert!(ln!(var!()).eval_interval(-2.0, -1.0).unwrap().is_empty());
+
+		// the bounds contain the sampled values
+		for expr in [
+			"sin(x) * cos(x) + tan(x) - cot(x)",
+			"sec(x) / csc(x) ** 2",
+			"arcsin(x / 10) + arccos(x / 10) + arctan(x) + arccot(x)",
+			"ln(x) - sqrt(pi - x) + e * 3!",
+			"log(x, 8) + log(2, -x)",
+			"(-x) ** 3 - x ** -2 + 2 ** x",
+			"abs(x) + floor(x) * ceil(x) - round(x) + sign(x)",
+			"exp(x / 4) + sinh(x) - cosh(x) * tanh(x)",
+			"arcsinh(x) + arccosh(x) + arctanh(x / 10) + log10(x) - log2(x)",
+			"min(x, 1) + max(sin(x), cos(x)) + atan2(x, x - 1) + hypot(x, 2)",
+			"mod(x, 3) + mod(7, x) + root(3, x) - root(x, 2)",
+		] {
+			let f = crate::expression::parse(expr).unwrap();
+			for i in -100..100 {
+				let (lo, hi) = (f64::from(i) * 0.1, f64::from(i + 1) * 0.1);
+				let y = f.eval_interval(lo, hi).unwrap();
+				for j in 0..=10 {
+					let x = lo + (hi - lo) * f64::from(j) / 10.0;
+					if let Ok(v) = f.eval(x) {
+						assert!(y.contains(v), "{expr} at x = {x}: {v} not in {y}");
+					}
+				}
+			}
+		}
 	}
 }
@@ -88,3 +88,101 @@ macro_rules! sqrt {
 		$crate::func!("sqrt", $e).unwrap()
 	};
 }
+
+#[macro_export]
+macro_rules! abs {
+	($e:expr) => {
+		$crate::func!("abs", $e).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! exp {
+	($e:expr) => {
+		$crate::func!("exp", $e).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! floor {
+	($e:expr) => {
+		$crate::func!("floor", $e).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! ceil {
+	($e:expr) => {
+		$crate::func!("ceil", $e).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! round {
+	($e:expr) => {
+		$crate::func!("round", $e).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! sign {
+	($e:expr) => {
+		$crate::func!("sign", $e).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! sinh {
+	($e:expr) => {
+		$crate::func!("sinh", $e).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! cosh {
+	($e:expr) => {
+		$crate::func!("cosh", $e).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! tanh {
+	($e:expr) => {
+		$crate::func!("tanh", $e).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! arcsinh {
+	($e:expr) => {
+		$crate::func!("arcsinh", $e).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! arccosh {
+	($e:expr) => {
+		$crate::func!("arccosh", $e).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! arctanh {
+	($e:expr) => {
+		$crate::func!("arctanh", $e).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! log10 {
+	($e:expr) => {
+		$crate::func!("log10", $e).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! log2 {
+	($e:expr) => {
+		$crate::func!("log2", $e).unwrap()
+	};
+}
@@ -3,16 +3,42 @@
 #[macro_use]
 mod macros;
 
-use crate::utils::is_equal;
+use crate::consts::EPS;
 
-use super::{Expression, Function};
+use super::{
+	fold, ComplexFunction, Derivative, Env, EvalError, Expression, Function, IntervalFunction,
+	Scalar, Simplify, Typeset,
+};
+use crate::math::{self, Complex, Interval, MAX_POLYGAMMA_ORDER};
+use crate::{abs, add, div, floor, ln, mul, num, pow, sign, sub};
 
-pub const FUNCTION_NAMES: [&str; 1] = ["log"];
+pub const FUNCTION_NAMES: [&str; 8] = [
+	"log",
+	"min",
+	"max",
+	"atan2",
+	"mod",
+	"hypot",
+	"root",
+	"polygamma",
+];
 
 /// Function expression.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Func2 {
 	Log((Expression, Expression)),
+	Min((Expression, Expression)),
+	Max((Expression, Expression)),
+	/// `atan2(y, x)`, the angle of the point `(x, y)` in (-pi, pi].
+	Atan2((Expression, Expression)),
+	/// `mod(a, b)`, the remainder of the floored division, which has the sign of `b`.
+	Mod((Expression, Expression)),
+	Hypot((Expression, Expression)),
+	/// `root(n, x)`, the n-th root of x.
+	Root((Expression, Expression)),
+	/// `polygamma(n, x)`, the (n + 1)-th derivative of `ln(gamma(x))`.
+	Polygamma((Expression, Expression)),
 }
 
 impl Func2 {
@@ -21,35 +47,339 @@ impl Func2 {
 		lhs: L,
 		rhs: R,
 	) -> Option<Self> {
+		let args = (lhs.into(), rhs.into());
 		match f_name.as_ref() {
-			"log" => Some(Self::Log((lhs.into(), rhs.into()))),
+			"log" => Some(Self::Log(args)),
+			"min" => Some(Self::Min(args)),
+			"max" => Some(Self::Max(args)),
+			"atan2" => Some(Self::Atan2(args)),
+			"mod" => Some(Self::Mod(args)),
+			"hypot" => Some(Self::Hypot(args)),
+			"root" => Some(Self::Root(args)),
+			"polygamma" => Some(Self::Polygamma(args)),
 			_ => None,
 		}
 	}
 }
 
+/// Is `a` a valid logarithm base.
+fn is_valid_base<T: Scalar>(a: T) -> bool {
+	a > T::ZERO && (a.to_f64() - 1.0).abs() >= f64::from(EPS)
+}
+
+/// Is `n` an odd integer.
+fn is_odd<T: Scalar>(n: T) -> bool {
+	(n % (T::ONE + T::ONE)).abs() == T::ONE
+}
+
+impl Func2 {
+	/// The function name.
+	#[must_use]
+	pub fn name(&self) -> &'static str {
+		match self {
+			Func2::Log(_) => "log",
+			Func2::Min(_) => "min",
+			Func2::Max(_) => "max",
+			Func2::Atan2(_) => "atan2",
+			Func2::Mod(_) => "mod",
+			Func2::Hypot(_) => "hypot",
+			Func2::Root(_) => "root",
+			Func2::Polygamma(_) => "polygamma",
+		}
+	}
+
+	/// The two argument expressions.
+	#[must_use]
+	pub fn args(&self) -> (&Expression, &Expression) {
+		match self {
+			Func2::Log((lhs, rhs))
+			| Func2::Min((lhs, rhs))
+			| Func2::Max((lhs, rhs))
+			| Func2::Atan2((lhs, rhs))
+			| Func2::Mod((lhs, rhs))
+			| Func2::Hypot((lhs, rhs))
+			| Func2::Root((lhs, rhs))
+			| Func2::Polygamma((lhs, rhs)) => (lhs, rhs),
+		}
+	}
+
+	/// Create the same function of the arguments mapped by `f`.
+	pub(crate) fn map<F: FnMut(&Expression) -> Expression>(&self, mut f: F) -> Self {
+		let (lhs, rhs) = self.args();
+		Self::new(self.name(), f(lhs), f(rhs)).unwrap()
+	}
+
+	/// The function of the argument values.
+	///
+	/// The function returns an error if the values are not in the definition domain.
+	pub(crate) fn scalar_fn<T: Scalar>(&self) -> fn(T, T) -> Result<T, EvalError> {
+		match self {
+			Func2::Log(_) => |a, x| {
+				if !is_valid_base(a) {
+					Err(EvalError::InvalidLogBase(a.to_f64()))
+				} else if x > T::ZERO {
+					Ok(x.log(a))
+				} else {
+					Err(EvalError::LogOfNonPositive(x.to_f64()))
+				}
+			},
+			Func2::Min(_) => |a, b| Ok(if b < a { b } else { a }),
+			Func2::Max(_) => |a, b| Ok(if b > a { b } else { a }),
+			Func2::Atan2(_) => |y, x| Ok(y.atan2(x)),
+			Func2::Mod(_) => |a, b| {
+				if b == T::ZERO {
+					return Err(EvalError::DivisionByZero);
+				}
+
+				// the truncated remainder has the sign of a
+				let r = a % b;
+				Ok(if r != T::ZERO && (r < T::ZERO) != (b < T::ZERO) {
+					r + b
+				} else {
+					r
+				})
+			},
+			Func2::Hypot(_) => |a, b| Ok(a.hypot(b)),
+			Func2::Root(_) => |n, x| {
+				if n == T::ZERO || !n.is_finite() {
+					Err(EvalError::InvalidRootIndex(n.to_f64()))
+				} else if x == T::ZERO && n < T::ZERO {
+					Err(EvalError::ZeroToNegativePower((T::ONE / n).to_f64()))
+				} else if x >= T::ZERO {
+					Ok(x.powf(T::ONE / n))
+				} else if is_odd(n) {
+					// odd roots of negative numbers are real
+					Ok(-(-x).powf(T::ONE / n))
+				} else {
+					Err(EvalError::RootOfNegative(n.to_f64(), x.to_f64()))
+				}
+			},
+			Func2::Polygamma(_) => |n, x| {
+				let (n, x) = (n.to_f64(), x.to_f64());
+				if n < 0.0 || n.fract() != 0.0 || n > MAX_POLYGAMMA_ORDER {
+					Err(EvalError::InvalidPolygammaOrder(n))
+				} else if math::is_gamma_pole(x) {
+					Err(EvalError::PolygammaAtPole(x))
+				} else {
+					Ok(T::from_f64(math::polygamma(n, x)))
+				}
+			},
+		}
+	}
+}
+
 impl std::fmt::Display for Func2 {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let (lhs, rhs) = self.args();
+		write!(f, "{}({lhs}, {rhs})", self.name())
+	}
+}
+
+impl Function for Func2 {
+	fn eval_with<T: Scalar>(&self, env: &Env<T>) -> Result<T, EvalError> {
+		let (lhs, rhs) = self.args();
+		self.scalar_fn()(lhs.eval_with(env)?, rhs.eval_with(env)?)
+	}
+}
+
+impl ComplexFunction for Func2 {
+	fn eval_complex_with(&self, env: &Env<Complex>) -> Result<Complex, EvalError> {
+		let (lhs, rhs) = self.args();
+		let (a, z) = (lhs.eval_complex_with(env)?, rhs.eval_complex_with(env)?);
+
 		match self {
-			Func2::Log((lhs, rhs)) => write!(f, "log({lhs}, {rhs})"),
+			Func2::Log(_) => {
+				// log(a, z) = ln(z) / ln(a)
+				let ln_a = a.ln();
+				if !ln_a.is_finite() || ln_a.is_zero() {
+					Err(EvalError::InvalidLogBase(a.re))
+				} else if z.is_zero() {
+					Err(EvalError::LogOfNonPositive(z.re))
+				} else {
+					Ok(z.ln() / ln_a)
+				}
+			}
+			Func2::Root(_) => {
+				// root(n, z) = z ** (1 / n)
+				if a.is_zero() || !a.is_finite() {
+					return Err(EvalError::InvalidRootIndex(a.re));
+				}
+				let exp = Complex::ONE / a;
+				if z.is_zero() && exp.re <= 0.0 {
+					Err(EvalError::ZeroToNegativePower(exp.re))
+				} else {
+					Ok(z.powc(exp))
+				}
+			}
+			// the others compare or round real numbers
+			_ => {
+				if a.im == 0.0 && z.im == 0.0 {
+					self.scalar_fn()(a.re, z.re).map(Complex::from)
+				} else {
+					Err(EvalError::ComplexArgument(self.name().to_string()))
+				}
+			}
 		}
 	}
 }
 
-impl Function for Func2 {
-	fn is_x_valid(&self, x: f32) -> bool {
+impl IntervalFunction for Func2 {
+	fn eval_interval_with(&self, env: &Env<Interval>) -> Result<Interval, EvalError> {
+		let (lhs, rhs) = self.args();
+		let (a, b) = (lhs.eval_interval_with(env)?, rhs.eval_interval_with(env)?);
+
+		Ok(match self {
+			Func2::Log(_) => {
+				// only the valid bases, i.e., positive and not close to 1
+				let eps = f64::from(EPS);
+				let bases =
+					Interval::new(0.0, 1.0 - eps).union(&Interval::new(1.0 + eps, f64::INFINITY));
+				b.ln() / a.intersect(&bases).ln()
+			}
+			Func2::Min(_) => a.min(&b),
+			Func2::Max(_) => a.max(&b),
+			Func2::Atan2(_) => a.atan2(&b),
+			Func2::Mod(_) => a.modulo(&b),
+			Func2::Hypot(_) => a.hypot(&b),
+			Func2::Root(_) => b.root(&a),
+			Func2::Polygamma(_) => b.polygamma(&a),
+		})
+	}
+}
+
+impl Derivative for Func2 {
+	fn is_constant(&self) -> bool {
+		let (lhs, rhs) = self.args();
+		lhs.is_constant() && rhs.is_constant()
+	}
+
+	fn derivative(&self) -> Expression {
+		let (f, g) = self.args();
+		let (df, dg) = (f.derivative(), g.derivative());
+
+		match self {
+			Func2::Log(_) => {
+				if f.is_constant() {
+					// log(a, g)' = g' / (g * ln(a))
+					div!(dg, mul!(g.clone(), ln!(f.clone()))).into()
+				} else {
+					// log(f, g) = ln(g) / ln(f)
+					// log(f, g)' = (g' / g * ln(f) - f' / f * ln(g)) / ln(f) ** 2
+					div!(
+						sub!(
+							mul!(div!(dg, g.clone()), ln!(f.clone())),
+							mul!(div!(df, f.clone()), ln!(g.clone()))
+						),
+						pow!(ln!(f.clone()), num!(2.0))
+					)
+					.into()
+				}
+			}
+			// min(f, g) = (f + g - |f - g|) / 2
+			Func2::Min(_) => div!(
+				sub!(
+					add!(df.clone(), dg.clone()),
+					mul!(sign!(sub!(f.clone(), g.clone())), sub!(df, dg))
+				),
+				num!(2.0)
+			)
+			.into(),
+			// max(f, g) = (f + g + |f - g|) / 2
+			Func2::Max(_) => div!(
+				add!(
+					add!(df.clone(), dg.clone()),
+					mul!(sign!(sub!(f.clone(), g.clone())), sub!(df, dg))
+				),
+				num!(2.0)
+			)
+			.into(),
+			// atan2(f, g)' = (g * f' - f * g') / (f ** 2 + g ** 2)
+			Func2::Atan2(_) => div!(
+				sub!(mul!(g.clone(), df), mul!(f.clone(), dg)),
+				add!(pow!(f.clone(), num!(2.0)), pow!(g.clone(), num!(2.0)))
+			)
+			.into(),
+			// mod(f, g) = f - g * floor(f / g)
+			Func2::Mod(_) => sub!(df, mul!(dg, floor!(div!(f.clone(), g.clone())))).into(),
+			// hypot(f, g)' = (f * f' + g * g') / hypot(f, g)
+			Func2::Hypot(_) => div!(
+				add!(mul!(f.clone(), df), mul!(g.clone(), dg)),
+				hypot!(f.clone(), g.clone())
+			)
+			.into(),
+			Func2::Root(_) => {
+				let root: Expression = self.clone().into();
+				if f.is_constant() {
+					// root(n, g)' = root(n, g) * g' / (n * g)
+					mul!(root, div!(dg, mul!(f.clone(), g.clone()))).into()
+				} else {
+					// root(f, g) = |g| ** (1 / f) up to the sign
+					// root(f, g)' = root(f, g) * (g' / (f * g) - f' * ln(|g|) / f ** 2)
+					mul!(
+						root,
+						sub!(
+							div!(dg, mul!(f.clone(), g.clone())),
+							div!(mul!(df, ln!(abs!(g.clone()))), pow!(f.clone(), num!(2.0)))
+						)
+					)
+					.into()
+				}
+			}
+			// polygamma(n, g)' = polygamma(n + 1, g) * g'
+			Func2::Polygamma(_) => {
+				mul!(polygamma!(add!(f.clone(), num!(1.0)), g.clone()), dg).into()
+			}
+		}
+	}
+}
+
+impl Simplify for Func2 {
+	fn simplify(&self) -> Expression {
+		let (lhs, rhs) = self.args();
+		fold(
+			func2!(self.name(), lhs.simplify(), rhs.simplify())
+				.unwrap()
+				.into(),
+		)
+	}
+}
+
+impl Typeset for Func2 {
+	fn to_latex(&self) -> String {
+		let (lhs, rhs) = self.args();
+		let (lhs, rhs) = (lhs.to_latex(), rhs.to_latex());
+
 		match self {
-			Func2::Log((lhs, rhs)) => lhs.eval(x).is_some_and(|a| {
-				a > 0.0 && !is_equal(a, 1.0) && rhs.eval(x).is_some_and(|x| x > 0.0)
-			}),
+			Func2::Log(_) => format!("\\log_{{{lhs}}}\\left({rhs}\\right)"),
+			Func2::Root(_) => format!("\\sqrt[{lhs}]{{{rhs}}}"),
+			Func2::Polygamma(_) => format!("\\psi^{{\\left({lhs}\\right)}}\\left({rhs}\\right)"),
+			Func2::Min(_) | Func2::Max(_) => {
+				format!("\\{}\\left({lhs}, {rhs}\\right)", self.name())
+			}
+			// no predefined operator in LaTeX
+			_ => format!(
+				"\\operatorname{{{}}}\\left({lhs}, {rhs}\\right)",
+				self.name()
+			),
 		}
 	}
 
-	fn eval(&self, x: f32) -> Option<f32> {
+	fn to_mathml(&self) -> String {
+		let (lhs, rhs) = self.args();
+		let (lhs, rhs) = (lhs.to_mathml(), rhs.to_mathml());
+
 		match self {
-			Func2::Log((lhs, rhs)) => self
-				.is_x_valid(x)
-				.then_some(rhs.eval(x).unwrap().log(lhs.eval(x).unwrap())),
+			Func2::Log(_) => format!(
+				"<mrow><msub><mi>log</mi>{lhs}</msub><mo>&#x2061;</mo><mrow><mo>(</mo>{rhs}<mo>)</mo></mrow></mrow>"
+			),
+			Func2::Root(_) => format!("<mroot>{rhs}{lhs}</mroot>"),
+			Func2::Polygamma(_) => format!(
+				"<mrow><msup><mi>ψ</mi><mrow><mo>(</mo>{lhs}<mo>)</mo></mrow></msup><mo>&#x2061;</mo><mrow><mo>(</mo>{rhs}<mo>)</mo></mrow></mrow>"
+			),
+			_ => format!(
+				"<mrow><mi>{}</mi><mo>&#x2061;</mo><mrow><mo>(</mo>{lhs}<mo>,</mo>{rhs}<mo>)</mo></mrow></mrow>",
+				self.name()
+			),
 		}
 	}
 }
@@ -63,12 +393,144 @@ impl From<Func2> for Expression {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::{num, var};
+	use crate::{mul, num, var};
 
 	#[test]
 	fn test_log() {
 		let f = log!(num!(10.0), var!());
 		assert_eq!(f.eval(1.0).unwrap(), 0.0);
 		assert_eq!(f.eval(10.0).unwrap(), 1.0);
+		assert_eq!(f.eval(0.0), Err(EvalError::LogOfNonPositive(0.0)));
+
+		let f = log!(var!(), num!(2.0));
+		assert_eq!(f.eval(1.0), Err(EvalError::InvalidLogBase(1.0)));
+	}
+
+	#[test]
+	fn test_log_derivative() {
+		let f = log!(num!(10.0), var!()).derivative();
+		assert!((f.eval(2.0).unwrap() - 1.0 / (2.0 * 10.0f32.ln())).abs() < 1e-6);
+
+		// log(x, 8) = ln(8) / ln(x)
+		let f = log!(var!(), num!(8.0)).derivative();
+		let expected = -8.0f32.ln() / (2.0 * 2.0f32.ln().powi(2));
+		assert!((f.eval(2.0).unwrap() - expected).abs() < 1e-6);
+	}
+
+	#[test]
+	fn test_log_typeset() {
+		let f = log!(num!(2.0), var!());
+		assert_eq!(f.to_latex(), "\\log_{2}\\left(x\\right)");
+		assert_eq!(
+			f.to_mathml(),
+			"<mrow><msub><mi>log</mi><mn>2</mn></msub><mo>&#x2061;</mo><mrow><mo>(</mo><mi>x</mi><mo>)</mo></mrow></mrow>"
+		);
+	}
+
+	#[test]
+	fn test_min_max() {
+		assert_eq!(min!(var!(), num!(1.0)).eval(2.0).unwrap(), 1.0);
+		assert_eq!(max!(var!(), num!(1.0)).eval(2.0).unwrap(), 2.0);
+		assert_eq!(hypot!(var!(), num!(4.0)).eval(3.0).unwrap(), 5.0);
+		assert_eq!(
+			atan2!(num!(1.0), var!()).eval(0.0).unwrap(),
+			std::f32::consts::FRAC_PI_2
+		);
+	}
+
+	#[test]
+	fn test_mod() {
+		let f = modulo!(var!(), num!(3.0));
+		assert_eq!(f.eval(7.0).unwrap(), 1.0);
+		assert_eq!(f.eval(-7.0).unwrap(), 2.0);
+		let f = modulo!(var!(), num!(-3.0));
+		assert_eq!(f.eval(7.0).unwrap(), -2.0);
+		let f = modulo!(num!(1.0), var!());
+		assert_eq!(f.eval(0.0), Err(EvalError::DivisionByZero));
+	}
+
+	#[test]
+	fn test_root() {
+		let f = root!(num!(3.0), var!());
+		assert_eq!(f.eval(8.0).unwrap(), 2.0);
+		assert_eq!(f.eval(-8.0).unwrap(), -2.0);
+		let f = root!(num!(2.0), var!());
+		assert_eq!(f.eval(-4.0), Err(EvalError::RootOfNegative(2.0, -4.0)));
+		let f = root!(var!(), num!(2.0));
+		assert_eq!(f.eval(0.0), Err(EvalError::InvalidRootIndex(0.0)));
+
+		let z = root!(num!(2.0), var!())
+			.eval_complex(Complex::from(-4.0))
+			.unwrap();
+		assert!((z - Complex::new(0.0, 2.0)).abs() < 1e-12);
+		assert_eq!(
+			min!(var!(), num!(1.0)).eval_complex(Complex::I),
+			Err(EvalError::ComplexArgument("min".to_string()))
+		);
+	}
+
+	#[test]
+	fn test_polygamma() {
+		let f = polygamma!(num!(0.0), var!());
+		assert!((f.eval(1.0f64).unwrap() + 0.577_215_664_901_532_9).abs() < 1e-14);
+		assert!((f.eval(-0.5f64).unwrap() - 0.036_489_973_978_576_52).abs() < 1e-14);
+		assert_eq!(f.eval(-1.0), Err(EvalError::PolygammaAtPole(-1.0)));
+		let f = polygamma!(var!(), num!(1.0));
+		assert_eq!(f.eval(0.5), Err(EvalError::InvalidPolygammaOrder(0.5)));
+		assert_eq!(f.eval(-1.0), Err(EvalError::InvalidPolygammaOrder(-1.0)));
+
+		// trigamma(1) = pi ** 2 / 6
+		let f = polygamma!(num!(1.0), var!());
+		let pi2 = std::f64::consts::PI.powi(2);
+		assert!((f.eval(1.0f64).unwrap() - pi2 / 6.0).abs() < 1e-14);
+		assert_eq!(f.to_string(), "polygamma(1, x)");
+		assert_eq!(f.to_latex(), "\\psi^{\\left(1\\right)}\\left(x\\right)");
+
+		let y = f.eval_interval(1.0, 2.0).unwrap();
+		assert!(y.contains(pi2 / 6.0) && y.contains(pi2 / 6.0 - 1.0));
+		assert!(!f.eval_interval(-1.5, -0.5).unwrap().is_bounded());
+	}
+
+	#[test]
+	fn test_derivative() {
+		let x = 0.5f32;
+		let cases = [
+			(min!(var!(), num!(1.0)), 1.0),
+			(max!(var!(), num!(1.0)), 0.0),
+			(atan2!(var!(), num!(1.0)), 1.0 / (1.0 + x * x)),
+			(modulo!(mul!(num!(3.0), var!()), num!(1.0)), 3.0),
+			(hypot!(var!(), num!(1.0)), x / (x * x + 1.0).sqrt()),
+			(root!(num!(3.0), var!()), x.cbrt() / (3.0 * x)),
+			// trigamma(1 / 2) = pi ** 2 / 2
+			(
+				polygamma!(num!(0.0), var!()),
+				std::f32::consts::PI.powi(2) / 2.0,
+			),
+			// x ** (1 / x)
+			(
+				root!(var!(), var!()),
+				x.powf(1.0 / x) * (1.0 - x.ln()) / (x * x),
+			),
+		];
+
+		for (f, expected) in cases {
+			let df = f.derivative();
+			assert!(
+				(df.eval(x).unwrap() - expected).abs() < 1e-5,
+				"d/dx {f} = {df}"
+			);
+		}
+	}
+
+	#[test]
+	fn test_typeset() {
+		let f = root!(num!(3.0), var!());
+		assert_eq!(f.to_latex(), "\\sqrt[3]{x}");
+		assert_eq!(f.to_mathml(), "<mroot><mi>x</mi><mn>3</mn></mroot>");
+		let f = max!(var!(), num!(1.0));
+		assert_eq!(f.to_latex(), "\\max\\left(x, 1\\right)");
+		let f = modulo!(var!(), num!(2.0));
+		assert_eq!(f.to_string(), "mod(x, 2)");
+		assert_eq!(f.to_latex(), "\\operatorname{mod}\\left(x, 2\\right)");
 	}
 }
@@ -11,3 +11,52 @@ macro_rules! log {
 		$crate::func2!("log", $lhs, $rhs).unwrap()
 	};
 }
+
+#[macro_export]
+macro_rules! min {
+	($lhs:expr, $rhs:expr) => {
+		$crate::func2!("min", $lhs, $rhs).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! max {
+	($lhs:expr, $rhs:expr) => {
+		$crate::func2!("max", $lhs, $rhs).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! atan2 {
+	($lhs:expr, $rhs:expr) => {
+		$crate::func2!("atan2", $lhs, $rhs).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! modulo {
+	($lhs:expr, $rhs:expr) => {
+		$crate::func2!("mod", $lhs, $rhs).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! hypot {
+	($lhs:expr, $rhs:expr) => {
+		$crate::func2!("hypot", $lhs, $rhs).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! root {
+	($lhs:expr, $rhs:expr) => {
+		$crate::func2!("root", $lhs, $rhs).unwrap()
+	};
+}
+
+#[macro_export]
+macro_rules! polygamma {
+	($lhs:expr, $rhs:expr) => {
+		$crate::func2!("polygamma", $lhs, $rhs).unwrap()
+	};
+}
@@ -0,0 +1,296 @@
+use super::{
+	fold, latex_operand, mathml_operand, ComplexFunction, Derivative, Env, EvalError, Expression,
+	Function, IntervalFunction, Operator, Scalar, Simplify, Typeset,
+};
+use crate::{
+	math::{Complex, Interval},
+	mul,
+	numerics::{integrate_fn, IntegrationError},
+	sub,
+};
+
+/// Name of the definite integral.
+pub const INTEGRAL: &str = "integral";
+
+/// Absolute tolerance of the integrals in expressions.
+const TOLERANCE: f64 = 1e-10;
+
+/// Definite integral of the integrand over x between the bounds, e.g., `integral(sin(x), 0, pi)`.
+///
+/// x in the integrand is the variable of integration, which hides x outside, so
+/// `integral(sin(x), 0, x)` is the antiderivative of `sin(x)` that is 0 at 0.
+///
+/// ```bnf
+/// integral ::= "integral" "(" sub_expr "," sub_expr "," sub_expr ")"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Integral {
+	integrand: Expression,
+	lower: Expression,
+	upper: Expression,
+}
+
+impl Integral {
+	pub fn new<F: Into<Expression>, A: Into<Expression>, B: Into<Expression>>(
+		integrand: F,
+		lower: A,
+		upper: B,
+	) -> Self {
+		Self {
+			integrand: integrand.into(),
+			lower: lower.into(),
+			upper: upper.into(),
+		}
+	}
+
+	/// The integrand, a function of the variable of integration x.
+	#[must_use]
+	pub fn integrand(&self) -> &Expression {
+		&self.integrand
+	}
+
+	/// The lower and upper bounds.
+	#[must_use]
+	pub fn bounds(&self) -> (&Expression, &Expression) {
+		(&self.lower, &self.upper)
+	}
+
+	/// Replace the variables, except x in the integrand, which is bound by the integral.
+	///
+	/// Return `None` if the integrand uses a variable whose replacement uses x, which would be
+	/// captured as the variable of integration.
+	pub(crate) fn substitute_all(&self, bindings: &[(String, Expression)]) -> Option<Self> {
+		let inner: Vec<_> = bindings
+			.iter()
+			.filter(|(name, _)| name != "x" && self.integrand.uses(name))
+			.cloned()
+			.collect();
+		if inner.iter().any(|(_, expr)| expr.uses("x")) {
+			return None;
+		}
+
+		Some(Self {
+			integrand: self.integrand.substitute_all(&inner)?,
+			lower: self.lower.substitute_all(bindings)?,
+			upper: self.upper.substitute_all(bindings)?,
+		})
+	}
+
+	/// Create the integral with the bounds mapped by `f`.
+	pub(crate) fn map_bounds<F: FnMut(&Expression) -> Expression>(&self, mut f: F) -> Self {
+		Self {
+			integrand: self.integrand.clone(),
+			lower: f(&self.lower),
+			upper: f(&self.upper),
+		}
+	}
+
+	/// Create the integral with the integrand and the bounds mapped by `f`.
+	pub(crate) fn map<F: FnMut(&Expression) -> Expression>(&self, mut f: F) -> Self {
+		Self {
+			integrand: f(&self.integrand),
+			lower: f(&self.lower),
+			upper: f(&self.upper),
+		}
+	}
+
+	/// The integrand with the variable of integration replaced by the bound.
+	fn integrand_at(&self, bound: &Expression) -> Expression {
+		self.integrand.compose(bound)
+	}
+}
+
+#[macro_export]
+macro_rules! integral {
+	($integrand:expr, $lower:expr, $upper:expr) => {
+		$crate::expression::Integral::new($integrand, $lower, $upper)
+	};
+}
+
+/// Integrate `g` from `lower` to `upper` to the tolerance of expressions.
+pub(crate) fn quadrature<G: FnMut(f64) -> Option<f64>>(
+	g: G,
+	lower: f64,
+	upper: f64,
+) -> Result<f64, EvalError> {
+	integrate_fn(g, lower, upper, TOLERANCE).map_err(|err| match err {
+		IntegrationError::Undefined(x) => EvalError::UndefinedIntegrand(x),
+		IntegrationError::Divergent => EvalError::DivergentIntegral,
+	})
+}
+
+impl std::fmt::Display for Integral {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"{INTEGRAL}({}, {}, {})",
+			self.integrand, self.lower, self.upper
+		)
+	}
+}
+
+impl Function for Integral {
+	fn eval_with<T: Scalar>(&self, env: &Env<T>) -> Result<T, EvalError> {
+		let lower = self.lower.eval_with(env)?.to_f64();
+		let upper = self.upper.eval_with(env)?.to_f64();
+
+		// the integrand is evaluated in f64, since the rounding error of f32 exceeds the tolerance
+		let mut inner = env.map(|v| v.to_f64());
+		quadrature(
+			|t| {
+				inner.set("x", t);
+				self.integrand
+					.eval_with(&inner)
+					.ok()
+					.filter(|y| y.is_finite())
+			},
+			lower,
+			upper,
+		)
+		.map(T::from_f64)
+	}
+}
+
+impl ComplexFunction for Integral {
+	/// Integral along the real axis, where the integrand is undefined unless it is real.
+	fn eval_complex_with(&self, env: &Env<Complex>) -> Result<Complex, EvalError> {
+		let lower = self.lower.eval_complex_with(env)?;
+		let upper = self.upper.eval_complex_with(env)?;
+		if lower.im != 0.0 || upper.im != 0.0 {
+			return Err(EvalError::ComplexArgument(INTEGRAL.to_string()));
+		}
+
+		let mut inner = env.clone();
+		quadrature(
+			|t| {
+				inner.set("x", Complex::from(t));
+				self.integrand
+					.eval_complex_with(&inner)
+					.ok()
+					.filter(|z| z.im == 0.0 && z.re.is_finite())
+					.map(|z| z.re)
+			},
+			lower.re,
+			upper.re,
+		)
+		.map(Complex::from)
+	}
+}
+
+impl IntervalFunction for Integral {
+	fn eval_interval_with(&self, _env: &Env<Interval>) -> Result<Interval, EvalError> {
+		Err(EvalError::NoIntervalEvaluation(INTEGRAL.to_string()))
+	}
+}
+
+impl Derivative for Integral {
+	fn is_constant(&self) -> bool {
+		self.lower.is_constant() && self.upper.is_constant()
+	}
+
+	/// Fundamental theorem of calculus, `f(b) * b' - f(a) * a'`.
+	fn derivative(&self) -> Expression {
+		sub!(
+			mul!(self.integrand_at(&self.upper), self.upper.derivative()),
+			mul!(self.integrand_at(&self.lower), self.lower.derivative())
+		)
+		.into()
+	}
+}
+
+impl Simplify for Integral {
+	fn simplify(&self) -> Expression {
+		fold(
+			Self {
+				integrand: self.integrand.simplify(),
+				lower: self.lower.simplify(),
+				upper: self.upper.simplify(),
+			}
+			.into(),
+		)
+	}
+}
+
+impl Typeset for Integral {
+	fn to_latex(&self) -> String {
+		let paren = self.integrand.typeset_precedence() < Operator::Mul.precedence();
+		format!(
+			"\\int_{{{}}}^{{{}}} {} \\, dx",
+			self.lower.to_latex(),
+			self.upper.to_latex(),
+			latex_operand(&self.integrand, paren)
+		)
+	}
+
+	fn to_mathml(&self) -> String {
+		let paren = self.integrand.typeset_precedence() < Operator::Mul.precedence();
+		format!(
+			"<mrow><msubsup><mo>∫</mo>{}{}</msubsup>{}<mo>&#x2062;</mo><mrow><mi>d</mi><mi>x</mi></mrow></mrow>",
+			self.lower.to_mathml(),
+			self.upper.to_mathml(),
+			mathml_operand(&self.integrand, paren)
+		)
+	}
+}
+
+impl From<Integral> for Expression {
+	fn from(val: Integral) -> Self {
+		Expression::Integral(val.into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{add, constant, div, num, sin, sqrt, var};
+
+	#[test]
+	fn test_integral() {
+		let f = integral!(sin!(var!()), num!(0.0), constant!("pi").unwrap());
+		assert_eq!(f.to_string(), "integral(sin(x), 0, pi)");
+		assert!((f.eval(0.0).unwrap() - 2.0).abs() < 1e-10);
+		assert!(f.is_constant());
+
+		// antiderivative of sin(x)
+		let f = integral!(sin!(var!()), num!(0.0), var!());
+		assert!((f.eval(1.0f64).unwrap() - (1.0 - 1f64.cos())).abs() < 1e-10);
+		assert!((f.eval(-1.0f64).unwrap() - (1.0 - 1f64.cos())).abs() < 1e-10);
+		assert_eq!(f.derivative().simplify().to_string(), "sin(x)");
+		assert!(
+			(f.eval_complex(Complex::from(1.0)).unwrap().re - (1.0 - 1f64.cos())).abs() < 1e-10
+		);
+
+		// other variables are bound outside
+		let f = integral!(mul!(var!("t"), var!()), num!(0.0), num!(2.0));
+		let env = Env::new().with("t", 3.0f64).with("x", 100.0);
+		assert!((f.eval_with(&env).unwrap() - 6.0).abs() < 1e-10);
+	}
+
+	#[test]
+	fn test_integral_error() {
+		let f = integral!(div!(num!(1.0), var!()), num!(-1.0), num!(1.0));
+		assert_eq!(f.eval(0.0f64), Err(EvalError::DivergentIntegral));
+
+		let f = integral!(sqrt!(var!()), num!(-1.0), var!());
+		assert!(matches!(
+			f.eval(1.0f64),
+			Err(EvalError::UndefinedIntegrand(x)) if x < 0.0
+		));
+		assert!((f.eval(-1.0f64).unwrap()).abs() < 1e-10);
+		assert_eq!(
+			f.eval_interval(0.0, 1.0),
+			Err(EvalError::NoIntervalEvaluation(INTEGRAL.to_string()))
+		);
+	}
+
+	#[test]
+	fn test_integral_typeset() {
+		let f = integral!(add!(var!(), num!(1.0)), num!(0.0), num!(1.0));
+		assert_eq!(f.to_latex(), "\\int_{0}^{1} \\left(x + 1\\right) \\, dx");
+		assert_eq!(
+			f.to_mathml(),
+			"<mrow><msubsup><mo>∫</mo><mn>0</mn><mn>1</mn></msubsup><mrow><mo>(</mo><mrow><mi>x</mi><mo>+</mo><mn>1</mn></mrow><mo>)</mo></mrow><mo>&#x2062;</mo><mrow><mi>d</mi><mi>x</mi></mrow></mrow>"
+		);
+	}
+}
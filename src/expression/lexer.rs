@@ -1,23 +1,55 @@
-// NOTE: longer first
-pub const OPERATORS: [&str; 5] = ["**", "+", "-", "*", "/"];
+use super::{Operator, NOT, OPERATORS};
+
+/// Unicode symbols and their ASCII spellings, which are lexed as the latter.
+const SYMBOLS: [(char, &str); 9] = [
+	('π', "pi"),
+	('×', "*"),
+	('·', "*"),
+	('⋅', "*"),
+	('÷', "/"),
+	('−', "-"),
+	('≤', "<="),
+	('≥', ">="),
+	('≠', "!="),
+];
+
+/// Superscript digits from 0 to 9.
+const SUPERSCRIPT_DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+/// Superscript minus sign.
+const SUPERSCRIPT_MINUS: char = '⁻';
+
+/// Radical sign of the square root.
+const RADICAL: char = '√';
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Token {
 	WhiteSpace(char),
 	/// ```bnf
-	/// number ::= (digit)+ ["." (digit)+]
+	/// number ::= digits ["." digits] [exponent] | "." digits [exponent] | "0x" (hex_digit)+
+	/// digits ::= digit (["_"] digit)*
+	/// exponent ::= ("e" | "E") ["+" | "-"] digits
 	/// ```
-	Number(f32),
+	Number(f64),
+	/// Postfix `!`, which is not followed by `=`.
+	Factorial,
+	/// Exponent in superscript, e.g., `²` in `x²`.
+	///
 	/// ```bnf
-	/// factorial ::= (digit)+ "!"
+	/// superscript ::= ["⁻"] ("⁰" | "¹" | ... | "⁹")+
 	/// ```
-	Factorial(u32),
+	Superscript(f64),
+	/// Prefix `√` of the square root.
+	Radical,
 	/// ```bnf
-	/// id ::= ("a"..."z" | "A"..."Z")+
+	/// id ::= letter (letter | digit | "_")*
+	/// letter ::= "a"..."z" | "A"..."Z"
 	/// ```
 	Identifier(String),
 	/// ```bnf
-	/// op ::= "+" | "-" | "*" | "/"
+	/// op ::= "+" | "-" | "*" | "/" | "**" | "^"
+	///      | "<" | "<=" | "==" | "!=" | ">=" | ">" | "and" | "or" | "not"
+	/// ```
 	Operator(String),
 	OpenParenthesis,
 	CloseParenthesis,
@@ -87,40 +119,217 @@ impl Token {
 		}
 	}
 
+	#[inline]
+	#[must_use]
+	pub fn is_equal_sign(&self) -> bool {
+		if let Self::Other(s) = self {
+			s == "="
+		} else {
+			false
+		}
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn is_semicolon(&self) -> bool {
+		if let Self::Other(s) = self {
+			s == ";"
+		} else {
+			false
+		}
+	}
+
 	/// Token precedence.
 	///
 	/// Return `Some` if self is an operator.
 	#[inline]
 	#[must_use]
 	pub fn precedence(&self) -> Option<u8> {
+		self.operator().map(Operator::precedence)
+	}
+
+	/// The binary operator of the token.
+	///
+	/// Return `Some` if self is an operator.
+	#[inline]
+	#[must_use]
+	pub fn operator(&self) -> Option<Operator> {
 		if let Token::Operator(op) = self {
-			Some(match op.as_str() {
-				"+" | "-" => 10,
-				"*" | "/" => 20,
-				"**" => 30,
-				_ => unreachable!(),
-			})
+			Operator::from_symbol(op)
 		} else {
 			None
 		}
 	}
+
+	/// Can the token start the right operand of an implicit multiplication, e.g., `x` in `2x`.
+	#[inline]
+	#[must_use]
+	pub fn is_implicit_operand(&self) -> bool {
+		matches!(
+			self,
+			Self::Identifier(_) | Self::OpenParenthesis | Self::Radical
+		)
+	}
+}
+
+impl std::fmt::Display for Token {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Token::WhiteSpace(c) => write!(f, "{c}"),
+			Token::Number(n) => write!(f, "{n}"),
+			Token::Factorial => write!(f, "!"),
+			Token::Superscript(n) => {
+				let s: String = n
+					.to_string()
+					.chars()
+					.map(|c| match c.to_digit(10) {
+						Some(d) => SUPERSCRIPT_DIGITS[d as usize],
+						None if c == '-' => SUPERSCRIPT_MINUS,
+						None => c,
+					})
+					.collect();
+				write!(f, "{s}")
+			}
+			Token::Radical => write!(f, "{RADICAL}"),
+			Token::Identifier(s) | Token::Operator(s) | Token::Other(s) => write!(f, "{s}"),
+			Token::OpenParenthesis => write!(f, "("),
+			Token::CloseParenthesis => write!(f, ")"),
+			Token::Eof => write!(f, "EOF"),
+		}
+	}
+}
+
+/// Byte range of a token in the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+	pub start: usize,
+	pub end: usize,
+}
+
+impl Span {
+	#[must_use]
+	pub fn new(start: usize, end: usize) -> Self {
+		Self { start, end }
+	}
 }
 
 #[derive(Debug, Clone)]
 pub struct Lexer {
 	chars: Vec<char>,
+	/// byte offsets of chars, with the source length at the end
+	offsets: Vec<usize>,
 	/// current index
 	i: usize,
 }
 
 impl Lexer {
 	pub fn new<T: ToString>(expr: T) -> Self {
+		let expr = expr.to_string();
+		let offsets = expr
+			.char_indices()
+			.map(|(offset, _)| offset)
+			.chain(std::iter::once(expr.len()))
+			.collect();
+
 		Self {
-			chars: expr.to_string().chars().collect(),
+			chars: expr.chars().collect(),
+			offsets,
 			i: 0,
 		}
 	}
 
+	/// Empty span at the end of the source.
+	#[must_use]
+	pub fn eof_span(&self) -> Span {
+		let len = self.offsets[self.chars.len()];
+		Span::new(len, len)
+	}
+
+	/// Is the char at `i` an ASCII digit.
+	fn is_digit(&self, i: usize) -> bool {
+		self.chars.get(i).is_some_and(char::is_ascii_digit)
+	}
+
+	/// End of the digits from `i`, where a `_` may separate two digits, e.g., `1_000`.
+	fn skip_digits(&self, mut i: usize) -> usize {
+		while self.is_digit(i) || (self.chars.get(i) == Some(&'_') && self.is_digit(i + 1)) {
+			i += 1;
+		}
+		i
+	}
+
+	/// Lex the number from `i`.
+	fn get_number(&self, i: usize) -> (Token, usize) {
+		let chars = &self.chars;
+
+		// hexadecimal
+		if chars[i] == '0'
+			&& matches!(chars.get(i + 1), Some('x' | 'X'))
+			&& chars.get(i + 2).is_some_and(char::is_ascii_hexdigit)
+		{
+			let mut j = i + 2;
+			let mut n = 0.0;
+			while let Some(d) = chars.get(j).and_then(|c| c.to_digit(16)) {
+				n = n * 16.0 + f64::from(d);
+				j += 1;
+			}
+			return (Token::Number(n), j);
+		}
+
+		let mut j = self.skip_digits(i);
+		if chars.get(j) == Some(&'.') {
+			j += 1;
+			if !self.is_digit(j) {
+				return (Token::Other(chars[i..j].iter().collect()), j);
+			}
+			j = self.skip_digits(j);
+		}
+
+		// an `e` without digits is the constant, e.g., `2e`
+		if matches!(chars.get(j), Some('e' | 'E')) {
+			let k = if matches!(chars.get(j + 1), Some('+' | '-')) {
+				j + 2
+			} else {
+				j + 1
+			};
+			if self.is_digit(k) {
+				j = self.skip_digits(k);
+			}
+		}
+
+		let s: String = chars[i..j].iter().filter(|c| **c != '_').collect();
+		let token = s.parse().map_or_else(
+			|_| Token::Other(chars[i..j].iter().collect()),
+			Token::Number,
+		);
+		(token, j)
+	}
+
+	/// Lex the superscript from `i`.
+	fn get_superscript(&self, i: usize) -> (Token, usize) {
+		let chars = &self.chars;
+		let start = if chars[i] == SUPERSCRIPT_MINUS {
+			i + 1
+		} else {
+			i
+		};
+
+		let mut j = start;
+		let mut n = 0.0;
+		while let Some(d) = chars.get(j).copied().and_then(superscript_digit) {
+			n = n * 10.0 + f64::from(d);
+			j += 1;
+		}
+
+		if j == start {
+			(Token::Other(SUPERSCRIPT_MINUS.to_string()), j)
+		} else if start > i {
+			(Token::Superscript(-n), j)
+		} else {
+			(Token::Superscript(n), j)
+		}
+	}
+
 	/// Helper function for `Iterator::next`.
 	#[inline]
 	fn get_token(&self, i: usize) -> Option<(Token, usize)> {
@@ -136,57 +345,62 @@ impl Lexer {
 			return Some((Token::WhiteSpace(chars[i]), i + 1));
 		}
 
-		// check number & factorial
-		if chars[i].is_ascii_digit() {
-			let mut j = i + 1;
-
-			while chars.get(j).is_some_and(char::is_ascii_digit) {
-				j += 1;
-			}
-
-			if chars.get(j).is_some_and(|c| *c == '!') {
-				let s: String = chars[i..j].iter().collect();
-
-				return Some((Token::Factorial(s.parse().unwrap()), j + 1));
-			}
-
-			if chars.get(j).is_some_and(|c| *c == '.') {
-				j += 1;
-
-				if !chars.get(j).is_some_and(char::is_ascii_digit) {
-					return Some((Token::Other(chars[i..j].iter().collect()), j));
-				}
-
-				while chars.get(j).is_some_and(char::is_ascii_digit) {
-					j += 1;
-				}
-			}
+		// check number
+		if chars[i].is_ascii_digit() || (chars[i] == '.' && self.is_digit(i + 1)) {
+			return Some(self.get_number(i));
+		}
 
-			let s: String = chars[i..j].iter().collect();
-			let n: f32 = s.parse().unwrap();
+		// check Unicode symbols, e.g., `×`
+		if let Some((_, s)) = SYMBOLS.iter().find(|(c, _)| *c == chars[i]) {
+			let token = if Operator::from_symbol(s).is_some() {
+				Token::Operator((*s).to_string())
+			} else {
+				Token::Identifier((*s).to_string())
+			};
+			return Some((token, i + 1));
+		}
+		if chars[i] == RADICAL {
+			return Some((Token::Radical, i + 1));
+		}
 
-			return Some((Token::Number(n), j));
+		// check superscript
+		if chars[i] == SUPERSCRIPT_MINUS || superscript_digit(chars[i]).is_some() {
+			return Some(self.get_superscript(i));
 		}
 
 		// check identifier
 		if chars[i].is_ascii_alphabetic() {
 			let mut j = i + 1;
-			while chars.get(j).is_some_and(char::is_ascii_alphabetic) {
+			while chars
+				.get(j)
+				.is_some_and(|c| c.is_ascii_alphanumeric() || *c == '_')
+			{
 				j += 1;
 			}
 
-			return Some((Token::Identifier(chars[i..j].iter().collect()), j));
+			let s: String = chars[i..j].iter().collect();
+			// word operators, e.g., `and`
+			if s == NOT || Operator::from_symbol(&s).is_some() {
+				return Some((Token::Operator(s), j));
+			}
+
+			return Some((Token::Identifier(s), j));
 		}
 
 		// check operator
-		for op in OPERATORS {
-			// max length of operators is 2
+		for (op, _) in OPERATORS {
+			// max length of symbols is 2
 			let s: String = chars[i..(chars.len().min(i + 2))].iter().collect();
 			if s.starts_with(op) {
 				return Some((Token::Operator(op.to_string()), i + op.len()));
 			}
 		}
 
+		// check factorial, after the operators since `!=` is one
+		if chars[i] == '!' {
+			return Some((Token::Factorial, i + 1));
+		}
+
 		// check parenthesis
 		if chars[i] == '(' {
 			return Some((Token::OpenParenthesis, i + 1));
@@ -199,6 +413,15 @@ impl Lexer {
 	}
 }
 
+/// Value of a superscript digit.
+fn superscript_digit(c: char) -> Option<u8> {
+	SUPERSCRIPT_DIGITS
+		.iter()
+		.zip(0..)
+		.find(|(s, _)| **s == c)
+		.map(|(_, d)| d)
+}
+
 impl std::fmt::Display for Lexer {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		write!(
@@ -211,12 +434,13 @@ impl std::fmt::Display for Lexer {
 }
 
 impl Iterator for Lexer {
-	type Item = Token;
+	type Item = (Token, Span);
 
 	fn next(&mut self) -> Option<Self::Item> {
 		if let Some((token, i)) = self.get_token(self.i) {
+			let span = Span::new(self.offsets[self.i], self.offsets[i]);
 			self.i = i;
-			Some(token)
+			Some((token, span))
 		} else {
 			None
 		}
@@ -229,7 +453,9 @@ mod tests {
 
 	#[test]
 	fn test_tokenize() {
-		let tokens: Vec<_> = Lexer::new("-x + (1 - 2) * 3 / 4").collect();
+		let tokens: Vec<_> = Lexer::new("-x + (1 - 2) * 3 / 4")
+			.map(|(token, _)| token)
+			.collect();
 		assert_eq!(
 			tokens,
 			[
@@ -257,6 +483,105 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn test_tokenize_identifier() {
+		let tokens: Vec<_> = Lexer::new("k1 * t_0").map(|(token, _)| token).collect();
+		assert_eq!(
+			tokens,
+			[
+				Token::Identifier("k1".to_string()),
+				Token::WhiteSpace(' '),
+				Token::Operator("*".to_string()),
+				Token::WhiteSpace(' '),
+				Token::Identifier("t_0".to_string()),
+			]
+		);
+
+		let tokens: Vec<_> = Lexer::new("log10(x)").map(|(token, _)| token).collect();
+		assert_eq!(tokens[0], Token::Identifier("log10".to_string()));
+	}
+
+	#[test]
+	fn test_tokenize_number() {
+		let tokens: Vec<_> = Lexer::new("1 .5 1e-3 6.02E+23 0x1F 1_000 2e 1.")
+			.map(|(token, _)| token)
+			.filter(|token| !token.is_whitespace())
+			.collect();
+		assert_eq!(
+			tokens,
+			[
+				Token::Number(1.0),
+				Token::Number(0.5),
+				Token::Number(1e-3),
+				Token::Number(6.02e23),
+				Token::Number(31.0),
+				Token::Number(1000.0),
+				Token::Number(2.0),
+				Token::Identifier("e".to_string()),
+				Token::Other("1.".to_string()),
+			]
+		);
+	}
+
+	#[test]
+	fn test_tokenize_unicode() {
+		let tokens: Vec<_> = Lexer::new("2π × √x ÷ x⁻¹² − y²")
+			.map(|(token, _)| token)
+			.filter(|token| !token.is_whitespace())
+			.collect();
+		assert_eq!(
+			tokens,
+			[
+				Token::Number(2.0),
+				Token::Identifier("pi".to_string()),
+				Token::Operator("*".to_string()),
+				Token::Radical,
+				Token::Identifier("x".to_string()),
+				Token::Operator("/".to_string()),
+				Token::Identifier("x".to_string()),
+				Token::Superscript(-12.0),
+				Token::Operator("-".to_string()),
+				Token::Identifier("y".to_string()),
+				Token::Superscript(2.0),
+			]
+		);
+		assert_eq!(Token::Superscript(-12.0).to_string(), "⁻¹²");
+		assert_eq!(
+			Lexer::new("⁻").next().unwrap().0,
+			Token::Other("⁻".to_string())
+		);
+	}
+
+	#[test]
+	fn test_tokenize_factorial() {
+		let tokens: Vec<_> = Lexer::new("x!!=3!").map(|(token, _)| token).collect();
+		assert_eq!(
+			tokens,
+			[
+				Token::Identifier("x".to_string()),
+				Token::Factorial,
+				Token::Operator("!=".to_string()),
+				Token::Number(3.0),
+				Token::Factorial,
+			]
+		);
+	}
+
+	#[test]
+	fn test_span() {
+		let spans: Vec<_> = Lexer::new("π * 10").map(|(_, span)| span).collect();
+		assert_eq!(
+			spans,
+			[
+				Span::new(0, 2),
+				Span::new(2, 3),
+				Span::new(3, 4),
+				Span::new(4, 5),
+				Span::new(5, 7),
+			]
+		);
+	}
+
 	#[test]
 	fn test_display() {
 		let mut lexer = Lexer::new("-1.1 + x");
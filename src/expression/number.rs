@@ -1,4 +1,8 @@
-use super::{Expression, Function};
+use super::{
+	ComplexFunction, Derivative, Env, EvalError, Expression, Function, IntervalFunction, Scalar,
+	Simplify, Typeset,
+};
+use crate::math::{Complex, Interval};
 
 /// Number expression.
 ///
@@ -7,12 +11,18 @@ use super::{Expression, Function};
 /// digit ::= "0"..."9"
 /// ```
 #[derive(Debug, Clone)]
-pub struct Number(f32);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Number(f64);
 
 impl Number {
 	#[must_use]
-	pub fn new(n: f32) -> Self {
-		Self(n)
+	pub fn new<T: Into<f64>>(n: T) -> Self {
+		Self(n.into())
+	}
+
+	#[must_use]
+	pub fn value(&self) -> f64 {
+		self.0
 	}
 }
 
@@ -23,6 +33,35 @@ macro_rules! num {
 	};
 }
 
+/// Numbers are equal if their bit patterns are, so `Eq` and `Hash` hold for NaN and `-0` differs
+/// from `0`.
+impl PartialEq for Number {
+	fn eq(&self, other: &Self) -> bool {
+		self.0.to_bits() == other.0.to_bits()
+	}
+}
+
+impl Eq for Number {}
+
+impl std::hash::Hash for Number {
+	fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+		self.0.to_bits().hash(state);
+	}
+}
+
+impl PartialOrd for Number {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+/// Total order of IEEE 754, which agrees with the equality of bit patterns.
+impl Ord for Number {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		self.0.total_cmp(&other.0)
+	}
+}
+
 impl std::fmt::Display for Number {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		write!(f, "{}", self.0)
@@ -30,8 +69,46 @@ impl std::fmt::Display for Number {
 }
 
 impl Function for Number {
-	fn eval(&self, _x: f32) -> Option<f32> {
-		Some(self.0)
+	fn eval_with<T: Scalar>(&self, _env: &Env<T>) -> Result<T, EvalError> {
+		Ok(T::from_f64(self.0))
+	}
+}
+
+impl ComplexFunction for Number {
+	fn eval_complex_with(&self, _env: &Env<Complex>) -> Result<Complex, EvalError> {
+		Ok(Complex::from(self.0))
+	}
+}
+
+impl IntervalFunction for Number {
+	fn eval_interval_with(&self, _env: &Env<Interval>) -> Result<Interval, EvalError> {
+		Ok(Interval::point(self.0))
+	}
+}
+
+impl Derivative for Number {
+	fn derivative(&self) -> Expression {
+		Self::new(0.0).into()
+	}
+}
+
+impl Simplify for Number {
+	fn simplify(&self) -> Expression {
+		self.clone().into()
+	}
+}
+
+impl Typeset for Number {
+	fn to_latex(&self) -> String {
+		self.to_string()
+	}
+
+	fn to_mathml(&self) -> String {
+		if self.0.is_sign_negative() {
+			format!("<mrow><mo>-</mo><mn>{}</mn></mrow>", -self.0)
+		} else {
+			format!("<mn>{}</mn>", self.0)
+		}
 	}
 }
 
@@ -51,4 +128,11 @@ mod tests {
 		assert_eq!(f.eval(0.0).unwrap(), 5.0);
 		assert_eq!(f.eval(1.0).unwrap(), 5.0);
 	}
+
+	#[test]
+	fn test_num_eq() {
+		assert_eq!(num!(f64::NAN), num!(f64::NAN));
+		assert_ne!(num!(0.0), num!(-0.0));
+		assert!(num!(-0.0) < num!(0.0) && num!(1.0) < num!(f64::INFINITY));
+	}
 }
@@ -0,0 +1,153 @@
+//! Binary operator table shared by the lexer, parser and printer.
+
+/// Operator associativity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+	Left,
+	Right,
+}
+
+/// Binary operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+	Add,
+	Sub,
+	Mul,
+	Div,
+	Pow,
+	Lt,
+	Le,
+	Eq,
+	Ne,
+	Ge,
+	Gt,
+	And,
+	Or,
+}
+
+/// Operator symbols, where `^` is an alias of `**`.
+///
+/// NOTE: longer first
+pub const OPERATORS: [(&str, Operator); 14] = [
+	("**", Operator::Pow),
+	("<=", Operator::Le),
+	("==", Operator::Eq),
+	("!=", Operator::Ne),
+	(">=", Operator::Ge),
+	("^", Operator::Pow),
+	("+", Operator::Add),
+	("-", Operator::Sub),
+	("*", Operator::Mul),
+	("/", Operator::Div),
+	("<", Operator::Lt),
+	(">", Operator::Gt),
+	("and", Operator::And),
+	("or", Operator::Or),
+];
+
+/// Logical negation, which is a word like `and` and `or`.
+pub const NOT: &str = "not";
+
+/// Precedence of unary minus, which is lower than `**`, i.e., `-x ** 2` means `-(x ** 2)`.
+pub const NEG_PRECEDENCE: u8 = 25;
+
+/// Precedence of `not`, which is between `and` and the comparisons, i.e., `not x < 1 and y` means
+/// `(not (x < 1)) and y`.
+pub const NOT_PRECEDENCE: u8 = 5;
+
+/// Precedence of expressions that never need parenthesis, e.g., numbers and function calls.
+pub const ATOM_PRECEDENCE: u8 = u8::MAX;
+
+impl Operator {
+	#[must_use]
+	pub fn from_symbol(symbol: &str) -> Option<Self> {
+		OPERATORS
+			.iter()
+			.find(|(s, _)| *s == symbol)
+			.map(|(_, op)| *op)
+	}
+
+	/// Canonical symbol.
+	#[must_use]
+	pub fn symbol(self) -> &'static str {
+		match self {
+			Operator::Add => "+",
+			Operator::Sub => "-",
+			Operator::Mul => "*",
+			Operator::Div => "/",
+			Operator::Pow => "**",
+			Operator::Lt => "<",
+			Operator::Le => "<=",
+			Operator::Eq => "==",
+			Operator::Ne => "!=",
+			Operator::Ge => ">=",
+			Operator::Gt => ">",
+			Operator::And => "and",
+			Operator::Or => "or",
+		}
+	}
+
+	#[must_use]
+	pub fn precedence(self) -> u8 {
+		match self {
+			Operator::Or => 2,
+			Operator::And => 4,
+			Operator::Lt
+			| Operator::Le
+			| Operator::Eq
+			| Operator::Ne
+			| Operator::Ge
+			| Operator::Gt => 6,
+			Operator::Add | Operator::Sub => 10,
+			Operator::Mul | Operator::Div => 20,
+			Operator::Pow => 30,
+		}
+	}
+
+	/// Is the result a truth value, i.e., 0 or 1.
+	#[must_use]
+	pub fn is_logical(self) -> bool {
+		self.precedence() < Operator::Add.precedence()
+	}
+
+	#[must_use]
+	pub fn associativity(self) -> Associativity {
+		match self {
+			Operator::Pow => Associativity::Right,
+			_ => Associativity::Left,
+		}
+	}
+}
+
+impl std::fmt::Display for Operator {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.symbol())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_operator() {
+		assert_eq!(Operator::from_symbol("^"), Some(Operator::Pow));
+		assert_eq!(Operator::from_symbol("^").unwrap().symbol(), "**");
+		assert_eq!(Operator::from_symbol("%"), None);
+
+		for (symbol, op) in OPERATORS {
+			assert_eq!(Operator::from_symbol(symbol), Some(op));
+		}
+
+		assert!(Operator::Mul.precedence() < NEG_PRECEDENCE);
+		assert!(NEG_PRECEDENCE < Operator::Pow.precedence());
+		assert_eq!(Operator::Pow.associativity(), Associativity::Right);
+		assert_eq!(Operator::Sub.associativity(), Associativity::Left);
+
+		assert!(Operator::Or.precedence() < Operator::And.precedence());
+		assert!(Operator::And.precedence() < NOT_PRECEDENCE);
+		assert!(NOT_PRECEDENCE < Operator::Lt.precedence());
+		assert!(Operator::Lt.precedence() < Operator::Add.precedence());
+		assert!(Operator::Ne.is_logical() && !Operator::Pow.is_logical());
+	}
+}
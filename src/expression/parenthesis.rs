@@ -1,17 +1,28 @@
-use super::{Expression, Function};
+use super::{
+	fold, ComplexFunction, Derivative, Env, EvalError, Expression, Function, IntervalFunction,
+	Scalar, Simplify, Typeset,
+};
+use crate::math::{Complex, Interval};
 
 /// Parenthesis expression.
 ///
 /// ```bnf
 /// p_expr ::= "(" expression ")"
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Parenthesis(Expression);
 
 impl Parenthesis {
 	pub fn new<T: Into<Expression>>(expr: T) -> Self {
 		Self(expr.into())
 	}
+
+	/// The enclosed expression.
+	#[must_use]
+	pub fn inner(&self) -> &Expression {
+		&self.0
+	}
 }
 
 impl std::fmt::Display for Parenthesis {
@@ -21,12 +32,48 @@ impl std::fmt::Display for Parenthesis {
 }
 
 impl Function for Parenthesis {
-	fn is_x_valid(&self, x: f32) -> bool {
-		self.0.is_x_valid(x)
+	fn eval_with<T: Scalar>(&self, env: &Env<T>) -> Result<T, EvalError> {
+		self.0.eval_with(env)
+	}
+}
+
+impl ComplexFunction for Parenthesis {
+	fn eval_complex_with(&self, env: &Env<Complex>) -> Result<Complex, EvalError> {
+		self.0.eval_complex_with(env)
+	}
+}
+
+impl IntervalFunction for Parenthesis {
+	fn eval_interval_with(&self, env: &Env<Interval>) -> Result<Interval, EvalError> {
+		self.0.eval_interval_with(env)
+	}
+}
+
+impl Derivative for Parenthesis {
+	fn is_constant(&self) -> bool {
+		self.0.is_constant()
+	}
+
+	fn derivative(&self) -> Expression {
+		self.0.derivative()
+	}
+}
+
+impl Simplify for Parenthesis {
+	fn simplify(&self) -> Expression {
+		// parenthesis are inserted by the printer where needed
+		fold(self.0.simplify())
+	}
+}
+
+impl Typeset for Parenthesis {
+	// brackets are inserted by the enclosing expression where needed
+	fn to_latex(&self) -> String {
+		self.0.to_latex()
 	}
 
-	fn eval(&self, x: f32) -> Option<f32> {
-		self.0.eval(x)
+	fn to_mathml(&self) -> String {
+		self.0.to_mathml()
 	}
 }
 
@@ -46,7 +93,7 @@ macro_rules! paren {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::{add, mul, num};
+	use crate::{add, mul, num, var};
 
 	#[test]
 	fn test_paren() {
@@ -56,4 +103,19 @@ mod tests {
 		assert_eq!(f.eval(0.0).unwrap(), 9.0);
 		assert_eq!(f.eval(1.0).unwrap(), 9.0);
 	}
+
+	#[test]
+	fn test_paren_simplify() {
+		// ((x))
+		let f = paren!(paren!(var!())).simplify();
+		assert_eq!(f.to_string(), "x");
+
+		// (x + 0) * 2
+		let f = mul!(paren!(add!(var!(), num!(0.0))), num!(2.0)).simplify();
+		assert_eq!(f.to_string(), "x * 2");
+
+		// (x + 1) * 2
+		let f = mul!(paren!(add!(var!(), num!(1.0))), num!(2.0)).simplify();
+		assert_eq!(f.to_string(), "(x + 1) * 2");
+	}
 }
@@ -1,36 +1,69 @@
-use crate::{binary_expr, constant, factorial, func, func2, neg, num, paren, var};
+use crate::{binary_expr, constant, factorial, func, func2, neg, not, num, paren, pow, sqrt, var};
 
 use super::{
 	func, func2,
-	lexer::{Lexer, Token},
-	Expression,
+	lexer::{Lexer, Span, Token},
+	Associativity, Call, Conditional, Custom, Definition, Expression, FunctionRegistry, Integral,
+	Operator, ParseError, ParseErrorKind, Program, IF, INTEGRAL, NEG_PRECEDENCE, NOT,
+	NOT_PRECEDENCE,
 };
+use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub struct Parser {
 	current: Token,
+	/// span of the current token
+	span: Span,
 	lexer: Lexer,
+	/// bound variable names
+	variables: Vec<String>,
+	/// user functions defined so far
+	definitions: Vec<Arc<Definition>>,
+	/// name of the function whose body is being parsed
+	defining: Option<String>,
+	/// functions registered in addition to the built-in ones
+	registry: FunctionRegistry,
 }
 
 impl Parser {
+	/// Create a parser where only x is bound.
 	pub fn new<T: ToString>(expr: T) -> Self {
+		Self::with_variables(expr, &["x"])
+	}
+
+	/// Create a parser with the bound variable names.
+	pub fn with_variables<T: ToString, S: AsRef<str>>(expr: T, variables: &[S]) -> Self {
 		let mut out = Self {
 			current: Token::Eof,
+			span: Span::default(),
 			lexer: Lexer::new(expr),
+			variables: variables.iter().map(|v| v.as_ref().to_string()).collect(),
+			definitions: Vec::new(),
+			defining: None,
+			registry: FunctionRegistry::new(),
 		};
 		out.get_next();
 
 		out
 	}
 
+	/// Recognize the functions of the registry and return the parser.
+	#[must_use]
+	pub fn with_registry(mut self, registry: &FunctionRegistry) -> Self {
+		self.registry = registry.clone();
+		self
+	}
+
 	/// Update current token to the next non-whitespace token by lexer.
 	///
 	/// Set current token to `Eof` if no more token.
 	fn get_next(&mut self) {
-		if let Some(token) = self.lexer.next() {
+		if let Some((token, span)) = self.lexer.next() {
 			self.current = token;
+			self.span = span;
 		} else {
 			self.current = Token::Eof;
+			self.span = self.lexer.eof_span();
 		}
 
 		if self.current.is_whitespace() {
@@ -38,18 +71,41 @@ impl Parser {
 		}
 	}
 
-	/// Parse variable expression, i.e., x.
+	/// Return the next non-whitespace token without consuming it.
+	fn peek(&self) -> Token {
+		self.lexer
+			.clone()
+			.map(|(token, _)| token)
+			.find(|token| !token.is_whitespace())
+			.unwrap_or(Token::Eof)
+	}
+
+	/// Create an error located at the current token.
+	fn error(&self, kind: ParseErrorKind) -> ParseError {
+		ParseError::new(kind, self.span)
+	}
+
+	/// Create an error for the current token, which is not the `expected` one.
+	fn expected(&self, expected: &str) -> ParseError {
+		self.error(ParseErrorKind::Expected {
+			expected: expected.to_string(),
+			found: self.current.clone(),
+		})
+	}
+
+	/// Parse variable expression, e.g., x, t, k1.
 	///
 	/// ```bnf
-	/// variable ::= "x"
+	/// variable ::= id
 	/// ```
 	fn parse_variable(&mut self) -> Expression {
-		// we know this is a "x"
-		let x = var!().into();
+		let Token::Identifier(name) = self.current.clone() else {
+			unreachable!()
+		};
 
 		self.get_next();
 
-		x
+		var!(name).into()
 	}
 
 	/// Parse constant expression.
@@ -67,19 +123,27 @@ impl Parser {
 		constant!(c).unwrap().into()
 	}
 
-	/// Parse unary expression.
+	/// Parse unary expression, where `-` binds looser than `**`, i.e., `-x ** 2` is `-(x ** 2)`,
+	/// and `not` binds looser than the comparisons, i.e., `not x < 1` is `not (x < 1)`.
 	///
 	/// ```bnf
-	/// u_expr ::= u_op expression
-	/// u_op ::= "-" | "+"
+	/// u_expr ::= "-" primary ("**" primary)* | "not" primary b_subexpr
 	/// ```
-	fn parse_unary(&mut self) -> anyhow::Result<Expression> {
+	fn parse_unary(&mut self) -> Result<Expression, ParseError> {
 		if let Token::Operator(op) = &self.current {
 			if op == "-" {
 				self.get_next();
-				return Ok(neg!(self.parse_primary()?).into());
+				let expr = self.parse_primary()?;
+				let expr = self.parse_op_rhs(expr, NEG_PRECEDENCE + 1)?;
+				return Ok(neg!(expr).into());
 			}
-			anyhow::bail!("only - allowed");
+			if op == NOT {
+				self.get_next();
+				let expr = self.parse_primary()?;
+				let expr = self.parse_op_rhs(expr, NOT_PRECEDENCE + 1)?;
+				return Ok(not!(expr).into());
+			}
+			return Err(self.error(ParseErrorKind::InvalidUnaryOperator(op.clone())));
 		}
 		unreachable!()
 	}
@@ -89,7 +153,7 @@ impl Parser {
 	/// ```bnf
 	/// p_expr ::= "(" sub_expr ")"
 	/// ```
-	fn parse_parenthesis(&mut self) -> anyhow::Result<Expression> {
+	fn parse_parenthesis(&mut self) -> Result<Expression, ParseError> {
 		assert!(self.current.is_open_parenthesis());
 
 		// eat "("
@@ -98,7 +162,7 @@ impl Parser {
 		let expr = self.parse_sub()?;
 
 		if !self.current.is_close_parenthesis() {
-			anyhow::bail!("expected `)`, but found: {:?}", self.current);
+			return Err(self.expected("`)`"));
 		}
 
 		self.get_next();
@@ -113,16 +177,18 @@ impl Parser {
 	/// func ::= func_name "(" sub_expr ")"
 	/// func2 ::= func2_name "(" sub_expr "," sub_expr ")"
 	/// ```
-	fn parse_function(&mut self) -> anyhow::Result<Expression> {
+	fn parse_function(&mut self) -> Result<Expression, ParseError> {
 		let Token::Identifier(f_name) = self.current.clone() else {
-			anyhow::bail!("expected a function name, but found: {:?}", self.current);
+			return Err(self.expected("a function name"));
 		};
 
+		let name_span = self.span;
+
 		// eat f_name
 		self.get_next();
 
 		if !self.current.is_open_parenthesis() {
-			anyhow::bail!("expected `(`, but found: {:?}", self.current);
+			return Err(self.expected("`(`"));
 		}
 
 		// eat "("
@@ -133,7 +199,7 @@ impl Parser {
 			let expr = self.parse_sub()?;
 
 			if !self.current.is_close_parenthesis() {
-				anyhow::bail!("expected `)`, but found: {:?}", self.current);
+				return Err(self.expected("`)`"));
 			}
 
 			// eat ")"
@@ -144,7 +210,7 @@ impl Parser {
 			let lhs = self.parse_sub()?;
 
 			if !self.current.is_comma() {
-				anyhow::bail!("expected `,`, but found: {:?}", self.current);
+				return Err(self.expected("`,`"));
 			}
 
 			// eat ","
@@ -153,7 +219,7 @@ impl Parser {
 			let rhs = self.parse_sub()?;
 
 			if !self.current.is_close_parenthesis() {
-				anyhow::bail!("expected `)`, but found: {:?}", self.current);
+				return Err(self.expected("`)`"));
 			}
 
 			// eat ")"
@@ -161,36 +227,259 @@ impl Parser {
 
 			Ok(func2!(f_name, lhs, rhs).unwrap().into())
 		} else {
-			anyhow::bail!(
-				"expected a valid function name, but found: {:?}",
-				self.current
-			);
+			Err(ParseError::new(
+				ParseErrorKind::UnknownFunction(f_name),
+				name_span,
+			))
+		}
+	}
+
+	/// Parse the arguments of a call, returning the function name, its span, and the arguments.
+	///
+	/// ```bnf
+	/// args ::= "(" [sub_expr ("," sub_expr)*] ")"
+	/// ```
+	fn parse_arguments(&mut self) -> Result<(String, Span, Vec<Expression>), ParseError> {
+		let Token::Identifier(name) = self.current.clone() else {
+			unreachable!()
+		};
+
+		let name_span = self.span;
+
+		// eat name
+		self.get_next();
+
+		if !self.current.is_open_parenthesis() {
+			return Err(self.expected("`(`"));
+		}
+
+		// eat "("
+		self.get_next();
+
+		let mut args = Vec::new();
+		if !self.current.is_close_parenthesis() {
+			loop {
+				args.push(self.parse_sub()?);
+
+				if !self.current.is_comma() {
+					break;
+				}
+
+				// eat ","
+				self.get_next();
+			}
+		}
+
+		if !self.current.is_close_parenthesis() {
+			return Err(self.expected("`)`"));
 		}
+
+		// eat ")"
+		self.get_next();
+
+		Ok((name, name_span, args))
+	}
+
+	/// Parse call of a user function.
+	///
+	/// ```bnf
+	/// call ::= id args
+	/// ```
+	fn parse_call(&mut self) -> Result<Expression, ParseError> {
+		let (name, name_span, args) = self.parse_arguments()?;
+
+		let definition = self.definitions.iter().find(|d| d.name() == name).unwrap();
+		let (expected, found) = (definition.params().len(), args.len());
+
+		Call::new(definition, args).map(Into::into).ok_or_else(|| {
+			ParseError::new(
+				ParseErrorKind::ArgumentCount {
+					name,
+					expected: expected..=expected,
+					found,
+				},
+				name_span,
+			)
+		})
+	}
+
+	/// Parse call of a registered function.
+	///
+	/// ```bnf
+	/// custom ::= id args
+	/// ```
+	fn parse_custom(&mut self) -> Result<Expression, ParseError> {
+		let (name, name_span, args) = self.parse_arguments()?;
+
+		let function = self.registry.get(&name).unwrap();
+		let (expected, found) = (function.arity(), args.len());
+
+		Custom::new(function, args).map(Into::into).ok_or_else(|| {
+			ParseError::new(
+				ParseErrorKind::ArgumentCount {
+					name,
+					expected: expected..=expected,
+					found,
+				},
+				name_span,
+			)
+		})
+	}
+
+	/// Parse conditional expression.
+	///
+	/// ```bnf
+	/// conditional ::= "if" "(" sub_expr "," sub_expr ["," sub_expr] ")"
+	/// ```
+	fn parse_conditional(&mut self) -> Result<Expression, ParseError> {
+		let (_, name_span, mut args) = self.parse_arguments()?;
+
+		let expected = 2..=3;
+		if !expected.contains(&args.len()) {
+			return Err(ParseError::new(
+				ParseErrorKind::ArgumentCount {
+					name: IF.to_string(),
+					expected,
+					found: args.len(),
+				},
+				name_span,
+			));
+		}
+
+		let otherwise = (args.len() == 3).then(|| args.pop().unwrap());
+		let then = args.pop().unwrap();
+		let condition = args.pop().unwrap();
+
+		Ok(Conditional::new(condition, then, otherwise).into())
+	}
+
+	/// Parse definite integral, where x is bound in the integrand as the variable of integration.
+	///
+	/// ```bnf
+	/// integral ::= "integral" "(" sub_expr "," sub_expr "," sub_expr ")"
+	/// ```
+	fn parse_integral(&mut self) -> Result<Expression, ParseError> {
+		let name_span = self.span;
+
+		// eat name
+		self.get_next();
+
+		if !self.current.is_open_parenthesis() {
+			return Err(self.expected("`(`"));
+		}
+
+		// eat "("
+		self.get_next();
+
+		let variables = self.variables.clone();
+		if !self.variables.iter().any(|v| v == "x") {
+			self.variables.push("x".to_string());
+		}
+		let integrand = self.parse_sub();
+		self.variables = variables;
+		let integrand = integrand?;
+
+		let mut bounds = Vec::new();
+		while self.current.is_comma() {
+			// eat ","
+			self.get_next();
+
+			bounds.push(self.parse_sub()?);
+		}
+
+		if !self.current.is_close_parenthesis() {
+			return Err(self.expected("`)`"));
+		}
+
+		// eat ")"
+		self.get_next();
+
+		let [lower, upper]: [Expression; 2] = bounds.try_into().map_err(|bounds: Vec<_>| {
+			ParseError::new(
+				ParseErrorKind::ArgumentCount {
+					name: INTEGRAL.to_string(),
+					expected: 3..=3,
+					found: bounds.len() + 1,
+				},
+				name_span,
+			)
+		})?;
+
+		Ok(Integral::new(integrand, lower, upper).into())
 	}
 
 	/// Parse primary expression.
 	///
 	/// ```bnf
-	/// primary ::= number | factorial | variable | constant | u_expr | p_expr | f_expr
+	/// primary ::= (number | variable | constant | u_expr | p_expr | f_expr | radical | conditional
+	///            | integral | call | custom) ("!" | superscript)*
+	/// radical ::= "√" primary
 	/// ```
-	fn parse_primary(&mut self) -> anyhow::Result<Expression> {
-		match self.current.clone() {
+	fn parse_primary(&mut self) -> Result<Expression, ParseError> {
+		let mut expr = match self.current.clone() {
 			Token::Number(n) => Ok({
 				self.get_next();
 				num!(n).into()
 			}),
-			Token::Factorial(n) => Ok({
-				self.get_next();
-				factorial!(n).into()
-			}),
-			Token::Identifier(id) => match id.as_str() {
-				"x" => Ok(self.parse_variable()),
-				"e" | "pi" => Ok(self.parse_constant()),
-				_ => self.parse_function(),
-			},
+			Token::Identifier(id) => {
+				if self.variables.contains(&id) {
+					Ok(self.parse_variable())
+				} else if constant!(&id).is_some() {
+					Ok(self.parse_constant())
+				} else if id == IF {
+					self.parse_conditional()
+				} else if id == INTEGRAL {
+					self.parse_integral()
+				} else if func::FUNCTION_NAMES.contains(&id.as_str())
+					|| func2::FUNCTION_NAMES.contains(&id.as_str())
+				{
+					self.parse_function()
+				} else if self.registry.get(&id).is_some() {
+					self.parse_custom()
+				} else if self.definitions.iter().any(|d| d.name() == id) {
+					self.parse_call()
+				} else if self.defining.as_ref() == Some(&id) {
+					Err(self.error(ParseErrorKind::RecursiveDefinition(id)))
+				} else if self.peek().is_open_parenthesis() {
+					Err(self.error(ParseErrorKind::UnknownFunction(id)))
+				} else {
+					Err(self.error(ParseErrorKind::UnboundVariable(id)))
+				}
+			}
 			Token::Operator(_) => self.parse_unary(),
 			Token::OpenParenthesis => self.parse_parenthesis(),
-			_ => anyhow::bail!("not expected: {:?}", self.current),
+			Token::Radical => {
+				self.get_next();
+				// the brackets of `√(x + 1)` are those of the function
+				let arg = match self.parse_primary()? {
+					Expression::Parenthesis(e) => e.inner().clone(),
+					arg => arg,
+				};
+				Ok(sqrt!(arg).into())
+			}
+			_ => Err(self.error(ParseErrorKind::UnexpectedToken(self.current.clone()))),
+		}?;
+
+		// postfix factorials and powers, e.g., `x!²` is `(x!) ** 2`
+		loop {
+			expr = match self.current {
+				Token::Factorial => factorial!(expr).into(),
+				Token::Superscript(n) if n < 0.0 => pow!(expr, neg!(num!(-n))).into(),
+				Token::Superscript(n) => pow!(expr, num!(n)).into(),
+				_ => return Ok(expr),
+			};
+			self.get_next();
+		}
+	}
+
+	/// Current token as a binary operator.
+	///
+	/// Juxtaposition means multiplication, e.g., `2x` is `2 * x`.
+	fn current_operator(&self) -> Option<Operator> {
+		if self.current.is_implicit_operand() {
+			Some(Operator::Mul)
+		} else {
+			self.current.operator()
 		}
 	}
 
@@ -198,43 +487,51 @@ impl Parser {
 	///
 	/// ```bnf
 	/// b_subexpr ::= (bop_rhs)*
-	/// bop_rhs ::= b_op primary
-	/// b_op ::= "+" | "-" | "*" | "/"
+	/// bop_rhs ::= b_op primary | implicit_rhs
+	/// b_op ::= "+" | "-" | "*" | "/" | "**" | "^"
+	///        | "<" | "<=" | "==" | "!=" | ">=" | ">" | "and" | "or"
+	/// implicit_rhs ::= variable | constant | p_expr | f_expr | radical
 	/// ```
 	fn parse_op_rhs(
 		&mut self,
 		mut lhs: Expression,
 		lhs_precedence: u8,
-	) -> anyhow::Result<Expression> {
+	) -> Result<Expression, ParseError> {
 		loop {
-			match &self.current {
-				Token::Operator(op) => {
-					let op = op.clone();
-					let token_precedence = self.current.precedence().unwrap();
-
-					// Current token has lower precedence means that we are done for the sub-expression.
-					if token_precedence < lhs_precedence {
-						return Ok(lhs);
-					}
-
-					self.get_next();
-
-					// same or higher precedence means that we should consider the next op
-					let mut rhs = self.parse_primary()?;
-
-					// cannot merge, so consume all sub-expressions with higher precedence first
-					if self
-						.current
-						.precedence()
-						.is_some_and(|p| token_precedence < p)
-					{
-						rhs = self.parse_op_rhs(rhs, token_precedence + 1)?;
-					}
-
-					lhs = binary_expr!(&op, lhs, rhs).unwrap().into();
-				}
-				_ => return Ok(lhs),
+			let Some(op) = self.current_operator() else {
+				return Ok(lhs);
+			};
+			let token_precedence = op.precedence();
+
+			// Current token has lower precedence means that we are done for the sub-expression.
+			if token_precedence < lhs_precedence {
+				return Ok(lhs);
 			}
+
+			// eat the operator, which is absent for implicit multiplication
+			if self.current.is_operator() {
+				self.get_next();
+			}
+
+			// same or higher precedence means that we should consider the next op
+			let mut rhs = self.parse_primary()?;
+
+			// cannot merge, so consume all sub-expressions with higher precedence first, or with the
+			// same precedence if right-associative, e.g., `2 ** 3 ** 2` is `2 ** (3 ** 2)`
+			let right = op.associativity() == Associativity::Right;
+			if self.current_operator().is_some_and(|next| {
+				token_precedence < next.precedence()
+					|| (right && token_precedence == next.precedence())
+			}) {
+				let precedence = if right {
+					token_precedence
+				} else {
+					token_precedence + 1
+				};
+				rhs = self.parse_op_rhs(rhs, precedence)?;
+			}
+
+			lhs = binary_expr!(op.symbol(), lhs, rhs).unwrap().into();
 		}
 	}
 
@@ -243,7 +540,7 @@ impl Parser {
 	/// ```bnf
 	/// sub_expr ::= primary b_subexpr
 	/// ```
-	pub fn parse_sub(&mut self) -> anyhow::Result<Expression> {
+	pub fn parse_sub(&mut self) -> Result<Expression, ParseError> {
 		let lhs = self.parse_primary()?;
 
 		self.parse_op_rhs(lhs, 0)
@@ -254,25 +551,173 @@ impl Parser {
 	/// ```bnf
 	/// expression ::= sub_expr eof
 	/// ```
-	pub fn parse(&mut self) -> anyhow::Result<Expression> {
-		let result = self.parse_sub();
+	pub fn parse(&mut self) -> Result<Expression, ParseError> {
+		let result = self.parse_sub()?;
 
 		if !self.current.is_eof() {
-			anyhow::bail!("expected EOF, but found: {:?}", self.current);
+			return Err(self.expected("end of input"));
 		}
 
-		result
+		Ok(result)
+	}
+
+	/// Is the name used by a variable, a constant, or a function.
+	fn is_defined(&self, name: &str) -> bool {
+		self.variables.iter().any(|v| v == name)
+			|| constant!(name).is_some()
+			|| name == IF
+			|| name == INTEGRAL
+			|| self.registry.contains(name)
+			|| self.definitions.iter().any(|d| d.name() == name)
+	}
+
+	/// Does a function definition start at the current token, i.e., is there a `=` before the next
+	/// `;`.
+	fn is_definition(&self) -> bool {
+		matches!(self.current, Token::Identifier(_))
+			&& self
+				.lexer
+				.clone()
+				.map(|(token, _)| token)
+				.find(|token| token.is_equal_sign() || token.is_semicolon())
+				.is_some_and(|token| token.is_equal_sign())
+	}
+
+	/// Parse function definition, whose body may only use the parameters.
+	///
+	/// ```bnf
+	/// definition ::= id "(" [id ("," id)*] ")" "=" sub_expr
+	/// ```
+	fn parse_definition(&mut self) -> Result<Definition, ParseError> {
+		let Token::Identifier(name) = self.current.clone() else {
+			return Err(self.expected("a function name"));
+		};
+
+		if self.is_defined(&name) {
+			return Err(self.error(ParseErrorKind::Redefinition(name)));
+		}
+
+		// eat name
+		self.get_next();
+
+		if !self.current.is_open_parenthesis() {
+			return Err(self.expected("`(`"));
+		}
+
+		// eat "("
+		self.get_next();
+
+		let mut params: Vec<String> = Vec::new();
+		if !self.current.is_close_parenthesis() {
+			loop {
+				let Token::Identifier(param) = self.current.clone() else {
+					return Err(self.expected("a parameter name"));
+				};
+
+				if params.contains(&param) {
+					return Err(self.error(ParseErrorKind::Redefinition(param)));
+				}
+				params.push(param);
+
+				// eat param
+				self.get_next();
+
+				if !self.current.is_comma() {
+					break;
+				}
+
+				// eat ","
+				self.get_next();
+			}
+		}
+
+		if !self.current.is_close_parenthesis() {
+			return Err(self.expected("`)`"));
+		}
+
+		// eat ")"
+		self.get_next();
+
+		if !self.current.is_equal_sign() {
+			return Err(self.expected("`=`"));
+		}
+
+		// eat "="
+		self.get_next();
+
+		// only the parameters are bound in the body
+		let variables = std::mem::replace(&mut self.variables, params.clone());
+		self.defining = Some(name.clone());
+		let body = self.parse_sub();
+		self.variables = variables;
+		self.defining = None;
+
+		Ok(Definition::new(name, &params, body?))
+	}
+
+	/// Parse program of function definitions and an expression.
+	///
+	/// ```bnf
+	/// program ::= (definition ";")* sub_expr eof
+	/// ```
+	///
+	/// # Errors
+	///
+	/// Return an error if the program is invalid, e.g., a function calls itself.
+	pub fn parse_program(&mut self) -> Result<Program, ParseError> {
+		while self.is_definition() {
+			let definition = self.parse_definition()?;
+
+			if !self.current.is_semicolon() {
+				return Err(self.expected("`;`"));
+			}
+
+			// eat ";"
+			self.get_next();
+
+			self.definitions.push(Arc::new(definition));
+		}
+
+		let expr = self.parse()?;
+
+		Ok(Program::new(self.definitions.clone(), expr))
 	}
 }
 
 #[inline]
-pub fn parse<T: ToString>(expr: T) -> anyhow::Result<Expression> {
+pub fn parse<T: ToString>(expr: T) -> Result<Expression, ParseError> {
 	Parser::new(expr).parse()
 }
 
+/// Parse the expression with the bound variable names.
+///
+/// # Errors
+///
+/// Return an error if the expression is invalid or uses an unbound variable.
+#[inline]
+pub fn parse_with<T: ToString, S: AsRef<str>>(
+	expr: T,
+	variables: &[S],
+) -> Result<Expression, ParseError> {
+	Parser::with_variables(expr, variables).parse()
+}
+
+/// Parse the program of function definitions and an expression, where only x is bound.
+///
+/// # Errors
+///
+/// Return an error if the program is invalid, e.g., a function calls itself.
+#[inline]
+pub fn parse_program<T: ToString>(program: T) -> Result<Program, ParseError> {
+	Parser::new(program).parse_program()
+}
+
 #[cfg(test)]
 mod tests {
-	use super::{super::Function, *};
+	use super::{
+		super::{Env, Function, Simplify},
+		*,
+	};
 
 	#[test]
 	fn test_parse() {
@@ -323,6 +768,13 @@ mod tests {
 		assert_eq!(parse(expr).unwrap().to_string(), expr);
 
 		assert!(parse("1.").is_err());
+
+		let f = parse(".5 + 1e-3 + 2.5E2x + 0x1F + 1_000").unwrap();
+		assert_eq!(f.eval(2.0f64).unwrap(), 0.5 + 1e-3 + 500.0 + 31.0 + 1000.0);
+
+		// e without exponent digits is the constant
+		let f = parse("2e + 3e^x").unwrap();
+		assert_eq!(f.to_string(), "2 * e + 3 * e ** x");
 	}
 
 	#[test]
@@ -331,6 +783,16 @@ mod tests {
 		assert_eq!(parse(expr).unwrap().to_string(), expr);
 
 		assert!(parse("y").is_err());
+
+		let f = parse_with("a * x + k1", &["x", "a", "k1"]).unwrap();
+		assert_eq!(f.to_string(), "a * x + k1");
+		let env = Env::from_x(2.0).with("a", 3.0).with("k1", 1.0);
+		assert_eq!(f.eval_with(&env).unwrap(), 7.0);
+		assert!(f.eval(2.0).is_err());
+
+		let err = parse_with("t + y", &["t"]).unwrap_err();
+		assert_eq!(err.kind, ParseErrorKind::UnboundVariable("y".to_string()));
+		assert_eq!(err.span, Span::new(4, 5));
 	}
 
 	#[test]
@@ -413,7 +875,7 @@ mod tests {
 		let expr = "cot(x)";
 		let f = parse(expr).unwrap();
 		assert_eq!(f.to_string(), expr);
-		assert!(f.eval(0.0).is_none());
+		assert!(f.eval(0.0).is_err());
 
 		let expr = "sec(x)";
 		let f = parse(expr).unwrap();
@@ -423,7 +885,7 @@ mod tests {
 		let expr = "csc(x)";
 		let f = parse(expr).unwrap();
 		assert_eq!(f.to_string(), expr);
-		assert!(f.eval(0.0).is_none());
+		assert!(f.eval(0.0).is_err());
 
 		let expr = "arcsin(x)";
 		let f = parse(expr).unwrap();
@@ -448,7 +910,7 @@ mod tests {
 		let expr = "ln(x)";
 		let f = parse(expr).unwrap();
 		assert_eq!(f.to_string(), expr);
-		assert!(f.eval(0.0).is_none());
+		assert!(f.eval(0.0).is_err());
 
 		let expr = "sqrt(x)";
 		let f = parse(expr).unwrap();
@@ -458,6 +920,286 @@ mod tests {
 		let expr = "log(10, x)";
 		let f = parse(expr).unwrap();
 		assert_eq!(f.to_string(), expr);
-		assert!(f.eval(0.0).is_none());
+		assert!(f.eval(0.0).is_err());
+	}
+
+	#[test]
+	fn test_parse_simplify() {
+		let f = parse("x * 1 + 2 * 3 * x").unwrap().simplify();
+		assert_eq!(f.to_string(), "x + 6 * x");
+
+		let f = parse("pi / 2").unwrap().simplify();
+		assert_eq!(f.to_string(), std::f64::consts::FRAC_PI_2.to_string());
+	}
+
+	#[test]
+	fn test_parse_error() {
+		let err = parse("(x + 1").unwrap_err();
+		assert_eq!(err.to_string(), "expected `)`, but found end of input");
+		assert_eq!(err.span, Span::new(6, 6));
+
+		let err = parse("x + * 2").unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			"invalid unary operator `*`, only `-` and `not` are allowed"
+		);
+		assert_eq!(
+			err.render("x + * 2"),
+			"x + * 2\n    ^ invalid unary operator `*`, only `-` and `not` are allowed"
+		);
+
+		let err = parse("foo(x)").unwrap_err();
+		assert_eq!(err.kind, ParseErrorKind::UnknownFunction("foo".to_string()));
+		assert_eq!(err.span, Span::new(0, 3));
+
+		let err = parse("log(2 3)").unwrap_err();
+		assert_eq!(err.to_string(), "expected `,`, but found `3`");
+		assert_eq!(
+			err.render("log(2 3)"),
+			"log(2 3)\n      ^ expected `,`, but found `3`"
+		);
+
+		let err = parse("x )").unwrap_err();
+		assert_eq!(err.to_string(), "expected end of input, but found `)`");
+
+		let err = parse("").unwrap_err();
+		assert_eq!(err.kind, ParseErrorKind::UnexpectedToken(Token::Eof));
+	}
+
+	#[test]
+	fn test_parse_implicit_mul() {
+		let f = parse("2x").unwrap();
+		assert_eq!(f.to_string(), "2 * x");
+		assert_eq!(f.eval(3.0).unwrap(), 6.0);
+
+		let f = parse("3sin(x)").unwrap();
+		assert_eq!(f.to_string(), "3 * sin(x)");
+		assert_eq!(f.eval(0.0).unwrap(), 0.0);
+
+		let f = parse("(x+1)(x-1)").unwrap();
+		assert_eq!(f.to_string(), "(x + 1) * (x - 1)");
+		assert_eq!(f.eval(3.0).unwrap(), 8.0);
+
+		let f = parse("2pi x").unwrap();
+		assert_eq!(f.to_string(), "2 * pi * x");
+		assert_eq!(f.eval(1.0).unwrap(), 2.0 * std::f32::consts::PI);
+
+		// same precedence as `*`
+		let f = parse("1 + 2x ** 2").unwrap();
+		assert_eq!(f.to_string(), "1 + 2 * x ** 2");
+		assert_eq!(f.eval(3.0).unwrap(), 19.0);
+
+		let f = parse("x / 2x").unwrap();
+		assert_eq!(f.eval(3.0).unwrap(), 4.5);
+
+		// a number cannot be the right operand
+		assert!(parse("2 3").is_err());
+		assert!(parse("x 2").is_err());
+	}
+
+	#[test]
+	fn test_parse_pow() {
+		// right-associative
+		let f = parse("2 ** 3 ** 2").unwrap();
+		assert_eq!(f.eval(0.0).unwrap(), 512.0);
+
+		let f = parse("2 ^ 3 ^ 2").unwrap();
+		assert_eq!(f.to_string(), "2 ** 3 ** 2");
+		assert_eq!(f.eval(0.0).unwrap(), 512.0);
+
+		let f = parse("2 ** 3 ** 2 * 2").unwrap();
+		assert_eq!(f.eval(0.0).unwrap(), 1024.0);
+
+		// unary minus binds looser than power
+		let f = parse("-x ** 2").unwrap();
+		assert_eq!(f.eval(3.0).unwrap(), -9.0);
+
+		let f = parse("2 ** -x").unwrap();
+		assert_eq!(f.eval(1.0).unwrap(), 0.5);
+
+		let f = parse("-x * 2").unwrap();
+		assert_eq!(f.eval(3.0).unwrap(), -6.0);
+
+		let f = parse("(-x) ^ 2").unwrap();
+		assert_eq!(f.eval(3.0).unwrap(), 9.0);
+	}
+
+	#[test]
+	fn test_parse_program() {
+		let program = parse_program("f(x) = x**2 + 1; g(x) = f(x - 1) / 2; g(x) + f(2)").unwrap();
+		assert_eq!(
+			program.to_string(),
+			"f(x) = x ** 2 + 1; g(x) = f(x - 1) / 2; g(x) + f(2)"
+		);
+		assert_eq!(program.eval(3.0).unwrap(), 7.5);
+		assert_eq!(program.definitions().len(), 2);
+
+		let program = parse_program("h(a, b) = a * b - 1; 2h(x, 3)").unwrap();
+		assert_eq!(program.to_string(), "h(a, b) = a * b - 1; 2 * h(x, 3)");
+		assert_eq!(program.eval(2.0).unwrap(), 10.0);
+
+		// an expression without definitions
+		assert_eq!(parse_program("x + 1").unwrap().eval(1.0).unwrap(), 2.0);
+	}
+
+	#[test]
+	fn test_parse_program_error() {
+		let err = parse_program("f(x) = f(x - 1) + 1; f(x)").unwrap_err();
+		assert_eq!(
+			err.kind,
+			ParseErrorKind::RecursiveDefinition("f".to_string())
+		);
+		assert_eq!(err.to_string(), "function `f` cannot call itself");
+		assert_eq!(err.span, Span::new(7, 8));
+
+		// the body only binds the parameters
+		let err = parse_program("f(t) = t + x; f(x)").unwrap_err();
+		assert_eq!(err.kind, ParseErrorKind::UnboundVariable("x".to_string()));
+
+		let err = parse_program("sin(x) = x; sin(x)").unwrap_err();
+		assert_eq!(err.to_string(), "`sin` is already defined");
+
+		let err = parse_program("f(x, y) = x + y; f(x)").unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			"function `f` takes 2 argument(s), but 1 given"
+		);
+
+		let err = parse_program("f(x) = x + 1 2").unwrap_err();
+		assert_eq!(err.to_string(), "expected `;`, but found `2`");
+	}
+
+	#[test]
+	fn test_parse_registered() {
+		let registry = FunctionRegistry::new().with(crate::expression::CustomFunction::new(
+			"clamp01",
+			1,
+			|args| args[0].clamp(0.0, 1.0),
+			|_| vec![num!(1.0).into()],
+		));
+
+		let f = Parser::new("2clamp01(x - 1)")
+			.with_registry(&registry)
+			.parse()
+			.unwrap();
+		assert_eq!(f.to_string(), "2 * clamp01(x - 1)");
+		assert_eq!(f.eval(3.0).unwrap(), 2.0);
+
+		let err = Parser::new("clamp01(x, 1)")
+			.with_registry(&registry)
+			.parse()
+			.unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			"function `clamp01` takes 1 argument(s), but 2 given"
+		);
+
+		// registered names cannot be defined by the program
+		let err = Parser::new("clamp01(x) = x; x")
+			.with_registry(&registry)
+			.parse_program()
+			.unwrap_err();
+		assert_eq!(err.to_string(), "`clamp01` is already defined");
+
+		assert!(parse("clamp01(x)").is_err());
+	}
+
+	#[test]
+	fn test_parse_logical() {
+		let f = parse("x < 0 or x >= 1 and not x == 2").unwrap();
+		assert_eq!(f.to_string(), "x < 0 or x >= 1 and not x == 2");
+		let expected = [(-1.0, 1.0), (0.5, 0.0), (1.0, 1.0), (2.0, 0.0)];
+		for (x, y) in expected {
+			assert_eq!(f.eval(x).unwrap(), y, "at {x}");
+		}
+
+		// comparisons bind looser than arithmetic
+		let f = parse("x + 1 < 2x").unwrap();
+		assert_eq!(f.eval(2.0).unwrap(), 1.0);
+
+		// a number followed by `!=` is not a factorial
+		let f = parse("2!=x").unwrap();
+		assert_eq!(f.to_string(), "2 != x");
+	}
+
+	#[test]
+	fn test_parse_conditional() {
+		let f = parse("if(x < 0, -x, x ** 2)").unwrap();
+		assert_eq!(f.eval(-3.0).unwrap(), 3.0);
+		assert_eq!(f.eval(3.0).unwrap(), 9.0);
+
+		// step function
+		let f = parse("2if(x >= 0, 1, 0)").unwrap();
+		assert_eq!(f.to_string(), "2 * if(x >= 0, 1, 0)");
+		assert_eq!(f.eval(0.0).unwrap(), 2.0);
+
+		let f = parse("if(x > 0, ln(x))").unwrap();
+		assert!(f.eval(-1.0).is_err());
+
+		let err = parse("if(x)").unwrap_err();
+		assert_eq!(
+			err.kind,
+			ParseErrorKind::ArgumentCount {
+				name: "if".to_string(),
+				expected: 2..=3,
+				found: 1
+			}
+		);
+		assert_eq!(
+			err.to_string(),
+			"function `if` takes 2 to 3 arguments, but 1 given"
+		);
+		let err = parse_program("if(x) = x; x").unwrap_err();
+		assert_eq!(err.to_string(), "`if` is already defined");
+	}
+
+	#[test]
+	fn test_parse_integral() {
+		let f = parse("integral(2x, 0, x)").unwrap();
+		assert_eq!(f.to_string(), "integral(2 * x, 0, x)");
+		assert!((f.eval(3.0f64).unwrap() - 9.0).abs() < 1e-10);
+
+		// x is bound in the integrand of a definition
+		let f = parse_program("F(t) = integral(x ** 2, 0, t); F(3) + F(x)").unwrap();
+		let f = Expression::from(f);
+		assert!((f.eval(3.0f64).unwrap() - 18.0).abs() < 1e-10);
+
+		let err = parse_program("F(t) = integral(x, 0, x); F(1)").unwrap_err();
+		assert_eq!(err.to_string(), "unbound variable `x`");
+		let err = parse("integral(x, 0)").unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			"function `integral` takes 3 argument(s), but 2 given"
+		);
+		let err = parse_program("integral(x) = x; x").unwrap_err();
+		assert_eq!(err.to_string(), "`integral` is already defined");
+	}
+
+	#[test]
+	fn test_parse_factorial() {
+		let f = parse("2x! + (x + 1)!! - -3!").unwrap();
+		assert_eq!(f.to_string(), "2 * x! + (x + 1)!! - -3!");
+		assert_eq!(f.eval(2.0f64).unwrap(), 4.0 + 720.0 + 6.0);
+
+		let f = parse("20! / x!").unwrap();
+		assert_eq!(f.eval(20.0f64).unwrap(), 1.0);
+
+		let err = parse("!x").unwrap_err();
+		assert_eq!(err.kind, ParseErrorKind::UnexpectedToken(Token::Factorial));
+	}
+
+	#[test]
+	fn test_parse_unicode() {
+		let f = parse("2π × √x ÷ x² − √(x + 1)").unwrap();
+		assert_eq!(f.to_string(), "2 * pi * sqrt(x) / x ** 2 - sqrt(x + 1)");
+
+		let f = parse("2√x³ + x⁻¹ + 2x!²").unwrap();
+		assert_eq!(f.to_string(), "2 * sqrt(x ** 3) + x ** -1 + 2 * x! ** 2");
+		assert_eq!(f.eval(4.0f64).unwrap(), 16.0 + 0.25 + 2.0 * 576.0);
+
+		assert_eq!(
+			parse("x ≤ 1 ≠ x ≥ 2").unwrap().to_string(),
+			"x <= 1 != x >= 2"
+		);
 	}
 }
@@ -0,0 +1,123 @@
+//! Programs of user-defined functions followed by an expression.
+
+use super::{Env, EvalError, Expression, Function, Scalar};
+use std::sync::Arc;
+
+/// Definition of a user function, e.g., `f(x) = x ** 2 + 1`.
+///
+/// The body may only use the parameters as variables.
+///
+/// ```bnf
+/// definition ::= id "(" [id ("," id)*] ")" "=" sub_expr
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Definition {
+	name: String,
+	params: Vec<String>,
+	body: Expression,
+}
+
+impl Definition {
+	pub fn new<S: AsRef<str>, P: AsRef<str>, T: Into<Expression>>(
+		name: S,
+		params: &[P],
+		body: T,
+	) -> Self {
+		Self {
+			name: name.as_ref().to_string(),
+			params: params.iter().map(|p| p.as_ref().to_string()).collect(),
+			body: body.into(),
+		}
+	}
+
+	/// The function name.
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// The parameter names.
+	#[must_use]
+	pub fn params(&self) -> &[String] {
+		&self.params
+	}
+
+	/// The expression of the parameters.
+	#[must_use]
+	pub fn body(&self) -> &Expression {
+		&self.body
+	}
+}
+
+impl std::fmt::Display for Definition {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"{}({}) = {}",
+			self.name,
+			self.params.join(", "),
+			self.body
+		)
+	}
+}
+
+/// Program of function definitions and the expression that uses them.
+///
+/// ```bnf
+/// program ::= (definition ";")* sub_expr eof
+/// ```
+///
+/// ```
+/// use rsap::expression::{parse_program, Function};
+///
+/// let program = parse_program("f(x) = x ** 2 + 1; g(x) = f(x - 1) / 2; g(x) + f(2)").unwrap();
+/// assert_eq!(program.eval(3.0).unwrap(), 7.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Program {
+	definitions: Vec<Arc<Definition>>,
+	expr: Expression,
+}
+
+impl Program {
+	pub fn new<T: Into<Expression>>(definitions: Vec<Arc<Definition>>, expr: T) -> Self {
+		Self {
+			definitions,
+			expr: expr.into(),
+		}
+	}
+
+	/// The function definitions in order.
+	#[must_use]
+	pub fn definitions(&self) -> &[Arc<Definition>] {
+		&self.definitions
+	}
+
+	/// The expression of the program, where the calls of user functions are resolved.
+	#[must_use]
+	pub fn expression(&self) -> &Expression {
+		&self.expr
+	}
+}
+
+impl std::fmt::Display for Program {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for definition in &self.definitions {
+			write!(f, "{definition}; ")?;
+		}
+		write!(f, "{}", self.expr)
+	}
+}
+
+impl Function for Program {
+	fn eval_with<T: Scalar>(&self, env: &Env<T>) -> Result<T, EvalError> {
+		self.expr.eval_with(env)
+	}
+}
+
+impl From<Program> for Expression {
+	fn from(val: Program) -> Self {
+		val.expr
+	}
+}
@@ -0,0 +1,310 @@
+//! Registry of functions defined outside the crate, e.g., `erf` or `sigmoid`.
+
+use super::{func, func2, Constant, Custom, Expression, IF, INTEGRAL};
+use crate::math::Interval;
+use std::sync::Arc;
+
+/// Evaluator of the argument values, which are in the domain.
+pub type EvalFn = Arc<dyn Fn(&[f64]) -> f64 + Send + Sync>;
+
+/// Domain check of the argument values.
+pub type DomainFn = Arc<dyn Fn(&[f64]) -> bool + Send + Sync>;
+
+/// Partial derivatives of the call with respect to each argument.
+pub type PartialsFn = Arc<dyn Fn(&Custom) -> Vec<Expression> + Send + Sync>;
+
+/// Bounds of the values for all the arguments in the intervals.
+pub type IntervalFn = Arc<dyn Fn(&[Interval]) -> Interval + Send + Sync>;
+
+/// Function registered by name, with its arity, evaluator, domain check and derivative rule,
+/// which may capture parameters, e.g., the slope of a sigmoid.
+#[derive(Clone)]
+pub struct CustomFunction {
+	name: String,
+	arity: usize,
+	eval: EvalFn,
+	domain: Option<DomainFn>,
+	partials: PartialsFn,
+	interval: Option<IntervalFn>,
+}
+
+impl CustomFunction {
+	/// Create a function defined everywhere, whose derivative is given by the partial derivatives
+	/// with respect to each argument.
+	pub fn new<S, E, P>(name: S, arity: usize, eval: E, partials: P) -> Self
+	where
+		S: AsRef<str>,
+		E: Fn(&[f64]) -> f64 + Send + Sync + 'static,
+		P: Fn(&Custom) -> Vec<Expression> + Send + Sync + 'static,
+	{
+		Self {
+			name: name.as_ref().to_string(),
+			arity,
+			eval: Arc::new(eval),
+			domain: None,
+			partials: Arc::new(partials),
+			interval: None,
+		}
+	}
+
+	/// Restrict the function to the arguments where `domain` is true.
+	#[must_use]
+	pub fn with_domain<D: Fn(&[f64]) -> bool + Send + Sync + 'static>(mut self, domain: D) -> Self {
+		self.domain = Some(Arc::new(domain));
+		self
+	}
+
+	/// Evaluate the function over intervals with `interval`, which is needed for plotting across
+	/// poles.
+	#[must_use]
+	pub fn with_interval<I: Fn(&[Interval]) -> Interval + Send + Sync + 'static>(
+		mut self,
+		interval: I,
+	) -> Self {
+		self.interval = Some(Arc::new(interval));
+		self
+	}
+
+	/// The function name.
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// The number of arguments.
+	#[must_use]
+	pub fn arity(&self) -> usize {
+		self.arity
+	}
+
+	/// Is the function defined for the argument values.
+	#[must_use]
+	pub fn is_defined(&self, args: &[f64]) -> bool {
+		self.domain.as_ref().is_none_or(|domain| domain(args))
+	}
+
+	/// The value of the function, which is in the domain.
+	#[must_use]
+	pub fn value(&self, args: &[f64]) -> f64 {
+		(self.eval)(args)
+	}
+
+	/// The partial derivatives of the call.
+	#[must_use]
+	pub fn partials(&self, call: &Custom) -> Vec<Expression> {
+		(self.partials)(call)
+	}
+
+	/// Bounds of the function over the intervals, if the function can be evaluated so.
+	#[must_use]
+	pub fn interval(&self, args: &[Interval]) -> Option<Interval> {
+		self.interval.as_ref().map(|interval| interval(args))
+	}
+}
+
+impl std::fmt::Debug for CustomFunction {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("CustomFunction")
+			.field("name", &self.name)
+			.field("arity", &self.arity)
+			.finish_non_exhaustive()
+	}
+}
+
+/// Functions that the parser recognizes in addition to the built-in ones.
+///
+/// ```
+/// use rsap::expression::{CustomFunction, Function, FunctionRegistry, Parser};
+/// use rsap::{mul, num, sub};
+///
+/// // sigmoid(x)' = sigmoid(x) * (1 - sigmoid(x))
+/// let sigmoid = CustomFunction::new(
+/// 	"sigmoid",
+/// 	1,
+/// 	|args| 1.0 / (1.0 + (-args[0]).exp()),
+/// 	|call| vec![mul!(call.clone(), sub!(num!(1.0), call.clone())).into()],
+/// );
+/// let registry = FunctionRegistry::new().with(sigmoid);
+///
+/// let f = Parser::new("sigmoid(2x)").with_registry(&registry).parse().unwrap();
+/// assert_eq!(f.eval(0.0).unwrap(), 0.5);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FunctionRegistry(Vec<Arc<CustomFunction>>);
+
+#[cfg(feature = "serde")]
+thread_local! {
+	/// Registry of the expression being deserialized on this thread.
+	static DESERIALIZING: std::cell::RefCell<FunctionRegistry> = std::cell::RefCell::default();
+}
+
+impl FunctionRegistry {
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Register the function and return the registry.
+	///
+	/// # Panics
+	///
+	/// Panics if the name is a built-in function, a constant, or a keyword, e.g., `if`.
+	#[must_use]
+	pub fn with(mut self, function: CustomFunction) -> Self {
+		let name = function.name().to_string();
+		assert!(self.register(function), "`{name}` is built in");
+		self
+	}
+
+	/// Register the function, replacing the previous one of the same name if any.
+	///
+	/// Return `false` without registering if the name is a built-in function, a constant, or a
+	/// keyword, e.g., `if`.
+	pub fn register(&mut self, function: CustomFunction) -> bool {
+		if is_builtin(function.name()) {
+			return false;
+		}
+
+		let function = Arc::new(function);
+		if let Some(f) = self.0.iter_mut().find(|f| f.name() == function.name()) {
+			*f = function;
+		} else {
+			self.0.push(function);
+		}
+		true
+	}
+
+	/// Deserialize an expression, where the calls of registered functions are resolved in the
+	/// registry.
+	///
+	/// # Errors
+	///
+	/// Return an error if the input is invalid or calls a function that is not registered.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use rsap::expression::{CustomFunction, FunctionRegistry, Parser};
+	/// let registry = FunctionRegistry::new()
+	/// 	.with(CustomFunction::new("double", 1, |args| 2.0 * args[0], |_| vec![]));
+	/// let f = Parser::new("double(x)").with_registry(&registry).parse().unwrap();
+	///
+	/// let json = serde_json::to_string(&f).unwrap();
+	/// let g = registry
+	/// 	.deserialize_expression(&mut serde_json::Deserializer::from_str(&json))
+	/// 	.unwrap();
+	/// assert_eq!(g.to_string(), "double(x)");
+	/// ```
+	#[cfg(feature = "serde")]
+	pub fn deserialize_expression<'de, D: serde::Deserializer<'de>>(
+		&self,
+		deserializer: D,
+	) -> Result<Expression, D::Error> {
+		let previous = DESERIALIZING.replace(self.clone());
+		let result = serde::Deserialize::deserialize(deserializer);
+		DESERIALIZING.set(previous);
+		result
+	}
+
+	/// Apply `f` to the registry of the expression being deserialized.
+	#[cfg(feature = "serde")]
+	pub(crate) fn deserializing<R, F: FnOnce(&Self) -> R>(f: F) -> R {
+		DESERIALIZING.with_borrow(f)
+	}
+
+	/// Get the function of the name.
+	///
+	/// Return `None` if no function of the name is registered.
+	#[must_use]
+	pub fn get<S: AsRef<str>>(&self, name: S) -> Option<&Arc<CustomFunction>> {
+		self.0.iter().find(|f| f.name() == name.as_ref())
+	}
+
+	/// Is the name a built-in or a registered function.
+	#[must_use]
+	pub fn contains<S: AsRef<str>>(&self, name: S) -> bool {
+		let name = name.as_ref();
+		is_builtin_function(name) || self.get(name).is_some()
+	}
+
+	/// The number of arguments of the built-in or registered function.
+	#[must_use]
+	pub fn arity<S: AsRef<str>>(&self, name: S) -> Option<usize> {
+		let name = name.as_ref();
+		if func::FUNCTION_NAMES.contains(&name) {
+			Some(1)
+		} else if func2::FUNCTION_NAMES.contains(&name) {
+			Some(2)
+		} else {
+			self.get(name).map(|f| f.arity())
+		}
+	}
+}
+
+fn is_builtin_function(name: &str) -> bool {
+	func::FUNCTION_NAMES.contains(&name) || func2::FUNCTION_NAMES.contains(&name)
+}
+
+/// Is the name reserved by a built-in function, a constant, or a keyword.
+fn is_builtin(name: &str) -> bool {
+	is_builtin_function(name) || Constant::new(name).is_some() || name == IF || name == INTEGRAL
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		expression::{Derivative, Function},
+		num,
+	};
+
+	fn erf() -> CustomFunction {
+		CustomFunction::new("erf", 1, |args| args[0], |_| vec![num!(1.0).into()])
+	}
+
+	#[test]
+	fn test_registry() {
+		let mut registry = FunctionRegistry::new().with(erf());
+		assert!(registry.contains("erf"));
+		assert!(registry.contains("sin"));
+		assert!(!registry.contains("sigmoid"));
+		assert_eq!(registry.arity("erf"), Some(1));
+		assert_eq!(registry.arity("log"), Some(2));
+
+		// built-in names are reserved
+		assert!(!registry.register(CustomFunction::new("sin", 1, |_| 0.0, |_| Vec::new())));
+		assert!(!registry.register(CustomFunction::new("pi", 0, |_| 0.0, |_| Vec::new())));
+
+		// the function of the same name is replaced
+		assert!(registry.register(CustomFunction::new("erf", 2, |_| 0.0, |_| Vec::new())));
+		assert_eq!(registry.arity("erf"), Some(2));
+	}
+
+	#[test]
+	fn test_capture() {
+		// sigmoid of the slope k, with the derivative k * sigmoid(x) * (1 - sigmoid(x))
+		let sigmoid = |k: f64| {
+			CustomFunction::new(
+				"sigmoid",
+				1,
+				move |args| 1.0 / (1.0 + (-k * args[0]).exp()),
+				move |call| {
+					vec![crate::mul!(
+						num!(k),
+						crate::mul!(call.clone(), crate::sub!(num!(1.0), call.clone()))
+					)
+					.into()]
+				},
+			)
+		};
+		let registry = FunctionRegistry::new().with(sigmoid(4.0));
+		let f = registry.get("sigmoid").unwrap();
+		assert!((f.value(&[0.5]) - 1.0 / (1.0 + (-2.0f64).exp())).abs() < 1e-15);
+
+		let f = crate::expression::Parser::new("sigmoid(x)")
+			.with_registry(&registry)
+			.parse()
+			.unwrap();
+		assert_eq!(f.derivative().eval(0.0).unwrap(), 1.0);
+	}
+}
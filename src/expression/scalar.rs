@@ -0,0 +1,254 @@
+//! Scalar types of evaluation.
+
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// Floating-point type that expressions evaluate to, e.g., `f32` or `f64`.
+pub trait Scalar:
+	Copy
+	+ PartialOrd
+	+ std::fmt::Debug
+	+ std::fmt::Display
+	+ Add<Output = Self>
+	+ Sub<Output = Self>
+	+ Mul<Output = Self>
+	+ Div<Output = Self>
+	+ Neg<Output = Self>
+	+ Rem<Output = Self>
+{
+	const ZERO: Self;
+	const ONE: Self;
+	const E: Self;
+	const PI: Self;
+	const FRAC_PI_2: Self;
+
+	/// Convert from `f64`, rounding to the nearest value.
+	fn from_f64(val: f64) -> Self;
+
+	/// Convert to `f64`.
+	fn to_f64(self) -> f64;
+
+	#[must_use]
+	fn abs(self) -> Self;
+	#[must_use]
+	fn sin(self) -> Self;
+	#[must_use]
+	fn cos(self) -> Self;
+	#[must_use]
+	fn tan(self) -> Self;
+	#[must_use]
+	fn asin(self) -> Self;
+	#[must_use]
+	fn acos(self) -> Self;
+	#[must_use]
+	fn atan(self) -> Self;
+	#[must_use]
+	fn ln(self) -> Self;
+	#[must_use]
+	fn sqrt(self) -> Self;
+	#[must_use]
+	fn powf(self, n: Self) -> Self;
+	#[must_use]
+	fn log(self, base: Self) -> Self;
+	#[must_use]
+	fn exp(self) -> Self;
+	#[must_use]
+	fn floor(self) -> Self;
+	#[must_use]
+	fn ceil(self) -> Self;
+	/// Round half away from zero.
+	#[must_use]
+	fn round(self) -> Self;
+	#[must_use]
+	fn signum(self) -> Self;
+	#[must_use]
+	fn sinh(self) -> Self;
+	#[must_use]
+	fn cosh(self) -> Self;
+	#[must_use]
+	fn tanh(self) -> Self;
+	#[must_use]
+	fn asinh(self) -> Self;
+	#[must_use]
+	fn acosh(self) -> Self;
+	#[must_use]
+	fn atanh(self) -> Self;
+	#[must_use]
+	fn log10(self) -> Self;
+	#[must_use]
+	fn log2(self) -> Self;
+	#[must_use]
+	fn atan2(self, x: Self) -> Self;
+	#[must_use]
+	fn hypot(self, y: Self) -> Self;
+
+	fn is_finite(self) -> bool;
+	fn is_nan(self) -> bool;
+
+	/// Truth value, i.e., 1 for true and 0 for false.
+	#[must_use]
+	fn from_bool(b: bool) -> Self {
+		if b {
+			Self::ONE
+		} else {
+			Self::ZERO
+		}
+	}
+
+	/// Is the value true, i.e., non-zero.
+	fn is_true(self) -> bool {
+		self != Self::ZERO
+	}
+}
+
+macro_rules! impl_scalar {
+	($t:ident) => {
+		impl Scalar for $t {
+			const ZERO: Self = 0.0;
+			const ONE: Self = 1.0;
+			const E: Self = std::$t::consts::E;
+			const PI: Self = std::$t::consts::PI;
+			const FRAC_PI_2: Self = std::$t::consts::FRAC_PI_2;
+
+			#[allow(clippy::cast_possible_truncation, clippy::cast_lossless)]
+			fn from_f64(val: f64) -> Self {
+				val as $t
+			}
+
+			fn to_f64(self) -> f64 {
+				f64::from(self)
+			}
+
+			fn abs(self) -> Self {
+				$t::abs(self)
+			}
+
+			fn sin(self) -> Self {
+				$t::sin(self)
+			}
+
+			fn cos(self) -> Self {
+				$t::cos(self)
+			}
+
+			fn tan(self) -> Self {
+				$t::tan(self)
+			}
+
+			fn asin(self) -> Self {
+				$t::asin(self)
+			}
+
+			fn acos(self) -> Self {
+				$t::acos(self)
+			}
+
+			fn atan(self) -> Self {
+				$t::atan(self)
+			}
+
+			fn ln(self) -> Self {
+				$t::ln(self)
+			}
+
+			fn sqrt(self) -> Self {
+				$t::sqrt(self)
+			}
+
+			fn powf(self, n: Self) -> Self {
+				$t::powf(self, n)
+			}
+
+			fn log(self, base: Self) -> Self {
+				$t::log(self, base)
+			}
+
+			fn exp(self) -> Self {
+				$t::exp(self)
+			}
+
+			fn floor(self) -> Self {
+				$t::floor(self)
+			}
+
+			fn ceil(self) -> Self {
+				$t::ceil(self)
+			}
+
+			fn round(self) -> Self {
+				$t::round(self)
+			}
+
+			fn signum(self) -> Self {
+				$t::signum(self)
+			}
+
+			fn sinh(self) -> Self {
+				$t::sinh(self)
+			}
+
+			fn cosh(self) -> Self {
+				$t::cosh(self)
+			}
+
+			fn tanh(self) -> Self {
+				$t::tanh(self)
+			}
+
+			fn asinh(self) -> Self {
+				$t::asinh(self)
+			}
+
+			fn acosh(self) -> Self {
+				$t::acosh(self)
+			}
+
+			fn atanh(self) -> Self {
+				$t::atanh(self)
+			}
+
+			fn log10(self) -> Self {
+				$t::log10(self)
+			}
+
+			fn log2(self) -> Self {
+				$t::log2(self)
+			}
+
+			fn atan2(self, x: Self) -> Self {
+				$t::atan2(self, x)
+			}
+
+			fn hypot(self, y: Self) -> Self {
+				$t::hypot(self, y)
+			}
+
+			fn is_finite(self) -> bool {
+				$t::is_finite(self)
+			}
+
+			fn is_nan(self) -> bool {
+				$t::is_nan(self)
+			}
+		}
+	};
+}
+
+impl_scalar!(f32);
+impl_scalar!(f64);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::expression::{parse, Function};
+
+	#[test]
+	fn test_precision() {
+		let f = parse("ln(1 + x)").unwrap();
+		assert_eq!(f.eval(1e-10f32).unwrap(), 0.0);
+		assert!((f.eval(1e-10f64).unwrap() - 1e-10).abs() < 1e-15);
+
+		assert_eq!(f64::from_f64(0.1), 0.1);
+		assert_eq!(f32::from_f64(0.1), 0.1f32);
+		assert_eq!(0.5f32.to_f64(), 0.5);
+	}
+}
@@ -1,14 +1,102 @@
+use super::{Env, EvalError, Expression, Scalar};
+use crate::math::{Complex, Interval};
+
 pub trait Function {
+	/// Evaluate the function in the scalar type of the environment, with the variables bound by
+	/// the environment in a single pass.
+	///
+	/// # Errors
+	///
+	/// Return the error of the first undefined node if the environment is not in the definition
+	/// domain or a variable is unbound.
+	fn eval_with<T: Scalar>(&self, env: &Env<T>) -> Result<T, EvalError>;
+
+	/// Is the environment in the definition domain.
+	fn is_valid_with<T: Scalar>(&self, env: &Env<T>) -> bool {
+		self.eval_with(env).is_ok()
+	}
+
 	/// Is input x in the definition domain.
-	#[allow(unused_variables)]
-	fn is_x_valid(&self, x: f32) -> bool {
+	fn is_x_valid<T: Scalar>(&self, x: T) -> bool {
+		self.is_valid_with(&Env::from_x(x))
+	}
+
+	/// Evaluate the function for the input x.
+	///
+	/// # Errors
+	///
+	/// Return an error if x is not in the definition domain.
+	fn eval<T: Scalar>(&self, x: T) -> Result<T, EvalError> {
+		self.eval_with(&Env::from_x(x))
+	}
+}
+
+/// Evaluation over complex numbers, using the principal branches of multivalued functions.
+pub trait ComplexFunction {
+	/// Evaluate the function with the variables bound by the environment.
+	///
+	/// # Errors
+	///
+	/// Return the error of the first undefined node, e.g., a pole or an unbound variable.
+	fn eval_complex_with(&self, env: &Env<Complex>) -> Result<Complex, EvalError>;
+
+	/// Evaluate the function for the input x.
+	///
+	/// # Errors
+	///
+	/// Return an error if x is a singularity.
+	fn eval_complex(&self, x: Complex) -> Result<Complex, EvalError> {
+		self.eval_complex_with(&Env::from_x(x))
+	}
+}
+
+/// Evaluation over intervals, bounding the values of the function for all the inputs in them.
+pub trait IntervalFunction {
+	/// Evaluate the function with the variables bound to intervals by the environment.
+	///
+	/// The result contains the value at every input where the function is defined, so it is empty
+	/// if the function is undefined over the whole intervals.
+	///
+	/// # Errors
+	///
+	/// Return an error if a variable is unbound or the expression is not real.
+	fn eval_interval_with(&self, env: &Env<Interval>) -> Result<Interval, EvalError>;
+
+	/// Evaluate the function for x in `[lo, hi]`.
+	///
+	/// # Errors
+	///
+	/// Return an error if a variable other than x is used or the expression is not real.
+	fn eval_interval(&self, lo: f64, hi: f64) -> Result<Interval, EvalError> {
+		self.eval_interval_with(&Env::from_x(Interval::new(lo, hi)))
+	}
+}
+
+/// Symbolic differentiation with respect to x.
+pub trait Derivative {
+	/// Does the expression not depend on x.
+	fn is_constant(&self) -> bool {
 		true
 	}
 
-	// Evaluate the function for the input x.
-	//
-	// # Return
-	//
-	// Return `None` if x is not in the definition domain.
-	fn eval(&self, x: f32) -> Option<f32>;
+	/// Return the derivative d/dx as a new expression.
+	fn derivative(&self) -> Expression;
+}
+
+/// Algebraic simplification.
+pub trait Simplify {
+	/// Return a simplified expression.
+	///
+	/// Constant sub-expressions are folded and identities are removed. Note that this may extend
+	/// the definition domain, e.g., `ln(x) * 0` is simplified to `0`.
+	fn simplify(&self) -> Expression;
+}
+
+/// Export for typesetting.
+pub trait Typeset {
+	/// Return the LaTeX source in math mode.
+	fn to_latex(&self) -> String;
+
+	/// Return the presentation `MathML` as a single element, to be put in a `<math>` element.
+	fn to_mathml(&self) -> String;
 }
@@ -1,46 +1,154 @@
 #[macro_use]
 mod macros;
 
-use super::{Expression, Function};
+use super::{
+	fmt_operand, fold, latex_operand, mathml_operand, ComplexFunction, Derivative, Env, EvalError,
+	Expression, Function, IntervalFunction, Scalar, Simplify, Typeset, NEG_PRECEDENCE, NOT,
+	NOT_PRECEDENCE,
+};
+use crate::{
+	math::{Complex, Interval},
+	num,
+};
 
 /// Unary expression.
 ///
 /// ```bnf
-/// u_expr ::= u_op expression
-/// u_op ::= "-" | "+"
+/// u_expr ::= "-" primary ("**" primary)* | "not" primary b_subexpr
 /// ```
-#[derive(Debug, Clone)]
+///
+/// The operand of `not` extends over the comparisons, e.g., `not x < 1` is `not (x < 1)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Unary {
 	Neg(Expression),
+	Not(Expression),
 }
 
 impl Unary {
 	pub fn new<S: AsRef<str>, T: Into<Expression>>(op: S, expr: T) -> Option<Self> {
 		match op.as_ref() {
 			"-" => Some(Self::Neg(expr.into())),
+			NOT => Some(Self::Not(expr.into())),
 			_ => None,
 		}
 	}
+
+	/// The operand.
+	#[must_use]
+	pub fn operand(&self) -> &Expression {
+		match self {
+			Unary::Neg(expr) | Unary::Not(expr) => expr,
+		}
+	}
+
+	/// Precedence of the expression when printed as an operand.
+	#[must_use]
+	pub fn precedence(&self) -> u8 {
+		match self {
+			Unary::Neg(_) => NEG_PRECEDENCE,
+			Unary::Not(_) => NOT_PRECEDENCE,
+		}
+	}
 }
 
 impl std::fmt::Display for Unary {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		match self {
-			Unary::Neg(expr) => write!(f, "-{expr}"),
+			Unary::Neg(expr) => {
+				write!(f, "-")?;
+				// the operand is parsed as a primary with `**` operators
+				fmt_operand(f, expr, expr.precedence() < NEG_PRECEDENCE)
+			}
+			Unary::Not(expr) => {
+				write!(f, "{NOT} ")?;
+				fmt_operand(f, expr, expr.precedence() < NOT_PRECEDENCE)
+			}
 		}
 	}
 }
 
 impl Function for Unary {
-	fn is_x_valid(&self, x: f32) -> bool {
+	fn eval_with<T: Scalar>(&self, env: &Env<T>) -> Result<T, EvalError> {
+		match self {
+			Unary::Neg(expr) => Ok(-expr.eval_with(env)?),
+			Unary::Not(expr) => Ok(T::from_bool(!expr.eval_with(env)?.is_true())),
+		}
+	}
+}
+
+impl ComplexFunction for Unary {
+	fn eval_complex_with(&self, env: &Env<Complex>) -> Result<Complex, EvalError> {
+		match self {
+			Unary::Neg(expr) => Ok(-expr.eval_complex_with(env)?),
+			Unary::Not(expr) => Ok(Complex::from(f64::from_bool(
+				expr.eval_complex_with(env)?.is_zero(),
+			))),
+		}
+	}
+}
+
+impl IntervalFunction for Unary {
+	fn eval_interval_with(&self, env: &Env<Interval>) -> Result<Interval, EvalError> {
+		match self {
+			Unary::Neg(expr) => Ok(-expr.eval_interval_with(env)?),
+			Unary::Not(expr) => Ok(expr.eval_interval_with(env)?.logical_not()),
+		}
+	}
+}
+
+impl Derivative for Unary {
+	fn is_constant(&self) -> bool {
+		self.operand().is_constant()
+	}
+
+	fn derivative(&self) -> Expression {
+		match self {
+			Unary::Neg(expr) => neg!(expr.derivative()).into(),
+			// truth values are piecewise constant
+			Unary::Not(_) => num!(0.0).into(),
+		}
+	}
+}
+
+impl Simplify for Unary {
+	fn simplify(&self) -> Expression {
+		match self {
+			Unary::Neg(expr) => match expr.simplify() {
+				// --x = x
+				Expression::Unary(e) if matches!(*e, Unary::Neg(_)) => e.operand().clone(),
+				expr => fold(neg!(expr).into()),
+			},
+			Unary::Not(expr) => fold(not!(expr.simplify()).into()),
+		}
+	}
+}
+
+impl Typeset for Unary {
+	fn to_latex(&self) -> String {
 		match self {
-			Unary::Neg(expr) => expr.is_x_valid(x),
+			// a negated negative operand is enclosed, e.g., -(-x)
+			Unary::Neg(expr) => format!(
+				"-{}",
+				latex_operand(expr, expr.typeset_precedence() <= NEG_PRECEDENCE)
+			),
+			Unary::Not(expr) => format!(
+				"\\lnot {}",
+				latex_operand(expr, expr.typeset_precedence() < NOT_PRECEDENCE)
+			),
 		}
 	}
 
-	fn eval(&self, x: f32) -> Option<f32> {
+	fn to_mathml(&self) -> String {
 		match self {
-			Unary::Neg(expr) => Some(-expr.eval(x)?),
+			Unary::Neg(expr) => format!(
+				"<mrow><mo>-</mo>{}</mrow>",
+				mathml_operand(expr, expr.typeset_precedence() <= NEG_PRECEDENCE)
+			),
+			Unary::Not(expr) => format!(
+				"<mrow><mo>¬</mo>{}</mrow>",
+				mathml_operand(expr, expr.typeset_precedence() < NOT_PRECEDENCE)
+			),
 		}
 	}
 }
@@ -54,7 +162,7 @@ impl From<Unary> for Expression {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::var;
+	use crate::{add, div, num, paren, pow, var};
 
 	#[test]
 	fn test_neg() {
@@ -62,4 +170,52 @@ mod tests {
 		assert_eq!(f.eval(0.0).unwrap(), 0.0);
 		assert_eq!(f.eval(1.0).unwrap(), -1.0);
 	}
+
+	#[test]
+	fn test_neg_derivative() {
+		let f = neg!(var!()).derivative();
+		assert_eq!(f.to_string(), "-1");
+		assert_eq!(f.eval(0.0).unwrap(), -1.0);
+	}
+
+	#[test]
+	fn test_neg_simplify() {
+		let f = neg!(neg!(var!())).simplify();
+		assert_eq!(f.to_string(), "x");
+
+		let f = neg!(paren!(neg!(var!()))).simplify();
+		assert_eq!(f.to_string(), "x");
+
+		let f = neg!(num!(2.0)).simplify();
+		assert_eq!(f.to_string(), "-2");
+	}
+
+	#[test]
+	fn test_neg_typeset() {
+		let f = neg!(pow!(var!(), num!(2.0)));
+		assert_eq!(f.to_latex(), "-x^{2}");
+
+		let f = neg!(paren!(add!(var!(), num!(1.0))));
+		assert_eq!(f.to_latex(), "-\\left(x + 1\\right)");
+		assert_eq!(
+			f.to_mathml(),
+			"<mrow><mo>-</mo><mrow><mo>(</mo><mrow><mi>x</mi><mo>+</mo><mn>1</mn></mrow><mo>)</mo></mrow></mrow>"
+		);
+
+		let f = neg!(div!(num!(1.0), var!()));
+		assert_eq!(f.to_latex(), "-\\frac{1}{x}");
+	}
+
+	#[test]
+	fn test_not() {
+		let f = not!(crate::lt!(var!(), num!(1.0)));
+		assert_eq!(f.to_string(), "not x < 1");
+		assert_eq!(f.eval(0.0).unwrap(), 0.0);
+		assert_eq!(f.eval(2.0).unwrap(), 1.0);
+		assert_eq!(f.derivative().to_string(), "0");
+		assert_eq!(f.to_latex(), "\\lnot x < 1");
+
+		let f = not!(paren!(crate::or!(var!(), num!(0.0))));
+		assert_eq!(f.simplify().to_string(), "not (x or 0)");
+	}
 }
@@ -11,3 +11,10 @@ macro_rules! neg {
 		$crate::unary_expr!("-", $e).unwrap()
 	};
 }
+
+#[macro_export]
+macro_rules! not {
+	($e:expr) => {
+		$crate::unary_expr!("not", $e).unwrap()
+	};
+}
@@ -1,36 +1,102 @@
-use super::{Expression, Function};
+use super::{
+	ComplexFunction, Derivative, Env, EvalError, Expression, Function, IntervalFunction, Scalar,
+	Simplify, Typeset,
+};
+use crate::math::{Complex, Interval};
+use crate::num;
 
-/// Variable expression, i.e., x.
+/// Variable expression, e.g., x, t, k1.
 ///
 /// ```bnf
-/// variable ::= "x"
+/// variable ::= id
 /// ```
-#[derive(Debug, Clone, Default)]
-pub struct Variable;
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Variable(String);
 
 impl Variable {
+	pub fn new<S: Into<String>>(name: S) -> Self {
+		Self(name.into())
+	}
+
 	#[must_use]
-	pub fn new() -> Self {
-		Self
+	pub fn name(&self) -> &str {
+		&self.0
+	}
+}
+
+impl Default for Variable {
+	/// The variable x.
+	fn default() -> Self {
+		Self::new("x")
 	}
 }
 
 #[macro_export]
 macro_rules! var {
 	() => {
-		$crate::expression::Variable::new()
+		$crate::expression::Variable::default()
+	};
+	($name:expr) => {
+		$crate::expression::Variable::new($name)
 	};
 }
 
 impl std::fmt::Display for Variable {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		write!(f, "x")
+		write!(f, "{}", self.0)
 	}
 }
 
 impl Function for Variable {
-	fn eval(&self, x: f32) -> Option<f32> {
-		Some(x)
+	fn eval_with<T: Scalar>(&self, env: &Env<T>) -> Result<T, EvalError> {
+		env.get(&self.0)
+			.ok_or_else(|| EvalError::UnboundVariable(self.0.clone()))
+	}
+}
+
+impl ComplexFunction for Variable {
+	fn eval_complex_with(&self, env: &Env<Complex>) -> Result<Complex, EvalError> {
+		env.get(&self.0)
+			.ok_or_else(|| EvalError::UnboundVariable(self.0.clone()))
+	}
+}
+
+impl IntervalFunction for Variable {
+	fn eval_interval_with(&self, env: &Env<Interval>) -> Result<Interval, EvalError> {
+		env.get(&self.0)
+			.ok_or_else(|| EvalError::UnboundVariable(self.0.clone()))
+	}
+}
+
+impl Derivative for Variable {
+	fn is_constant(&self) -> bool {
+		self.0 != "x"
+	}
+
+	fn derivative(&self) -> Expression {
+		num!(if self.is_constant() { 0.0 } else { 1.0 }).into()
+	}
+}
+
+impl Simplify for Variable {
+	fn simplify(&self) -> Expression {
+		self.clone().into()
+	}
+}
+
+impl Typeset for Variable {
+	fn to_latex(&self) -> String {
+		// single letters are italic in math mode, longer names are set as one word
+		if self.0.len() == 1 {
+			self.0.clone()
+		} else {
+			format!("\\mathit{{{}}}", self.0.replace('_', "\\_"))
+		}
+	}
+
+	fn to_mathml(&self) -> String {
+		format!("<mi>{}</mi>", self.0)
 	}
 }
 
@@ -49,5 +115,20 @@ mod tests {
 		let f = var!();
 		assert_eq!(f.eval(0.0).unwrap(), 0.0);
 		assert_eq!(f.eval(1.0).unwrap(), 1.0);
+
+		let f = var!("t");
+		assert_eq!(f.to_string(), "t");
+		assert_eq!(
+			f.eval(1.0),
+			Err(EvalError::UnboundVariable("t".to_string()))
+		);
+		assert_eq!(f.eval_with(&Env::new().with("t", 2.0)).unwrap(), 2.0);
+	}
+
+	#[test]
+	fn test_variable_typeset() {
+		assert_eq!(var!().to_latex(), "x");
+		assert_eq!(var!("t_0").to_latex(), "\\mathit{t\\_0}");
+		assert_eq!(var!("t_0").to_mathml(), "<mi>t_0</mi>");
 	}
 }
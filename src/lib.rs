@@ -4,6 +4,7 @@ pub mod expression;
 pub mod image;
 pub mod math;
 pub mod matrix44f;
+pub mod numerics;
 pub mod obj;
 pub mod option;
 pub mod utils;
@@ -1,3 +1,8 @@
+pub mod complex;
+pub mod interval;
+
+pub use complex::Complex;
+pub use interval::Interval;
 use std::f32::consts::PI;
 
 /// convert radian to degree.
@@ -30,23 +35,182 @@ pub fn deg2rad(deg: f32) -> f32 {
 	deg / 180.0 * PI
 }
 
-/// Calculate factorial of a number.
+/// Coefficients of the Lanczos approximation with g = 7.
+const LANCZOS: [f64; 9] = [
+	0.999_999_999_999_809_9,
+	676.520_368_121_885_1,
+	-1_259.139_216_722_402_8,
+	771.323_428_777_653_1,
+	-176.615_029_162_140_6,
+	12.507_343_278_686_905,
+	-0.138_571_095_265_720_12,
+	9.984_369_578_019_572e-6,
+	1.505_632_735_149_311_6e-7,
+];
+
+/// Bernoulli numbers `B_2, B_4, ..., B_14` of the asymptotic series of polygamma.
+const BERNOULLI: [f64; 7] = [
+	1.0 / 6.0,
+	-1.0 / 30.0,
+	1.0 / 42.0,
+	-1.0 / 30.0,
+	5.0 / 66.0,
+	-691.0 / 2730.0,
+	7.0 / 6.0,
+];
+
+/// Largest order of polygamma, above which `n!` overflows.
+pub const MAX_POLYGAMMA_ORDER: f64 = 170.0;
+
+/// Is x a pole of gamma, i.e., 0, -1, -2, ...
+#[must_use]
+#[inline]
+pub fn is_gamma_pole(x: f64) -> bool {
+	x <= 0.0 && x.fract() == 0.0
+}
+
+/// Gamma function by the Lanczos approximation, which is NaN at the poles.
 ///
 /// # Examples
 ///
 /// ```
 /// # use rsap::math::*;
-/// assert_eq!(factorial(0), 1);
-/// assert_eq!(factorial(1), 1);
-/// assert_eq!(factorial(2), 2);
-/// assert_eq!(factorial(6), 720);
+/// assert!((gamma(0.5) - std::f64::consts::PI.sqrt()).abs() < 1e-14);
+/// assert!(gamma(-1.0).is_nan());
 /// ```
 #[must_use]
-#[inline]
-pub fn factorial(n: u32) -> u32 {
-	if n <= 1 {
-		1
+pub fn gamma(x: f64) -> f64 {
+	use std::f64::consts::PI;
+
+	if is_gamma_pole(x) {
+		f64::NAN
+	} else if x > 172.0 {
+		// overflows, where exp(-t) below may underflow to 0
+		f64::INFINITY
+	} else if x < 0.5 {
+		// reflection formula
+		PI / ((PI * x).sin() * gamma(1.0 - x))
+	} else {
+		let x = x - 1.0;
+		let t = x + 7.5;
+		let sum = LANCZOS[1..]
+			.iter()
+			.zip(1..)
+			.fold(LANCZOS[0], |sum, (c, i)| sum + c / (x + f64::from(i)));
+
+		// t ** (x + 0.5) is split in halves, which do not overflow before exp(-t) scales them
+		let half = t.powf(0.5 * (x + 0.5));
+		(2.0 * PI).sqrt() * half * (-t).exp() * half * sum
+	}
+}
+
+/// Factorial generalized to real numbers, i.e., `gamma(x + 1)`, which is exact for integers and
+/// NaN at the negative integers.
+///
+/// # Examples
+///
+/// ```
+/// # use rsap::math::*;
+/// assert_eq!(factorial(0.0), 1.0);
+/// assert_eq!(factorial(6.0), 720.0);
+/// assert_eq!(factorial(20.0), 2_432_902_008_176_640_000.0);
+/// assert!((factorial(0.5) - 0.886_226_925_452_758).abs() < 1e-14);
+/// ```
+#[must_use]
+pub fn factorial(x: f64) -> f64 {
+	if x >= 0.0 && x.fract() == 0.0 && x <= 170.0 {
+		// the product of floats does not overflow like integers
+		let mut product = 1.0;
+		let mut k = 2.0;
+		while k <= x {
+			product *= k;
+			k += 1.0;
+		}
+		product
+	} else {
+		gamma(x + 1.0)
+	}
+}
+
+/// The n-th derivative of `cot(t)` as a polynomial of `cot(t)`, with the coefficients in
+/// ascending order.
+fn cot_derivative(n: f64) -> Vec<f64> {
+	let mut p = vec![0.0, 1.0];
+	let mut k = 0.0;
+	while k < n {
+		// (d/dt) p(cot(t)) = -(1 + cot(t) ** 2) * p'(cot(t))
+		let dp: Vec<_> = p
+			.iter()
+			.zip(0..)
+			.skip(1)
+			.map(|(c, i)| c * f64::from(i))
+			.collect();
+		p = vec![0.0; dp.len() + 2];
+		for (i, c) in dp.iter().enumerate() {
+			p[i] -= c;
+			p[i + 2] -= c;
+		}
+		k += 1.0;
+	}
+	p
+}
+
+/// Polygamma function of order n, i.e., the (n + 1)-th derivative of `ln(gamma(x))`, which is NaN
+/// if n is not an integer in `[0, MAX_POLYGAMMA_ORDER]` or x is a pole of gamma.
+///
+/// # Examples
+///
+/// ```
+/// # use rsap::math::*;
+/// // digamma(1) is minus the Euler-Mascheroni constant
+/// assert!((polygamma(0.0, 1.0) + 0.577_215_664_901_532_9).abs() < 1e-14);
+/// // trigamma(1) = pi ** 2 / 6
+/// assert!((polygamma(1.0, 1.0) - std::f64::consts::PI.powi(2) / 6.0).abs() < 1e-14);
+/// ```
+#[must_use]
+pub fn polygamma(n: f64, x: f64) -> f64 {
+	use std::f64::consts::PI;
+
+	if !(0.0..=MAX_POLYGAMMA_ORDER).contains(&n) || n.fract() != 0.0 || is_gamma_pole(x) {
+		return f64::NAN;
+	}
+	if x.is_infinite() && x > 0.0 {
+		return if n == 0.0 { f64::INFINITY } else { 0.0 };
+	}
+
+	let sign = if n % 2.0 == 0.0 { 1.0 } else { -1.0 };
+	if x < 0.5 {
+		// reflection formula, differentiated n times
+		let cot = 1.0 / (PI * x).tan();
+		let p = cot_derivative(n);
+		let cot_n = p.iter().rev().fold(0.0, |sum, c| sum * cot + c);
+		return sign * polygamma(n, 1.0 - x) - PI.powf(n + 1.0) * cot_n;
+	}
+
+	// shift x up by the recurrence, psi_n(x) = psi_n(x + 1) - (-1) ** n * n! / x ** (n + 1)
+	let n_factorial = factorial(n);
+	let mut x = x;
+	let mut sum = 0.0;
+	while x < 10.0 + n {
+		sum -= sign * n_factorial / x.powf(n + 1.0);
+		x += 1.0;
+	}
+
+	// asymptotic series
+	let terms = BERNOULLI.iter().zip(1..).map(|(b, k)| {
+		let k2 = f64::from(2 * k);
+		if n == 0.0 {
+			b / (k2 * x.powf(k2))
+		} else {
+			b * factorial(k2 + n - 1.0) / (factorial(k2) * x.powf(k2 + n))
+		}
+	});
+	sum + if n == 0.0 {
+		x.ln() - 0.5 / x - terms.sum::<f64>()
 	} else {
-		n * factorial(n - 1)
+		-sign
+			* (factorial(n - 1.0) / x.powf(n)
+				+ n_factorial / (2.0 * x.powf(n + 1.0))
+				+ terms.sum::<f64>())
 	}
 }
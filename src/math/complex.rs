@@ -0,0 +1,256 @@
+//! Complex numbers with principal branches of the multivalued functions.
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Complex number `re + im * i`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex {
+	pub re: f64,
+	pub im: f64,
+}
+
+impl Complex {
+	pub const ZERO: Self = Self::new(0.0, 0.0);
+	pub const ONE: Self = Self::new(1.0, 0.0);
+	pub const I: Self = Self::new(0.0, 1.0);
+
+	#[must_use]
+	pub const fn new(re: f64, im: f64) -> Self {
+		Self { re, im }
+	}
+
+	#[must_use]
+	pub fn is_zero(self) -> bool {
+		self.re == 0.0 && self.im == 0.0
+	}
+
+	#[must_use]
+	pub fn is_finite(self) -> bool {
+		self.re.is_finite() && self.im.is_finite()
+	}
+
+	/// Modulus.
+	#[must_use]
+	pub fn abs(self) -> f64 {
+		self.re.hypot(self.im)
+	}
+
+	/// Argument in (-pi, pi].
+	#[must_use]
+	pub fn arg(self) -> f64 {
+		self.im.atan2(self.re)
+	}
+
+	#[must_use]
+	pub fn exp(self) -> Self {
+		let r = self.re.exp();
+		Self::new(r * self.im.cos(), r * self.im.sin())
+	}
+
+	/// Principal natural logarithm, i.e., `ln|z| + i * arg(z)`.
+	#[must_use]
+	pub fn ln(self) -> Self {
+		Self::new(self.abs().ln(), self.arg())
+	}
+
+	/// Principal square root, with non-negative real part.
+	#[must_use]
+	pub fn sqrt(self) -> Self {
+		if self.im == 0.0 && self.re >= 0.0 {
+			return Self::from(self.re.sqrt());
+		}
+
+		// sqrt((|z| + re) / 2) + i * sign(im) * sqrt((|z| - re) / 2)
+		let r = self.abs();
+		let re = r.midpoint(self.re).sqrt();
+		let im = ((r - self.re) / 2.0).sqrt();
+		Self::new(re, if self.im < 0.0 { -im } else { im })
+	}
+
+	/// Principal power, i.e., `exp(w * ln(z))`.
+	///
+	/// Real powers of non-negative real numbers are computed in real numbers.
+	#[must_use]
+	pub fn powc(self, w: Self) -> Self {
+		if self.im == 0.0 && self.re >= 0.0 && w.im == 0.0 {
+			return Self::from(self.re.powf(w.re));
+		}
+		if self.is_zero() {
+			// 0 ** w = 0 for re(w) > 0
+			return Self::ZERO;
+		}
+
+		(w * self.ln()).exp()
+	}
+
+	#[must_use]
+	pub fn sin(self) -> Self {
+		Self::new(
+			self.re.sin() * self.im.cosh(),
+			self.re.cos() * self.im.sinh(),
+		)
+	}
+
+	#[must_use]
+	pub fn cos(self) -> Self {
+		Self::new(
+			self.re.cos() * self.im.cosh(),
+			-self.re.sin() * self.im.sinh(),
+		)
+	}
+
+	/// Principal arcsine, i.e., `-i * ln(i * z + sqrt(1 - z ** 2))`.
+	#[must_use]
+	pub fn asin(self) -> Self {
+		-Self::I * (Self::I * self + (Self::ONE - self * self).sqrt()).ln()
+	}
+
+	/// Principal arccosine, i.e., `pi / 2 - arcsin(z)`.
+	#[must_use]
+	pub fn acos(self) -> Self {
+		Self::from(std::f64::consts::FRAC_PI_2) - self.asin()
+	}
+
+	/// Principal arctangent, i.e., `i / 2 * (ln(1 - i * z) - ln(1 + i * z))`.
+	#[must_use]
+	pub fn atan(self) -> Self {
+		let iz = Self::I * self;
+		Self::new(0.0, 0.5) * ((Self::ONE - iz).ln() - (Self::ONE + iz).ln())
+	}
+
+	#[must_use]
+	pub fn sinh(self) -> Self {
+		Self::new(
+			self.re.sinh() * self.im.cos(),
+			self.re.cosh() * self.im.sin(),
+		)
+	}
+
+	#[must_use]
+	pub fn cosh(self) -> Self {
+		Self::new(
+			self.re.cosh() * self.im.cos(),
+			self.re.sinh() * self.im.sin(),
+		)
+	}
+
+	#[must_use]
+	pub fn tanh(self) -> Self {
+		self.sinh() / self.cosh()
+	}
+
+	/// Principal inverse hyperbolic sine, i.e., `ln(z + sqrt(z ** 2 + 1))`.
+	#[must_use]
+	pub fn asinh(self) -> Self {
+		(self + (self * self + Self::ONE).sqrt()).ln()
+	}
+
+	/// Principal inverse hyperbolic cosine, i.e., `ln(z + sqrt(z + 1) * sqrt(z - 1))`.
+	#[must_use]
+	pub fn acosh(self) -> Self {
+		(self + (self + Self::ONE).sqrt() * (self - Self::ONE).sqrt()).ln()
+	}
+
+	/// Principal inverse hyperbolic tangent, i.e., `(ln(1 + z) - ln(1 - z)) / 2`.
+	#[must_use]
+	pub fn atanh(self) -> Self {
+		Self::from(0.5) * ((Self::ONE + self).ln() - (Self::ONE - self).ln())
+	}
+}
+
+impl From<f64> for Complex {
+	fn from(re: f64) -> Self {
+		Self::new(re, 0.0)
+	}
+}
+
+impl std::fmt::Display for Complex {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.im.is_sign_negative() {
+			write!(f, "{} - {}i", self.re, -self.im)
+		} else {
+			write!(f, "{} + {}i", self.re, self.im)
+		}
+	}
+}
+
+impl Add for Complex {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self {
+		Self::new(self.re + rhs.re, self.im + rhs.im)
+	}
+}
+
+impl Sub for Complex {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self {
+		Self::new(self.re - rhs.re, self.im - rhs.im)
+	}
+}
+
+impl Mul for Complex {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self {
+		Self::new(
+			self.re * rhs.re - self.im * rhs.im,
+			self.re * rhs.im + self.im * rhs.re,
+		)
+	}
+}
+
+impl Div for Complex {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self {
+		let d = rhs.re * rhs.re + rhs.im * rhs.im;
+		Self::new(
+			(self.re * rhs.re + self.im * rhs.im) / d,
+			(self.im * rhs.re - self.re * rhs.im) / d,
+		)
+	}
+}
+
+impl Neg for Complex {
+	type Output = Self;
+
+	fn neg(self) -> Self {
+		Self::new(-self.re, -self.im)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn assert_close(a: Complex, b: Complex) {
+		assert!((a - b).abs() < 1e-12, "{a} != {b}");
+	}
+
+	#[test]
+	fn test_complex() {
+		use std::f64::consts::PI;
+
+		assert_eq!(Complex::I * Complex::I, -Complex::ONE);
+		assert_close(Complex::from(-4.0).sqrt(), Complex::new(0.0, 2.0));
+		assert_close(
+			Complex::from(-1.0).ln(),
+			Complex::new(0.0, std::f64::consts::PI),
+		);
+		assert_close(
+			Complex::from(-8.0).powc(Complex::from(1.0 / 3.0)),
+			Complex::new(1.0, 3f64.sqrt()),
+		);
+		assert_close(Complex::from(2.0).asin().sin(), Complex::from(2.0));
+
+		let z = Complex::new(0.5, 1.0);
+		assert_close(z.atan().sin() / z.atan().cos(), z);
+		assert_close(z.acos().cos(), z);
+		assert_close(z.asinh().sinh(), z);
+		assert_close(z.acosh().cosh(), z);
+		assert_close(z.atanh().tanh(), z);
+		assert_close(Complex::from(-2.0).acosh(), Complex::new(2f64.acosh(), PI));
+	}
+}
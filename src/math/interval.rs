@@ -0,0 +1,965 @@
+//! Interval arithmetic with outward rounding, which bounds the values of a function over a range of
+//! inputs.
+
+use super::{gamma, is_gamma_pole, polygamma, MAX_POLYGAMMA_ORDER};
+use std::f64::consts::{FRAC_PI_2, PI, TAU};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// More parts than this are merged into their hull to bound the cost of the operations.
+const MAX_PARTS: usize = 8;
+
+/// Bound of the error of gamma and polygamma, relative to the value or 1 if it is smaller.
+const SPECIAL_ERROR: f64 = 1e-12;
+
+/// The positive x where gamma has its minimum, and the minimum.
+const GAMMA_MIN: (f64, f64) = (1.461_632_144_968_362_3, 0.885_603_194_410_888_7);
+
+/// Set of real numbers as a union of disjoint closed intervals, sorted in ascending order.
+///
+/// The bounds may be infinite, e.g., `1 / [-1, 1]` is `[-inf, -1] ∪ [1, inf]`. The operations
+/// round outwards, so the result contains every value of the operation on the operands, but may
+/// be wider.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Interval {
+	parts: Vec<(f64, f64)>,
+}
+
+impl Interval {
+	/// Create the interval `[lo, hi]`, which is empty if `lo > hi` or a bound is NaN.
+	#[must_use]
+	pub fn new(lo: f64, hi: f64) -> Self {
+		if Self::is_valid_part(lo, hi) {
+			Self {
+				parts: vec![(lo, hi)],
+			}
+		} else {
+			Self::empty()
+		}
+	}
+
+	/// Create the interval that contains only `val`.
+	#[must_use]
+	pub fn point(val: f64) -> Self {
+		Self::new(val, val)
+	}
+
+	#[must_use]
+	pub fn empty() -> Self {
+		Self { parts: Vec::new() }
+	}
+
+	/// Create the interval of all real numbers.
+	#[must_use]
+	pub fn whole() -> Self {
+		Self::new(f64::NEG_INFINITY, f64::INFINITY)
+	}
+
+	/// Create the union of the intervals.
+	fn from_parts<I: IntoIterator<Item = (f64, f64)>>(parts: I) -> Self {
+		let mut parts: Vec<_> = parts
+			.into_iter()
+			.filter(|&(lo, hi)| Self::is_valid_part(lo, hi))
+			.collect();
+		parts.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+		let mut merged: Vec<(f64, f64)> = Vec::with_capacity(parts.len());
+		for (lo, hi) in parts {
+			match merged.last_mut() {
+				Some(last) if lo <= last.1 => last.1 = last.1.max(hi),
+				_ => merged.push((lo, hi)),
+			}
+		}
+
+		if merged.len() > MAX_PARTS {
+			merged = vec![(merged[0].0, merged[merged.len() - 1].1)];
+		}
+		Self { parts: merged }
+	}
+
+	/// Create the interval `[lo, hi]` widened by an ulp on each side, which contains the exact
+	/// value of bounds computed with a rounding error.
+	fn rounded(lo: f64, hi: f64) -> Self {
+		if Self::is_valid_part(lo, hi) {
+			Self::new(lo.next_down(), hi.next_up())
+		} else {
+			Self::empty()
+		}
+	}
+
+	/// Create the interval `[lo, hi]` widened by the error of the special functions.
+	fn special(lo: f64, hi: f64) -> Self {
+		let error = |v: f64| v.abs().max(1.0) * SPECIAL_ERROR;
+		Self::new(lo - error(lo), hi + error(hi))
+	}
+
+	/// Is `[lo, hi]` a non-empty interval of real numbers.
+	fn is_valid_part(lo: f64, hi: f64) -> bool {
+		lo <= hi && lo < f64::INFINITY && hi > f64::NEG_INFINITY
+	}
+
+	/// Apply `f` to each part and return the union of the results.
+	fn map<F: Fn(f64, f64) -> Self>(&self, f: F) -> Self {
+		Self::from_parts(self.parts.iter().flat_map(|&(lo, hi)| f(lo, hi).parts))
+	}
+
+	/// Apply `f` to each pair of parts and return the union of the results.
+	fn map2<F: Fn((f64, f64), (f64, f64)) -> Self>(&self, other: &Self, f: F) -> Self {
+		let f = &f;
+		Self::from_parts(self.parts.iter().flat_map(|&lhs| {
+			other
+				.parts
+				.iter()
+				.flat_map(move |&rhs| f(lhs, rhs).parts)
+				.collect::<Vec<_>>()
+		}))
+	}
+
+	/// The disjoint intervals in ascending order.
+	#[must_use]
+	pub fn parts(&self) -> &[(f64, f64)] {
+		&self.parts
+	}
+
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.parts.is_empty()
+	}
+
+	/// Does the set consist of more than one interval, e.g., around a pole.
+	#[must_use]
+	pub fn is_split(&self) -> bool {
+		self.parts.len() > 1
+	}
+
+	/// Are both bounds of the set finite.
+	#[must_use]
+	pub fn is_bounded(&self) -> bool {
+		self.hull()
+			.is_none_or(|(lo, hi)| lo.is_finite() && hi.is_finite())
+	}
+
+	/// The smallest and the largest bounds, or `None` if the set is empty.
+	#[must_use]
+	pub fn hull(&self) -> Option<(f64, f64)> {
+		Some((self.parts.first()?.0, self.parts.last()?.1))
+	}
+
+	#[must_use]
+	pub fn contains(&self, val: f64) -> bool {
+		self.parts.iter().any(|&(lo, hi)| lo <= val && val <= hi)
+	}
+
+	#[must_use]
+	pub fn union(&self, other: &Self) -> Self {
+		Self::from_parts(self.parts.iter().chain(&other.parts).copied())
+	}
+
+	#[must_use]
+	pub fn intersect(&self, other: &Self) -> Self {
+		self.map2(other, |(a, b), (c, d)| Self::new(a.max(c), b.min(d)))
+	}
+
+	/// Reciprocal, which is split if the interval contains zero in its interior.
+	#[must_use]
+	pub fn recip(&self) -> Self {
+		self.map(|lo, hi| {
+			if lo > 0.0 || hi < 0.0 {
+				Self::rounded(1.0 / hi, 1.0 / lo)
+			} else if lo == 0.0 && hi == 0.0 {
+				Self::empty()
+			} else if lo == 0.0 {
+				Self::rounded(1.0 / hi, f64::INFINITY)
+			} else if hi == 0.0 {
+				Self::rounded(f64::NEG_INFINITY, 1.0 / lo)
+			} else {
+				Self::from_parts([(f64::NEG_INFINITY, 1.0 / lo), (1.0 / hi, f64::INFINITY)])
+					.map(Self::rounded)
+			}
+		})
+	}
+
+	/// Real power, which is defined for negative bases only with integer exponents.
+	#[must_use]
+	#[allow(clippy::float_cmp)]
+	pub fn pow(&self, exp: &Self) -> Self {
+		self.map2(exp, |(a, b), (c, d)| {
+			let mut parts = Vec::new();
+
+			// x ** y is monotonic in both x and y for positive x, so the extrema are at corners
+			// avoid -0, whose negative powers are negative
+			if b >= 0.0 {
+				let lo = if a > 0.0 { a } else { 0.0 };
+				parts.push(hull_of([lo, b], [c, d], f64::powf));
+			}
+
+			// |x| ** n with the sign of x ** n for negative x
+			if a < 0.0 {
+				let (lo, hi) = (if b < 0.0 { -b } else { 0.0 }, -a);
+				let (first, last) = (c.ceil(), d.floor());
+				if first == last {
+					let (min, max) = hull_of([lo, hi], [first, first], f64::powf);
+					parts.push(if first % 2.0 == 0.0 {
+						(min, max)
+					} else {
+						(-max, -min)
+					});
+				} else if first < last {
+					let (_, max) = hull_of([lo, hi], [first, last], f64::powf);
+					parts.push((-max, max));
+				}
+			}
+
+			Self::from_parts(parts).map(Self::rounded)
+		})
+	}
+
+	#[must_use]
+	pub fn sin(&self) -> Self {
+		self.map(|lo, hi| {
+			periodic_hull(lo, hi, f64::sin, FRAC_PI_2, -FRAC_PI_2)
+				.map_or_else(|| Self::new(-1.0, 1.0), |(lo, hi)| Self::rounded(lo, hi))
+		})
+	}
+
+	#[must_use]
+	pub fn cos(&self) -> Self {
+		self.map(|lo, hi| {
+			periodic_hull(lo, hi, f64::cos, 0.0, PI)
+				.map_or_else(|| Self::new(-1.0, 1.0), |(lo, hi)| Self::rounded(lo, hi))
+		})
+	}
+
+	/// Tangent, which is split around a pole.
+	#[must_use]
+	pub fn tan(&self) -> Self {
+		self.map(|lo, hi| {
+			if hi - lo >= PI {
+				return Self::whole();
+			}
+
+			// the tangent increases between poles at pi / 2 + k * pi, so a decrease also means a
+			// pole in between
+			let (tan_lo, tan_hi) = (lo.tan(), hi.tan());
+			let pole = FRAC_PI_2 + ((lo - FRAC_PI_2) / PI).ceil() * PI;
+			if pole <= hi || tan_lo > tan_hi {
+				Self::from_parts([(f64::NEG_INFINITY, tan_hi), (tan_lo, f64::INFINITY)])
+					.map(Self::rounded)
+			} else {
+				Self::rounded(tan_lo, tan_hi)
+			}
+		})
+	}
+
+	#[must_use]
+	pub fn asin(&self) -> Self {
+		self.intersect(&Self::new(-1.0, 1.0))
+			.map(|lo, hi| Self::rounded(lo.asin(), hi.asin()))
+	}
+
+	#[must_use]
+	pub fn acos(&self) -> Self {
+		self.intersect(&Self::new(-1.0, 1.0))
+			.map(|lo, hi| Self::rounded(hi.acos(), lo.acos()))
+	}
+
+	#[must_use]
+	pub fn atan(&self) -> Self {
+		self.map(|lo, hi| Self::rounded(lo.atan(), hi.atan()))
+	}
+
+	/// Square root of the non-negative part.
+	#[must_use]
+	pub fn sqrt(&self) -> Self {
+		self.map(|lo, hi| {
+			if hi < 0.0 {
+				Self::empty()
+			} else {
+				Self::rounded(lo.max(0.0).sqrt(), hi.sqrt())
+			}
+		})
+	}
+
+	/// Logarithm of the positive part, where `f` is increasing.
+	fn log_with(&self, f: fn(f64) -> f64) -> Self {
+		self.map(|lo, hi| {
+			if hi <= 0.0 {
+				Self::empty()
+			} else if lo <= 0.0 {
+				Self::rounded(f64::NEG_INFINITY, f(hi))
+			} else {
+				Self::rounded(f(lo), f(hi))
+			}
+		})
+	}
+
+	/// Image of a non-decreasing function with integer values, which is a set of points if they
+	/// are few, e.g., `floor`.
+	fn steps(&self, f: fn(f64) -> f64) -> Self {
+		self.map(|lo, hi| {
+			let (first, last) = (f(lo), f(hi));
+			let points: Vec<_> = std::iter::successors(Some(first), |v| Some(v + 1.0))
+				.take_while(|&v| v <= last)
+				.take(MAX_PARTS + 1)
+				.map(|v| (v, v))
+				.collect();
+
+			if points.len() > MAX_PARTS {
+				Self::new(first, last)
+			} else {
+				Self::from_parts(points)
+			}
+		})
+	}
+
+	#[must_use]
+	pub fn abs(&self) -> Self {
+		self.map(|lo, hi| {
+			if lo >= 0.0 {
+				Self::new(lo, hi)
+			} else if hi <= 0.0 {
+				Self::new(-hi, -lo)
+			} else {
+				Self::new(0.0, hi.max(-lo))
+			}
+		})
+	}
+
+	#[must_use]
+	pub fn exp(&self) -> Self {
+		self.map(|lo, hi| Self::rounded(lo.exp(), hi.exp()))
+	}
+
+	#[must_use]
+	pub fn floor(&self) -> Self {
+		self.steps(f64::floor)
+	}
+
+	#[must_use]
+	pub fn ceil(&self) -> Self {
+		self.steps(f64::ceil)
+	}
+
+	/// Round half away from zero.
+	#[must_use]
+	pub fn round(&self) -> Self {
+		self.steps(f64::round)
+	}
+
+	/// Sign, which is -1, 0, or 1.
+	#[must_use]
+	pub fn signum(&self) -> Self {
+		self.steps(|v| if v == 0.0 { 0.0 } else { v.signum() })
+	}
+
+	#[must_use]
+	pub fn sinh(&self) -> Self {
+		self.map(|lo, hi| Self::rounded(lo.sinh(), hi.sinh()))
+	}
+
+	#[must_use]
+	pub fn cosh(&self) -> Self {
+		self.map(|lo, hi| {
+			if lo >= 0.0 {
+				Self::rounded(lo.cosh(), hi.cosh())
+			} else if hi <= 0.0 {
+				Self::rounded(hi.cosh(), lo.cosh())
+			} else {
+				Self::rounded(1.0, lo.cosh().max(hi.cosh()))
+			}
+		})
+	}
+
+	#[must_use]
+	pub fn tanh(&self) -> Self {
+		self.map(|lo, hi| Self::rounded(lo.tanh(), hi.tanh()))
+	}
+
+	#[must_use]
+	pub fn asinh(&self) -> Self {
+		self.map(|lo, hi| Self::rounded(lo.asinh(), hi.asinh()))
+	}
+
+	#[must_use]
+	pub fn acosh(&self) -> Self {
+		self.intersect(&Self::new(1.0, f64::INFINITY))
+			.map(|lo, hi| Self::rounded(lo.acosh(), hi.acosh()))
+	}
+
+	/// Inverse hyperbolic tangent of the part in (-1, 1).
+	#[must_use]
+	pub fn atanh(&self) -> Self {
+		self.map(|lo, hi| {
+			if hi <= -1.0 || lo >= 1.0 {
+				Self::empty()
+			} else {
+				Self::rounded(lo.max(-1.0).atanh(), hi.min(1.0).atanh())
+			}
+		})
+	}
+
+	/// Natural logarithm of the positive part.
+	#[must_use]
+	pub fn ln(&self) -> Self {
+		self.log_with(f64::ln)
+	}
+
+	#[must_use]
+	pub fn log10(&self) -> Self {
+		self.log_with(f64::log10)
+	}
+
+	#[must_use]
+	pub fn log2(&self) -> Self {
+		self.log_with(f64::log2)
+	}
+
+	#[must_use]
+	pub fn min(&self, other: &Self) -> Self {
+		self.map2(other, |(a, b), (c, d)| Self::new(a.min(c), b.min(d)))
+	}
+
+	#[must_use]
+	pub fn max(&self, other: &Self) -> Self {
+		self.map2(other, |(a, b), (c, d)| Self::new(a.max(c), b.max(d)))
+	}
+
+	/// Angle of the point `(x, y)`, where the interval is y.
+	#[must_use]
+	pub fn atan2(&self, x: &Self) -> Self {
+		self.map2(x, |(a, b), (c, d)| {
+			// the angle jumps across the negative x-axis and is undefined at the origin
+			if a <= 0.0 && 0.0 <= b && c <= 0.0 {
+				Self::rounded(-PI, PI)
+			} else {
+				let (lo, hi) = hull_of([a, b], [c, d], f64::atan2);
+				Self::rounded(lo, hi)
+			}
+		})
+	}
+
+	/// Remainder of the floored division, which has the sign of the divisor.
+	#[must_use]
+	pub fn modulo(&self, divisor: &Self) -> Self {
+		self.map2(divisor, |(a, b), (c, d)| {
+			let (x, m) = (Self::new(a, b), Self::new(c, d));
+			let quotient = (x.clone() / m.clone()).floor();
+			// the remainder is between 0 and the divisor
+			(x - m * quotient).intersect(&Self::new(c.min(0.0), d.max(0.0)))
+		})
+	}
+
+	#[must_use]
+	pub fn hypot(&self, other: &Self) -> Self {
+		self.abs().map2(&other.abs(), |(a, b), (c, d)| {
+			Self::rounded(a.hypot(c), b.hypot(d))
+		})
+	}
+
+	/// Root with the index, where negative numbers have real roots only for odd indices.
+	#[must_use]
+	pub fn root(&self, index: &Self) -> Self {
+		self.map2(index, |(a, b), (c, d)| {
+			let exp = Self::new(c, d).recip();
+			let mut result = Self::new(a.max(0.0), b).pow(&exp);
+
+			// -(|x| ** (1 / n)) for negative x
+			let odd = c.ceil() + if c.ceil() % 2.0 == 0.0 { 1.0 } else { 0.0 };
+			if a < 0.0 && odd <= d {
+				let magnitude = Self::new(if b < 0.0 { -b } else { 0.0 }, -a);
+				result = result.union(&-magnitude.pow(&exp));
+			}
+
+			result
+		})
+	}
+
+	/// Apply `f` to the pieces between the poles of gamma, i.e., 0, -1, -2, ..., where a piece may
+	/// end at a pole but not contain one.
+	fn between_poles<F: Fn(f64, f64) -> Self>(&self, f: F) -> Self {
+		self.map(|lo, hi| {
+			let (first, last) = (lo.ceil(), hi.floor().min(0.0));
+			if last - first >= MAX_PARTS as f64 {
+				return Self::whole();
+			}
+
+			let poles =
+				std::iter::successors(Some(first), |p| Some(p + 1.0)).take_while(|&p| p <= last);
+			let bounds: Vec<_> = std::iter::once(lo)
+				.chain(poles)
+				.chain(std::iter::once(hi))
+				.collect();
+			Self::from_parts(
+				bounds
+					.windows(2)
+					.filter(|w| w[0] < w[1] || !is_gamma_pole(w[0]))
+					.flat_map(|w| f(w[0], w[1]).parts),
+			)
+		})
+	}
+
+	/// Gamma function, which is unbounded around a pole.
+	#[must_use]
+	pub fn gamma(&self) -> Self {
+		self.between_poles(|a, b| {
+			let (lo, hi) = gamma_bounds(a, b);
+			Self::special(lo, hi)
+		})
+	}
+
+	/// Polygamma function of the orders in the interval, which is unbounded around a pole.
+	#[must_use]
+	pub fn polygamma(&self, order: &Self) -> Self {
+		let orders: Vec<_> = order
+			.parts
+			.iter()
+			.flat_map(|&(lo, hi)| {
+				std::iter::successors(Some(lo.max(0.0).ceil()), |n| Some(n + 1.0))
+					.take_while(move |&n| n <= hi.min(MAX_POLYGAMMA_ORDER))
+			})
+			.take(MAX_PARTS + 1)
+			.collect();
+		if orders.len() > MAX_PARTS {
+			return Self::whole();
+		}
+
+		Self::from_parts(orders.into_iter().flat_map(|n| {
+			self.between_poles(|a, b| {
+				let (lo, hi) = polygamma_bounds(n, a, b);
+				Self::special(lo, hi)
+			})
+			.parts
+		}))
+	}
+
+	/// The set of truth values that may occur, where 0 is false and 1 is true.
+	#[must_use]
+	pub fn truth(can_be_false: bool, can_be_true: bool) -> Self {
+		let mut parts = Vec::new();
+		if can_be_false {
+			parts.push((0.0, 0.0));
+		}
+		if can_be_true {
+			parts.push((1.0, 1.0));
+		}
+		Self { parts }
+	}
+
+	/// Does the set contain false, i.e., 0.
+	#[must_use]
+	pub fn can_be_false(&self) -> bool {
+		self.contains(0.0)
+	}
+
+	/// Does the set contain true, i.e., a non-zero value.
+	#[must_use]
+	pub fn can_be_true(&self) -> bool {
+		self.parts.iter().any(|&(lo, hi)| lo < 0.0 || hi > 0.0)
+	}
+
+	/// Truth values of `a < b` for a in the set and b in the other.
+	#[must_use]
+	pub fn cmp_lt(&self, other: &Self) -> Self {
+		match (self.hull(), other.hull()) {
+			(Some((a, b)), Some((c, d))) => Self::truth(b >= c, a < d),
+			_ => Self::empty(),
+		}
+	}
+
+	/// Truth values of `a <= b` for a in the set and b in the other.
+	#[must_use]
+	pub fn cmp_le(&self, other: &Self) -> Self {
+		match (self.hull(), other.hull()) {
+			(Some((a, b)), Some((c, d))) => Self::truth(b > c, a <= d),
+			_ => Self::empty(),
+		}
+	}
+
+	/// Truth values of `a == b` for a in the set and b in the other.
+	#[must_use]
+	#[allow(clippy::float_cmp)]
+	pub fn cmp_eq(&self, other: &Self) -> Self {
+		match (self.hull(), other.hull()) {
+			(Some((a, b)), Some((c, d))) => Self::truth(
+				!(a == b && c == d && a == c),
+				!self.intersect(other).is_empty(),
+			),
+			_ => Self::empty(),
+		}
+	}
+
+	#[must_use]
+	pub fn logical_not(&self) -> Self {
+		Self::truth(self.can_be_true(), self.can_be_false())
+	}
+
+	#[must_use]
+	pub fn logical_and(&self, other: &Self) -> Self {
+		if self.is_empty() || other.is_empty() {
+			return Self::empty();
+		}
+		Self::truth(
+			self.can_be_false() || other.can_be_false(),
+			self.can_be_true() && other.can_be_true(),
+		)
+	}
+
+	#[must_use]
+	pub fn logical_or(&self, other: &Self) -> Self {
+		if self.is_empty() || other.is_empty() {
+			return Self::empty();
+		}
+		Self::truth(
+			self.can_be_false() && other.can_be_false(),
+			self.can_be_true() || other.can_be_true(),
+		)
+	}
+}
+
+/// The smallest and the largest values of `f(x, y)` at the corners of `xs` by `ys`.
+fn hull_of<F: Fn(f64, f64) -> f64>(xs: [f64; 2], ys: [f64; 2], f: F) -> (f64, f64) {
+	let values = [
+		f(xs[0], ys[0]),
+		f(xs[0], ys[1]),
+		f(xs[1], ys[0]),
+		f(xs[1], ys[1]),
+	];
+	values
+		.into_iter()
+		.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+			(min.min(v), max.max(v))
+		})
+}
+
+/// The range of a `2 * pi` periodic function `f` over `[lo, hi]`, given the phases of its maximum
+/// 1 and minimum -1, or `None` if the interval covers a period.
+fn periodic_hull<F: Fn(f64) -> f64>(
+	lo: f64,
+	hi: f64,
+	f: F,
+	max_phase: f64,
+	min_phase: f64,
+) -> Option<(f64, f64)> {
+	if hi - lo >= TAU {
+		return None;
+	}
+
+	let contains_phase = |phase: f64| phase + ((lo - phase) / TAU).ceil() * TAU <= hi;
+	let (f_lo, f_hi) = (f(lo), f(hi));
+	Some((
+		if contains_phase(min_phase) {
+			-1.0
+		} else {
+			f_lo.min(f_hi)
+		},
+		if contains_phase(max_phase) {
+			1.0
+		} else {
+			f_lo.max(f_hi)
+		},
+	))
+}
+
+/// Bounds of gamma over `[a, b]`, which has no pole inside.
+fn gamma_bounds(a: f64, b: f64) -> (f64, f64) {
+	let (x_min, y_min) = GAMMA_MIN;
+	if a >= 0.0 {
+		// decreasing before the minimum and increasing after
+		let (ga, gb) = (if a == 0.0 { f64::INFINITY } else { gamma(a) }, gamma(b));
+		if a >= x_min {
+			(ga, gb)
+		} else if b <= x_min {
+			(gb, ga)
+		} else {
+			(y_min, ga.max(gb))
+		}
+	} else {
+		// between two poles, the sign is constant and the magnitude has a minimum
+		let sign = gamma(0.5 * (a + b)).signum();
+		let at = |x: f64| {
+			if is_gamma_pole(x) {
+				sign * f64::INFINITY
+			} else {
+				gamma(x)
+			}
+		};
+		let (ga, gb) = (at(a), at(b));
+		if sign > 0.0 {
+			(0.0, ga.max(gb))
+		} else {
+			(ga.min(gb), 0.0)
+		}
+	}
+}
+
+/// Bounds of polygamma of order n over `[a, b]`, which has no pole inside.
+fn polygamma_bounds(n: f64, a: f64, b: f64) -> (f64, f64) {
+	let at = |x: f64, limit: f64| {
+		if is_gamma_pole(x) {
+			limit
+		} else {
+			polygamma(n, x)
+		}
+	};
+	if n % 2.0 == 0.0 {
+		// increasing between poles, since the derivative is of odd order
+		(at(a, f64::NEG_INFINITY), at(b, f64::INFINITY))
+	} else if a >= 0.0 {
+		// positive and decreasing
+		(at(b, f64::INFINITY), at(a, f64::INFINITY))
+	} else {
+		// positive with a minimum between poles
+		(0.0, at(a, f64::INFINITY).max(at(b, f64::INFINITY)))
+	}
+}
+
+/// Product of bounds, where zero times infinity is zero since bounds are not attained.
+fn mul_bound(a: f64, b: f64) -> f64 {
+	if a == 0.0 || b == 0.0 {
+		0.0
+	} else {
+		a * b
+	}
+}
+
+impl From<f64> for Interval {
+	fn from(val: f64) -> Self {
+		Self::point(val)
+	}
+}
+
+impl std::fmt::Display for Interval {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.is_empty() {
+			return write!(f, "∅");
+		}
+
+		for (i, (lo, hi)) in self.parts.iter().enumerate() {
+			if i > 0 {
+				write!(f, " ∪ ")?;
+			}
+			write!(f, "[{lo}, {hi}]")?;
+		}
+		Ok(())
+	}
+}
+
+impl Add for Interval {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self {
+		self.map2(&rhs, |(a, b), (c, d)| Self::rounded(a + c, b + d))
+	}
+}
+
+impl Sub for Interval {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self {
+		self.map2(&rhs, |(a, b), (c, d)| Self::rounded(a - d, b - c))
+	}
+}
+
+impl Mul for Interval {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self {
+		self.map2(&rhs, |(a, b), (c, d)| {
+			let (lo, hi) = hull_of([a, b], [c, d], mul_bound);
+			Self::rounded(lo, hi)
+		})
+	}
+}
+
+impl Div for Interval {
+	type Output = Self;
+
+	#[allow(clippy::suspicious_arithmetic_impl)]
+	fn div(self, rhs: Self) -> Self {
+		self * rhs.recip()
+	}
+}
+
+impl Neg for Interval {
+	type Output = Self;
+
+	fn neg(self) -> Self {
+		Self::from_parts(self.parts.iter().map(|&(lo, hi)| (-hi, -lo)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::f64::consts::FRAC_PI_4;
+
+	/// Assert that the interval contains every value and is at most `slack` wider on each side.
+	fn assert_tight(interval: &Interval, values: &[f64], slack: f64) {
+		for &v in values {
+			assert!(interval.contains(v), "{v} not in {interval}");
+		}
+		let (lo, hi) = interval.hull().unwrap();
+		let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+		let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+		assert!(
+			min - lo <= slack && hi - max <= slack,
+			"{interval} too wide"
+		);
+	}
+
+	#[test]
+	fn test_arithmetic() {
+		let a = Interval::new(1.0, 2.0);
+		let b = Interval::new(-3.0, 4.0);
+		assert_tight(&(a.clone() + b.clone()), &[-2.0, 6.0], 1e-12);
+		assert_tight(&(a.clone() - b.clone()), &[-3.0, 5.0], 1e-12);
+		assert_tight(&(a.clone() * b.clone()), &[-6.0, 8.0], 1e-12);
+		assert_eq!(-a.clone(), Interval::new(-2.0, -1.0));
+
+		// division by an interval containing zero splits
+		let q = Interval::point(1.0) / Interval::new(-1.0, 2.0);
+		assert!(q.is_split() && !q.is_bounded());
+		assert!(q.contains(-1.0) && q.contains(0.5) && !q.contains(0.0));
+		assert!((Interval::point(1.0) / Interval::point(0.0)).is_empty());
+
+		assert_eq!(
+			Interval::new(0.0, 1.0).union(&Interval::new(0.5, 2.0)),
+			Interval::new(0.0, 2.0)
+		);
+	}
+
+	#[test]
+	fn test_pow() {
+		let x = Interval::new(-2.0, 3.0);
+		assert_tight(&x.pow(&Interval::point(2.0)), &[0.0, 9.0], 1e-12);
+		assert_tight(&x.pow(&Interval::point(3.0)), &[-8.0, 27.0], 1e-12);
+		// negative bases are undefined for non-integer exponents
+		assert_tight(&x.pow(&Interval::point(0.5)), &[0.0, 3f64.sqrt()], 1e-12);
+		assert!(Interval::new(-2.0, -1.0)
+			.pow(&Interval::point(0.5))
+			.is_empty());
+
+		let r = x.pow(&Interval::point(-1.0));
+		assert!(r.is_split() && r.contains(-0.5) && r.contains(1.0 / 3.0));
+	}
+
+	#[test]
+	fn test_functions() {
+		let x = Interval::new(0.0, 2.0);
+		assert_tight(&x.sin(), &[0.0, 1.0], 1e-12);
+		assert_tight(&x.cos(), &[2f64.cos(), 1.0], 1e-12);
+		assert_eq!(Interval::new(0.0, 10.0).sin(), Interval::new(-1.0, 1.0));
+
+		let t = x.tan();
+		assert!(t.is_split() && t.contains(1f64.tan()) && t.contains(2f64.tan()));
+		assert_tight(
+			&Interval::new(-1.0, 1.0).tan(),
+			&[-1f64.tan(), 1f64.tan()],
+			1e-12,
+		);
+
+		assert_tight(
+			&Interval::new(-2.0, 0.5).asin(),
+			&[-FRAC_PI_2, 0.5f64.asin()],
+			1e-12,
+		);
+		assert_tight(&Interval::new(-1.0, 4.0).sqrt(), &[0.0, 2.0], 1e-12);
+		assert_eq!(
+			Interval::new(-1.0, 1.0).ln().hull(),
+			Some((f64::NEG_INFINITY, 1f64.ln().next_up()))
+		);
+		assert!(Interval::new(-2.0, 0.0).ln().is_empty());
+	}
+
+	#[test]
+	fn test_extended_functions() {
+		let x = Interval::new(-1.5, 2.5);
+		assert_tight(&x.abs(), &[0.0, 2.5], 1e-12);
+		// the few integers are kept apart
+		assert_eq!(x.floor().parts().len(), 5);
+		assert_eq!(x.floor().hull(), Some((-2.0, 2.0)));
+		assert!(Interval::new(-1.0, 1.0).signum().contains(0.0));
+		assert_tight(&x.tanh(), &[-1.5f64.tanh(), 2.5f64.tanh()], 1e-12);
+		assert!(Interval::new(-2.0, 0.5).acosh().is_empty());
+
+		let one = Interval::point(1.0);
+		assert_tight(&x.min(&one), &[-1.5, 1.0], 1e-12);
+		assert_tight(&x.max(&one), &[1.0, 2.5], 1e-12);
+
+		// the angle jumps across the negative x-axis
+		let y = Interval::new(-1.0, 1.0);
+		assert_eq!(
+			y.atan2(&Interval::new(-2.0, -1.0)).hull().unwrap().1,
+			PI.next_up()
+		);
+		assert_tight(
+			&y.atan2(&Interval::new(1.0, 2.0)),
+			&[-FRAC_PI_4, FRAC_PI_4],
+			1e-12,
+		);
+
+		let r = Interval::new(2.0, 4.0).modulo(&Interval::point(3.0));
+		assert!(r.contains(2.0) && r.contains(0.0) && r.contains(1.0));
+		assert!(r.hull().unwrap().1 <= 3.0);
+
+		let r = Interval::new(-8.0, 8.0).root(&Interval::point(3.0));
+		assert_tight(&r, &[-2.0, 2.0], 1e-12);
+		let r = Interval::new(-8.0, 4.0).root(&Interval::point(2.0));
+		assert_tight(&r, &[0.0, 2.0], 1e-12);
+	}
+
+	#[test]
+	fn test_special_functions() {
+		let x = Interval::new(2.0, 4.0);
+		assert_tight(&x.gamma(), &[1.0, 6.0], 1e-10);
+		// the minimum inside
+		assert_tight(&Interval::new(1.0, 2.0).gamma(), &[GAMMA_MIN.1, 1.0], 1e-10);
+		assert_tight(
+			&Interval::new(-1.75, -1.25).gamma(),
+			&[0.0, super::gamma(-1.75).max(super::gamma(-1.25))],
+			1e-10,
+		);
+
+		// split around the poles
+		let y = Interval::new(-0.5, 0.5).gamma();
+		assert!(y.is_split() && !y.is_bounded());
+		assert!(!y.contains(1.0) && y.contains(super::gamma(-0.5)));
+		assert!(Interval::point(-1.0).gamma().is_empty());
+
+		let order = Interval::point(0.0);
+		assert_tight(
+			&x.polygamma(&order),
+			&[super::polygamma(0.0, 2.0), super::polygamma(0.0, 4.0)],
+			1e-10,
+		);
+		assert!(!Interval::new(-1.5, -0.5).polygamma(&order).is_bounded());
+		let y = x.polygamma(&Interval::new(0.0, 1.0));
+		assert!(y.contains(super::polygamma(0.0, 3.0)) && y.contains(super::polygamma(1.0, 3.0)));
+		assert!(x.polygamma(&Interval::point(0.5)).is_empty());
+	}
+
+	#[test]
+	fn test_truth() {
+		let (a, b) = (Interval::new(0.0, 1.0), Interval::new(2.0, 3.0));
+		assert_eq!(a.cmp_lt(&b), Interval::point(1.0));
+		assert_eq!(b.cmp_le(&a), Interval::point(0.0));
+		assert_eq!(a.cmp_lt(&Interval::point(0.5)), Interval::truth(true, true));
+		assert_eq!(a.cmp_eq(&b), Interval::point(0.0));
+		assert_eq!(
+			Interval::point(2.0).cmp_eq(&Interval::point(2.0)),
+			Interval::point(1.0)
+		);
+		assert!(a.cmp_lt(&Interval::empty()).is_empty());
+
+		let maybe = Interval::truth(true, true);
+		assert_eq!(
+			maybe.logical_and(&Interval::point(0.0)),
+			Interval::point(0.0)
+		);
+		assert_eq!(
+			maybe.logical_or(&Interval::point(2.0)),
+			Interval::point(1.0)
+		);
+		assert_eq!(Interval::new(1.0, 2.0).logical_not(), Interval::point(0.0));
+		assert_eq!(a.logical_not(), maybe);
+	}
+}
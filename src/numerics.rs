@@ -0,0 +1,751 @@
+//! Numerical solvers of functions of x, i.e., roots, extrema, intersections and integrals.
+//!
+//! The scanning solvers sample the function on an evenly spaced grid and refine each bracket, so
+//! features narrower than the grid spacing may be missed.
+
+use crate::expression::{Derivative, Function, Simplify};
+
+/// Tolerance of x in roots.
+pub const TOLERANCE: f64 = 1e-12;
+
+/// Relative tolerance of x in extrema, which is about the square root of the machine epsilon
+/// since the function is flat there.
+const EXTREMUM_TOLERANCE: f64 = 3e-8;
+
+/// Max |f(x)| of an extremum that touches the x-axis, e.g., `x ** 2` at 0.
+const TOUCH_TOLERANCE: f64 = 1e-10;
+
+const MAX_ITERATIONS: usize = 100;
+
+/// Max number of segments that the interval of integration is split into.
+const MAX_SEGMENTS: usize = 2000;
+
+/// Nodes of the 15-point Kronrod rule in `[0, 1]`, where those of the odd indices are the nodes
+/// of the 7-point Gauss rule.
+const KRONROD_NODES: [f64; 8] = [
+	0.991_455_371_120_812_6,
+	0.949_107_912_342_758_5,
+	0.864_864_423_359_769_1,
+	0.741_531_185_599_394_4,
+	0.586_087_235_467_691_1,
+	0.405_845_151_377_397_2,
+	0.207_784_955_007_898_5,
+	0.0,
+];
+
+const KRONROD_WEIGHTS: [f64; 8] = [
+	0.022_935_322_010_529_22,
+	0.063_092_092_629_978_55,
+	0.104_790_010_322_250_2,
+	0.140_653_259_715_525_9,
+	0.169_004_726_639_267_9,
+	0.190_350_578_064_785_4,
+	0.204_432_940_075_298_9,
+	0.209_482_141_084_727_8,
+];
+
+const GAUSS_WEIGHTS: [f64; 4] = [
+	0.129_484_966_168_869_7,
+	0.279_705_391_489_276_7,
+	0.381_830_050_505_118_9,
+	0.417_959_183_673_469_4,
+];
+
+/// Kind of local extrema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtremumKind {
+	Minimum,
+	Maximum,
+}
+
+/// Local extremum of a function.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Extremum {
+	pub x: f64,
+	pub y: f64,
+	pub kind: ExtremumKind,
+}
+
+/// Error of numerical integration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IntegrationError {
+	/// The integrand is undefined over a part of the interval, which includes the point.
+	Undefined(f64),
+	/// The integral does not converge to the tolerance, e.g., across a pole, or a bound is not
+	/// finite.
+	Divergent,
+}
+
+impl std::fmt::Display for IntegrationError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			IntegrationError::Undefined(x) => write!(f, "the integrand is undefined at {x}"),
+			IntegrationError::Divergent => write!(f, "the integral does not converge"),
+		}
+	}
+}
+
+impl std::error::Error for IntegrationError {}
+
+/// The finite value of the function at x.
+fn value<F: Function>(f: &F, x: f64) -> Option<f64> {
+	f.eval(x).ok().filter(|y| y.is_finite())
+}
+
+/// Values at `samples + 1` evenly spaced points in `[lo, hi]`.
+fn sample<G: Fn(f64) -> Option<f64>>(
+	g: &G,
+	lo: f64,
+	hi: f64,
+	samples: usize,
+) -> Vec<(f64, Option<f64>)> {
+	let samples = samples.max(1);
+	let step = (hi - lo) / samples as f64;
+	(0..=samples)
+		.map(|i| {
+			let x = if i == samples {
+				hi
+			} else {
+				lo + i as f64 * step
+			};
+			(x, g(x))
+		})
+		.collect()
+}
+
+/// Root in `[a, b]` by Brent's method, where `g(a) = fa` and `g(b) = fb` differ in sign.
+#[allow(clippy::float_cmp, clippy::many_single_char_names)]
+fn brent_root<G: Fn(f64) -> Option<f64>>(
+	g: &G,
+	mut a: f64,
+	mut b: f64,
+	mut fa: f64,
+	mut fb: f64,
+) -> Option<f64> {
+	if fa == 0.0 {
+		return Some(a);
+	}
+	if fb == 0.0 {
+		return Some(b);
+	}
+	if (fa < 0.0) == (fb < 0.0) {
+		return None;
+	}
+
+	// b is the best estimate, and c is the other end of the bracket
+	let (mut c, mut fc) = (b, fb);
+	let (mut d, mut e) = (0.0, 0.0);
+	for _ in 0..MAX_ITERATIONS {
+		if (fb < 0.0) == (fc < 0.0) {
+			(c, fc) = (a, fa);
+			d = b - a;
+			e = d;
+		}
+		if fc.abs() < fb.abs() {
+			(a, fa) = (b, fb);
+			(b, fb) = (c, fc);
+			(c, fc) = (a, fa);
+		}
+
+		let tol = 2.0 * f64::EPSILON * b.abs() + 0.5 * TOLERANCE;
+		let m = 0.5 * (c - b);
+		if m.abs() <= tol || fb == 0.0 {
+			return Some(b);
+		}
+
+		if e.abs() >= tol && fa.abs() > fb.abs() {
+			// secant or inverse quadratic interpolation
+			let s = fb / fa;
+			let (mut p, mut q) = if a == c {
+				(2.0 * m * s, 1.0 - s)
+			} else {
+				let (q, r) = (fa / fc, fb / fc);
+				(
+					s * (2.0 * m * q * (q - r) - (b - a) * (r - 1.0)),
+					(q - 1.0) * (r - 1.0) * (s - 1.0),
+				)
+			};
+			if p > 0.0 {
+				q = -q;
+			}
+			p = p.abs();
+
+			// accept the interpolation only if it stays in the bracket and converges fast enough
+			if 2.0 * p < (3.0 * m * q - (tol * q).abs()).min((e * q).abs()) {
+				e = d;
+				d = p / q;
+			} else {
+				d = m;
+				e = d;
+			}
+		} else {
+			// bisection
+			d = m;
+			e = d;
+		}
+
+		(a, fa) = (b, fb);
+		b += if d.abs() > tol { d } else { tol.copysign(m) };
+		fb = g(b)?;
+	}
+
+	Some(b)
+}
+
+/// Minimum in `[a, b]` by Brent's method, where `g(x)` is less than the values at the bounds.
+#[allow(clippy::float_cmp, clippy::many_single_char_names)]
+fn brent_min<G: Fn(f64) -> Option<f64>>(
+	g: &G,
+	mut a: f64,
+	mut b: f64,
+	x: f64,
+) -> Option<(f64, f64)> {
+	// golden section ratio
+	const C: f64 = 0.381_966_011_250_105_1;
+	const ZEPS: f64 = 1e-10;
+
+	let (mut x, mut w, mut v) = (x, x, x);
+	let fx = g(x)?;
+	let (mut fx, mut fw, mut fv) = (fx, fx, fx);
+	let (mut d, mut e): (f64, f64) = (0.0, 0.0);
+
+	for _ in 0..MAX_ITERATIONS {
+		let xm = 0.5 * (a + b);
+		let tol1 = EXTREMUM_TOLERANCE * x.abs() + ZEPS;
+		let tol2 = 2.0 * tol1;
+		if (x - xm).abs() <= tol2 - 0.5 * (b - a) {
+			return Some((x, fx));
+		}
+
+		if e.abs() > tol1 {
+			// parabola through x, w and v
+			let r = (x - w) * (fx - fv);
+			let mut q = (x - v) * (fx - fw);
+			let mut p = (x - v) * q - (x - w) * r;
+			q = 2.0 * (q - r);
+			if q > 0.0 {
+				p = -p;
+			}
+			q = q.abs();
+
+			let e_prev = e;
+			e = d;
+			if p.abs() >= (0.5 * q * e_prev).abs() || p <= q * (a - x) || p >= q * (b - x) {
+				e = if x >= xm { a - x } else { b - x };
+				d = C * e;
+			} else {
+				d = p / q;
+				let u = x + d;
+				if u - a < tol2 || b - u < tol2 {
+					d = tol1.copysign(xm - x);
+				}
+			}
+		} else {
+			e = if x >= xm { a - x } else { b - x };
+			d = C * e;
+		}
+
+		let u = if d.abs() >= tol1 {
+			x + d
+		} else {
+			x + tol1.copysign(d)
+		};
+		let fu = g(u)?;
+
+		if fu <= fx {
+			if u >= x {
+				a = x;
+			} else {
+				b = x;
+			}
+			(v, fv) = (w, fw);
+			(w, fw) = (x, fx);
+			(x, fx) = (u, fu);
+		} else {
+			if u < x {
+				a = u;
+			} else {
+				b = u;
+			}
+			if fu <= fw || w == x {
+				(v, fv) = (w, fw);
+				(w, fw) = (u, fu);
+			} else if fu <= fv || v == x || v == w {
+				(v, fv) = (u, fu);
+			}
+		}
+	}
+
+	Some((x, fx))
+}
+
+/// Local extrema inside the sampled interval, at the runs of equal values lower or higher than
+/// both neighbors.
+fn extrema_in<G: Fn(f64) -> Option<f64>>(g: &G, values: &[(f64, Option<f64>)]) -> Vec<Extremum> {
+	let neg = |x| g(x).map(|y: f64| -y);
+	let mut extrema = Vec::new();
+
+	let mut first = 1;
+	while first + 1 < values.len() {
+		let (x, Some(y)) = values[first] else {
+			first += 1;
+			continue;
+		};
+
+		// the end of the run of equal values
+		let mut last = first;
+		while values.get(last + 1).is_some_and(|&(_, v)| v == Some(y)) {
+			last += 1;
+		}
+
+		if let (Some(&(start, Some(left))), Some(&(end, Some(right)))) =
+			(values.get(first - 1), values.get(last + 1))
+		{
+			if left > y && right > y {
+				if let Some((x, y)) = brent_min(g, start, end, x) {
+					extrema.push(Extremum {
+						x,
+						y,
+						kind: ExtremumKind::Minimum,
+					});
+				}
+			} else if left < y && right < y {
+				if let Some((x, y)) = brent_min(&neg, start, end, x) {
+					extrema.push(Extremum {
+						x,
+						y: -y,
+						kind: ExtremumKind::Maximum,
+					});
+				}
+			}
+		}
+
+		first = last + 1;
+	}
+
+	extrema
+}
+
+/// Roots in the sampled interval, at the sign changes and the extrema touching the x-axis.
+fn roots_in<G: Fn(f64) -> Option<f64>>(
+	g: &G,
+	values: &[(f64, Option<f64>)],
+	extrema: &[Extremum],
+) -> Vec<f64> {
+	let mut roots = Vec::new();
+
+	for pair in values.windows(2) {
+		let [(a, Some(fa)), (b, Some(fb))] = *pair else {
+			continue;
+		};
+
+		if fa == 0.0 {
+			roots.push(a);
+		} else if fb != 0.0 && (fa < 0.0) != (fb < 0.0) {
+			// a pole also changes the sign, where the values do not converge to 0
+			let scale = fa.abs().max(fb.abs());
+			roots.extend(
+				brent_root(g, a, b, fa, fb)
+					.filter(|&x| g(x).is_some_and(|y| y.abs() <= 1e-6 * (1.0 + scale))),
+			);
+		}
+	}
+	if let Some(&(x, Some(y))) = values.last() {
+		if y == 0.0 {
+			roots.push(x);
+		}
+	}
+
+	roots.extend(
+		extrema
+			.iter()
+			.filter(|e| e.y.abs() <= TOUCH_TOLERANCE)
+			.map(|e| e.x),
+	);
+
+	roots.sort_by(f64::total_cmp);
+	roots.dedup_by(|a, b| (*a - *b).abs() <= EXTREMUM_TOLERANCE * (1.0 + a.abs()));
+	roots
+}
+
+/// Roots and local extrema in `[lo, hi]` from a single scan with `samples` steps.
+fn critical_points_of<G: Fn(f64) -> Option<f64>>(
+	g: &G,
+	lo: f64,
+	hi: f64,
+	samples: usize,
+) -> (Vec<f64>, Vec<Extremum>) {
+	let values = sample(g, lo, hi, samples);
+	let extrema = extrema_in(g, &values);
+	(roots_in(g, &values, &extrema), extrema)
+}
+
+/// Find a root of the function in `[a, b]` by Brent's method.
+///
+/// Return `None` if the function is undefined at a bound or has the same sign at both bounds.
+pub fn find_root<F: Function>(f: &F, a: f64, b: f64) -> Option<f64> {
+	let g = |x| value(f, x);
+	brent_root(&g, a, b, g(a)?, g(b)?)
+}
+
+/// Find the roots of the function in `[lo, hi]`, in ascending order.
+///
+/// The interval is scanned with `samples` steps for sign changes, which are refined by Brent's
+/// method, and for extrema on the x-axis, e.g., `x ** 2` at 0. Poles are not roots.
+///
+/// ```
+/// use rsap::{expression::parse, numerics::find_roots};
+///
+/// let roots = find_roots(&parse("x ** 2 - 2").unwrap(), -10.0, 10.0, 100);
+/// assert_eq!(roots.len(), 2);
+/// assert!((roots[1] - 2f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn find_roots<F: Function>(f: &F, lo: f64, hi: f64, samples: usize) -> Vec<f64> {
+	critical_points_of(&|x| value(f, x), lo, hi, samples).0
+}
+
+/// Find the local minima and maxima of the function in `(lo, hi)`, in ascending order of x.
+///
+/// The interval is scanned with `samples` steps for turning points, which are refined by Brent's
+/// minimization.
+pub fn find_extrema<F: Function>(f: &F, lo: f64, hi: f64, samples: usize) -> Vec<Extremum> {
+	let g = |x| value(f, x);
+	extrema_in(&g, &sample(&g, lo, hi, samples))
+}
+
+/// Find the roots and the local extrema of `g` in `[lo, hi]` as [`find_roots`] and
+/// [`find_extrema`] do, scanning the interval once.
+///
+/// `g` is the finite value of a function at x, or `None` outside the domain, so that compiled
+/// expressions can be solved.
+pub fn find_critical_points<G: Fn(f64) -> Option<f64>>(
+	g: &G,
+	lo: f64,
+	hi: f64,
+	samples: usize,
+) -> (Vec<f64>, Vec<Extremum>) {
+	critical_points_of(&|x| g(x).filter(|y| y.is_finite()), lo, hi, samples)
+}
+
+/// Find the points where the functions intersect in `[lo, hi]`, in ascending order of x.
+pub fn find_intersections<F: Function, G: Function>(
+	f: &F,
+	g: &G,
+	lo: f64,
+	hi: f64,
+	samples: usize,
+) -> Vec<(f64, f64)> {
+	critical_points_of(&|x| Some(value(f, x)? - value(g, x)?), lo, hi, samples)
+		.0
+		.into_iter()
+		.filter_map(|x| Some((x, value(f, x)?)))
+		.collect()
+}
+
+/// Find a root of the function near `x0` by Newton's method with the symbolic derivative.
+///
+/// Return `None` if the iteration leaves the domain, hits a zero derivative, or does not
+/// converge.
+pub fn newton<F: Function + Derivative>(f: &F, x0: f64) -> Option<f64> {
+	let df = f.derivative().simplify();
+
+	let mut x = x0;
+	for _ in 0..MAX_ITERATIONS {
+		let y = value(f, x)?;
+		if y == 0.0 {
+			return Some(x);
+		}
+
+		let step = y / value(&df, x).filter(|dy| *dy != 0.0)?;
+		x -= step;
+		if step.abs() <= TOLERANCE * (1.0 + x.abs()) {
+			return value(f, x).map(|_| x);
+		}
+	}
+
+	None
+}
+
+/// Part of the interval of integration, with the estimated integral and its error.
+struct Segment {
+	a: f64,
+	b: f64,
+	value: f64,
+	error: f64,
+}
+
+/// Result of the Gauss-Kronrod rule over a segment.
+enum Estimate {
+	Segment(Segment),
+	/// The integrand is undefined at the node, so the segment is split there.
+	Split(f64),
+	/// The integrand is undefined at all the nodes, including this one.
+	Undefined(f64),
+}
+
+/// Integrate over `[a, b]` by the 15-point Kronrod rule, estimating the error by the embedded
+/// 7-point Gauss rule.
+///
+/// The nodes are inside the segment, so the integrand is not evaluated at the bounds.
+fn gauss_kronrod<G: FnMut(f64) -> Option<f64>>(g: &mut G, a: f64, b: f64) -> Estimate {
+	let center = 0.5 * (a + b);
+	let half = 0.5 * (b - a);
+
+	let (mut kronrod, mut gauss) = (0.0, 0.0);
+	let (mut defined, mut undefined) = (false, None);
+	for (i, (node, weight)) in KRONROD_NODES.iter().zip(KRONROD_WEIGHTS).enumerate() {
+		let xs = if i == KRONROD_NODES.len() - 1 {
+			vec![center]
+		} else {
+			vec![center - half * node, center + half * node]
+		};
+		for x in xs {
+			let Some(y) = g(x) else {
+				undefined = undefined.or(Some(x));
+				continue;
+			};
+			defined = true;
+			kronrod += weight * y;
+			if i % 2 == 1 {
+				gauss += GAUSS_WEIGHTS[i / 2] * y;
+			}
+		}
+	}
+
+	match undefined {
+		Some(x) if defined && a < x && x < b => Estimate::Split(x),
+		Some(x) => Estimate::Undefined(x),
+		None => Estimate::Segment(Segment {
+			a,
+			b,
+			value: kronrod * half,
+			error: ((kronrod - gauss) * half).abs(),
+		}),
+	}
+}
+
+/// Integrate `g` over `[a, b]`, where `None` means it is undefined.
+pub(crate) fn integrate_fn<G: FnMut(f64) -> Option<f64>>(
+	mut g: G,
+	a: f64,
+	b: f64,
+	tolerance: f64,
+) -> Result<f64, IntegrationError> {
+	if !a.is_finite() || !b.is_finite() {
+		return Err(IntegrationError::Divergent);
+	}
+	if b < a {
+		return integrate_fn(g, b, a, tolerance).map(|value| -value);
+	}
+	if b <= a {
+		return Ok(0.0);
+	}
+
+	let mut segments: Vec<Segment> = Vec::new();
+	let mut pending = vec![(a, b)];
+	let mut count = 0;
+	loop {
+		while let Some((a, b)) = pending.pop() {
+			count += 1;
+			if count > MAX_SEGMENTS {
+				return Err(IntegrationError::Divergent);
+			}
+
+			match gauss_kronrod(&mut g, a, b) {
+				Estimate::Segment(segment) => segments.push(segment),
+				// an isolated point, e.g., `sin(x) / x` at 0, is not evaluated by the open rule
+				Estimate::Split(x) => pending.extend([(a, x), (x, b)]),
+				Estimate::Undefined(x) => return Err(IntegrationError::Undefined(x)),
+			}
+		}
+
+		let value: f64 = segments.iter().map(|s| s.value).sum();
+		let error: f64 = segments.iter().map(|s| s.error).sum();
+		if !value.is_finite() {
+			return Err(IntegrationError::Divergent);
+		}
+		// the error cannot be less than the rounding error
+		if error <= tolerance.max(50.0 * f64::EPSILON * value.abs()) {
+			return Ok(value);
+		}
+
+		// bisect the segment of the largest error, which is the one of a singularity if any
+		let worst = (0..segments.len())
+			.max_by(|&i, &j| segments[i].error.total_cmp(&segments[j].error))
+			.unwrap();
+		let Segment { a, b, .. } = segments.swap_remove(worst);
+		let mid = 0.5 * (a + b);
+		if mid <= a || b <= mid {
+			return Err(IntegrationError::Divergent);
+		}
+		pending.extend([(a, mid), (mid, b)]);
+	}
+}
+
+/// Integrate the function over `[a, b]` to the absolute `tolerance`, by adaptive Gauss-Kronrod
+/// quadrature.
+///
+/// The interval is split at the points where the function is undefined, e.g., `sin(x) / x` at 0,
+/// and the bounds are not evaluated, so integrable singularities there converge, e.g., `1 /
+/// sqrt(x)` at 0.
+///
+/// # Errors
+///
+/// Return [`IntegrationError::Undefined`] if the function is undefined over a part of the
+/// interval, and [`IntegrationError::Divergent`] if the integral does not converge, e.g., across
+/// a pole.
+///
+/// ```
+/// use rsap::{expression::parse, numerics::integrate};
+///
+/// let area = integrate(&parse("sin(x)").unwrap(), 0.0, std::f64::consts::PI, 1e-12).unwrap();
+/// assert!((area - 2.0).abs() < 1e-12);
+/// ```
+pub fn integrate<F: Function>(
+	f: &F,
+	a: f64,
+	b: f64,
+	tolerance: f64,
+) -> Result<f64, IntegrationError> {
+	integrate_fn(|x| value(f, x), a, b, tolerance)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::expression::parse;
+
+	fn assert_close(actual: &[f64], expected: &[f64], tolerance: f64) {
+		assert_eq!(actual.len(), expected.len(), "{actual:?} != {expected:?}");
+		for (a, e) in actual.iter().zip(expected) {
+			assert!((a - e).abs() <= tolerance, "{actual:?} != {expected:?}");
+		}
+	}
+
+	#[test]
+	fn test_find_root() {
+		let f = parse("cos(x) - x").unwrap();
+		let x = find_root(&f, 0.0, 1.0).unwrap();
+		assert!((x.cos() - x).abs() < 1e-12);
+		assert_eq!(find_root(&f, 1.0, 2.0), None);
+
+		let x = newton(&f, 1.0).unwrap();
+		assert!((x.cos() - x).abs() < 1e-12);
+		assert_eq!(newton(&parse("x ** 2 + 1").unwrap(), 0.0), None);
+	}
+
+	#[test]
+	fn test_find_roots() {
+		let f = parse("sin(x)").unwrap();
+		let expected: Vec<_> = (-3..=3)
+			.map(|k| f64::from(k) * std::f64::consts::PI)
+			.collect();
+		assert_close(&find_roots(&f, -10.0, 10.0, 1000), &expected, 1e-12);
+
+		// a root touching the x-axis and a pole
+		let f = parse("(x - 1) ** 2 / x").unwrap();
+		assert_close(&find_roots(&f, -3.0, 3.0, 99), &[1.0], 1e-7);
+		assert!(find_roots(&parse("tan(x) - 100").unwrap(), 1.0, 2.0, 100).len() == 1);
+
+		// undefined for negative x
+		let f = parse("sqrt(x) - 1").unwrap();
+		assert_close(&find_roots(&f, -4.0, 4.0, 100), &[1.0], 1e-12);
+
+		// a single step without samples
+		assert_close(
+			&find_roots(&parse("x").unwrap(), -1.0, 2.0, 0),
+			&[0.0],
+			1e-12,
+		);
+	}
+
+	#[test]
+	fn test_sample() {
+		let g = |x| Some(2.0 * x);
+		assert_eq!(
+			sample(&g, -1.0, 1.0, 0),
+			[(-1.0, Some(-2.0)), (1.0, Some(2.0))]
+		);
+		assert_eq!(
+			sample(&g, 0.0, 1.0, 2),
+			[(0.0, Some(0.0)), (0.5, Some(1.0)), (1.0, Some(2.0))]
+		);
+	}
+
+	#[test]
+	fn test_find_extrema() {
+		let f = parse("x ** 3 - 3x").unwrap();
+		let extrema = find_extrema(&f, -3.0, 3.0, 100);
+		assert_eq!(extrema.len(), 2);
+		assert_eq!(extrema[0].kind, ExtremumKind::Maximum);
+		assert!((extrema[0].x + 1.0).abs() < 1e-7 && (extrema[0].y - 2.0).abs() < 1e-12);
+		assert_eq!(extrema[1].kind, ExtremumKind::Minimum);
+		assert!((extrema[1].x - 1.0).abs() < 1e-7 && (extrema[1].y + 2.0).abs() < 1e-12);
+
+		// sampled symmetrically around the minimum
+		let extrema = find_extrema(&parse("abs(x)").unwrap(), -1.0, 1.0, 9);
+		assert_eq!(extrema.len(), 1);
+		assert!(extrema[0].x.abs() < 1e-7);
+	}
+
+	#[test]
+	fn test_find_critical_points() {
+		let f = parse("x ** 3 - 3x").unwrap();
+		let (roots, extrema) = find_critical_points(&|x| f.eval(x).ok(), -3.0, 3.0, 100);
+		assert_eq!(roots, find_roots(&f, -3.0, 3.0, 100));
+		assert_eq!(extrema, find_extrema(&f, -3.0, 3.0, 100));
+		assert_eq!(roots.len(), 3);
+
+		// poles are not extrema
+		let (roots, extrema) = find_critical_points(&|x| Some(1.0 / x), -1.0, 1.0, 10);
+		assert!(roots.is_empty() && extrema.is_empty());
+	}
+
+	#[test]
+	fn test_find_intersections() {
+		let (f, g) = (parse("x ** 2").unwrap(), parse("x + 2").unwrap());
+		let points = find_intersections(&f, &g, -5.0, 5.0, 100);
+		assert_eq!(points.len(), 2);
+		assert_close(&[points[0].0, points[0].1], &[-1.0, 1.0], 1e-12);
+		assert_close(&[points[1].0, points[1].1], &[2.0, 4.0], 1e-12);
+	}
+
+	#[test]
+	fn test_integrate() {
+		let f = parse("x ** 2").unwrap();
+		assert!((integrate(&f, 0.0, 3.0, 1e-12).unwrap() - 9.0).abs() < 1e-12);
+		assert!((integrate(&f, 3.0, 0.0, 1e-12).unwrap() + 9.0).abs() < 1e-12);
+		assert_eq!(integrate(&f, 1.0, 1.0, 1e-12), Ok(0.0));
+
+		// undefined at the center node 0
+		let f = parse("sin(x) / x").unwrap();
+		let area = integrate(&f, -1.0, 1.0, 1e-12).unwrap();
+		assert!((area - 1.892_166_140_734_366).abs() < 1e-12);
+
+		// integrable singularities at the bounds
+		let area = integrate(&parse("1 / sqrt(x)").unwrap(), 0.0, 1.0, 1e-10).unwrap();
+		assert!((area - 2.0).abs() < 1e-8);
+		let area = integrate(&parse("ln(x)").unwrap(), 0.0, 1.0, 1e-10).unwrap();
+		assert!((area + 1.0).abs() < 1e-8);
+	}
+
+	#[test]
+	fn test_integrate_error() {
+		let f = parse("1 / x").unwrap();
+		assert_eq!(
+			integrate(&f, -1.0, 1.0, 1e-10),
+			Err(IntegrationError::Divergent)
+		);
+		assert_eq!(
+			integrate(&parse("tan(x)").unwrap(), 0.0, 2.0, 1e-10),
+			Err(IntegrationError::Divergent)
+		);
+		assert_eq!(
+			integrate(&f, 1.0, f64::INFINITY, 1e-10),
+			Err(IntegrationError::Divergent)
+		);
+
+		let err = integrate(&parse("sqrt(x)").unwrap(), -1.0, 1.0, 1e-10).unwrap_err();
+		assert!(matches!(err, IntegrationError::Undefined(x) if x < 0.0));
+	}
+}
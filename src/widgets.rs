@@ -1,3 +1,3 @@
 pub mod canvas;
 
-pub use canvas::empty_canvas;
+pub use canvas::{empty_canvas, Plot, ShadedPlot};
@@ -5,17 +5,89 @@ use crate::expression::Expression;
 pub use consts::*;
 use iced::{
 	mouse,
-	widget::canvas::{self, Program},
+	widget::{
+		canvas::{self, Event, Program},
+		Action,
+	},
 	Renderer, Theme,
 };
+use std::cell::{Ref, RefCell};
 pub use utils::*;
 
+/// Zoom of a plot, which is changed by scrolling over it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+	zoom: f32,
+}
+
+impl Default for Viewport {
+	fn default() -> Self {
+		Self { zoom: 1.0 }
+	}
+}
+
+impl Viewport {
+	#[must_use]
+	pub fn zoom(&self) -> f32 {
+		self.zoom
+	}
+
+	/// Pixels per unit.
+	#[must_use]
+	pub fn scale(&self) -> f32 {
+		UNIT * self.zoom
+	}
+
+	/// Zoom in by `lines` scrolled, or out if negative.
+	pub fn zoom_by(&mut self, lines: f32) {
+		self.zoom = (self.zoom * ZOOM_STEP.powf(lines)).clamp(MIN_ZOOM, MAX_ZOOM);
+	}
+
+	/// Should the functions be evaluated in `f64` in a frame of the width in pixels, since `f32`
+	/// cannot resolve the points of a tiny viewport.
+	#[must_use]
+	pub fn uses_f64(&self, width: f32) -> bool {
+		width / self.scale() < F64_VIEWPORT_WIDTH
+	}
+
+	/// Zoom on the mouse wheel over the bounds.
+	fn update<Message>(
+		&mut self,
+		event: &Event,
+		bounds: iced::Rectangle,
+		cursor: mouse::Cursor,
+	) -> Option<Action<Message>> {
+		let Event::Mouse(mouse::Event::WheelScrolled { delta }) = event else {
+			return None;
+		};
+		if !cursor.is_over(bounds) {
+			return None;
+		}
+		let lines = match delta {
+			mouse::ScrollDelta::Lines { y, .. } => *y,
+			mouse::ScrollDelta::Pixels { y, .. } => y / UNIT,
+		};
+		self.zoom_by(lines);
+		Some(Action::request_redraw().and_capture())
+	}
+}
+
 impl<Message> Program<Message> for Expression {
-	type State = ();
+	type State = Viewport;
+
+	fn update(
+		&self,
+		state: &mut Self::State,
+		event: &Event,
+		bounds: iced::Rectangle,
+		cursor: mouse::Cursor,
+	) -> Option<Action<Message>> {
+		state.update(event, bounds, cursor)
+	}
 
 	fn draw(
 		&self,
-		_state: &Self::State,
+		state: &Self::State,
 		renderer: &Renderer,
 		_theme: &Theme,
 		bounds: iced::Rectangle,
@@ -23,8 +95,173 @@ impl<Message> Program<Message> for Expression {
 	) -> Vec<canvas::Geometry> {
 		let mut frame = canvas::Frame::new(renderer, bounds.size());
 
-		draw_background(&mut frame);
-		draw_function(&mut frame, self);
+		draw_background(&mut frame, state);
+		draw_function(&mut frame, state, self);
+
+		vec![frame.into_geometry()]
+	}
+}
+
+/// Roots and extrema of a plotted function, with the function and the frame they were found in.
+struct Markers {
+	expr: Expression,
+	viewport: Viewport,
+	width: f32,
+	points: Vec<(f64, f64)>,
+}
+
+/// State of a plot, which keeps the markers until the function or the viewport changes.
+#[derive(Default)]
+pub struct PlotState {
+	pub viewport: Viewport,
+	markers: RefCell<Option<Markers>>,
+}
+
+impl PlotState {
+	/// The roots and extrema of the function in a frame of the width.
+	fn markers(&self, expr: &Expression, width: f32) -> Ref<'_, [(f64, f64)]> {
+		let is_valid = self.markers.borrow().as_ref().is_some_and(|markers| {
+			markers.viewport == self.viewport
+				&& markers.width.to_bits() == width.to_bits()
+				&& markers.expr == *expr
+		});
+		if !is_valid {
+			self.markers.replace(Some(Markers {
+				expr: expr.clone(),
+				viewport: self.viewport,
+				width,
+				points: find_markers(expr, &self.viewport, width),
+			}));
+		}
+
+		Ref::map(self.markers.borrow(), |markers| {
+			markers
+				.as_ref()
+				.map_or(&[][..], |markers| &markers.points[..])
+		})
+	}
+}
+
+/// Plot of the function, optionally marking its roots and extrema.
+#[derive(Debug, Clone)]
+pub struct Plot {
+	pub expr: Expression,
+	pub markers: bool,
+}
+
+impl Plot {
+	#[must_use]
+	pub fn new(expr: Expression) -> Self {
+		Self {
+			expr,
+			markers: false,
+		}
+	}
+
+	/// Mark the roots and the extrema if `markers`.
+	#[must_use]
+	pub fn with_markers(mut self, markers: bool) -> Self {
+		self.markers = markers;
+		self
+	}
+
+	/// Draw the function and the markers over the background.
+	fn draw_on(&self, frame: &mut canvas::Frame, state: &PlotState) {
+		draw_function(frame, &state.viewport, &self.expr);
+		if self.markers {
+			let markers = state.markers(&self.expr, frame.width());
+			draw_markers(frame, &state.viewport, &markers);
+		}
+	}
+}
+
+impl From<Expression> for Plot {
+	fn from(expr: Expression) -> Self {
+		Self::new(expr)
+	}
+}
+
+impl<Message> Program<Message> for Plot {
+	type State = PlotState;
+
+	fn update(
+		&self,
+		state: &mut Self::State,
+		event: &Event,
+		bounds: iced::Rectangle,
+		cursor: mouse::Cursor,
+	) -> Option<Action<Message>> {
+		state.viewport.update(event, bounds, cursor)
+	}
+
+	fn draw(
+		&self,
+		state: &Self::State,
+		renderer: &Renderer,
+		_theme: &Theme,
+		bounds: iced::Rectangle,
+		_cursor: mouse::Cursor,
+	) -> Vec<canvas::Geometry> {
+		let mut frame = canvas::Frame::new(renderer, bounds.size());
+
+		draw_background(&mut frame, &state.viewport);
+		self.draw_on(&mut frame, state);
+
+		vec![frame.into_geometry()]
+	}
+}
+
+/// Plot of the function with the signed area under the curve shaded between two x values.
+#[derive(Debug, Clone)]
+pub struct ShadedPlot {
+	pub plot: Plot,
+	pub from: f64,
+	pub to: f64,
+}
+
+impl ShadedPlot {
+	#[must_use]
+	pub fn new<P: Into<Plot>>(plot: P, from: f64, to: f64) -> Self {
+		Self {
+			plot: plot.into(),
+			from,
+			to,
+		}
+	}
+}
+
+impl<Message> Program<Message> for ShadedPlot {
+	type State = PlotState;
+
+	fn update(
+		&self,
+		state: &mut Self::State,
+		event: &Event,
+		bounds: iced::Rectangle,
+		cursor: mouse::Cursor,
+	) -> Option<Action<Message>> {
+		state.viewport.update(event, bounds, cursor)
+	}
+
+	fn draw(
+		&self,
+		state: &Self::State,
+		renderer: &Renderer,
+		_theme: &Theme,
+		bounds: iced::Rectangle,
+		_cursor: mouse::Cursor,
+	) -> Vec<canvas::Geometry> {
+		let mut frame = canvas::Frame::new(renderer, bounds.size());
+
+		draw_background(&mut frame, &state.viewport);
+		draw_area(
+			&mut frame,
+			&state.viewport,
+			&self.plot.expr,
+			self.from,
+			self.to,
+		);
+		self.plot.draw_on(&mut frame, state);
 
 		vec![frame.into_geometry()]
 	}
@@ -45,7 +282,7 @@ impl<Message> Program<Message> for EmptyCanvas {
 	) -> Vec<canvas::Geometry> {
 		let mut frame = canvas::Frame::new(renderer, bounds.size());
 
-		draw_background(&mut frame);
+		draw_background(&mut frame, &Viewport::default());
 
 		vec![frame.into_geometry()]
 	}
@@ -56,3 +293,41 @@ impl<Message> Program<Message> for EmptyCanvas {
 pub fn empty_canvas<Message>() -> canvas::Canvas<EmptyCanvas, Message> {
 	canvas::Canvas::new(EmptyCanvas)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_viewport() {
+		let mut viewport = Viewport::default();
+		assert_eq!(viewport.scale(), UNIT);
+		assert!(!viewport.uses_f64(1000.0));
+
+		// 1000 pixels are 0.005 units
+		viewport.zoom_by(ZOOM_STEP.ln().recip() * 4000f32.ln());
+		assert!((viewport.zoom() - 4000.0).abs() < 1.0);
+		assert!(viewport.uses_f64(1000.0));
+
+		viewport.zoom_by(1000.0);
+		assert_eq!(viewport.zoom(), MAX_ZOOM);
+		viewport.zoom_by(-1000.0);
+		assert_eq!(viewport.zoom(), MIN_ZOOM);
+	}
+
+	#[test]
+	fn test_plot_state() {
+		let state = PlotState::default();
+		let f = crate::expression::parse("x ** 2 - 1").unwrap();
+		let markers = state.markers(&f, 500.0).to_vec();
+		// the roots and the minimum
+		assert_eq!(markers.len(), 3);
+		assert!(markers.contains(&(1.0, 0.0)));
+
+		// cached for the same function and frame
+		let cached = state.markers.borrow().as_ref().unwrap().points.as_ptr();
+		assert_eq!(state.markers(&f, 500.0).as_ptr(), cached);
+		let g = crate::expression::parse("x").unwrap();
+		assert_eq!(&*state.markers(&g, 500.0), [(0.0, 0.0)]);
+	}
+}
@@ -2,3 +2,19 @@
 pub const UNIT: f32 = 50.0;
 
 pub const LINE_WIDTH: f32 = 1.5;
+
+/// radius of the markers of roots and extrema
+pub const MARKER_RADIUS: f32 = 3.0;
+
+/// opacity of the shaded areas under curves
+pub const AREA_ALPHA: f32 = 0.3;
+
+/// viewport width in units below which functions are evaluated in `f64`
+pub const F64_VIEWPORT_WIDTH: f32 = 1e-2;
+
+/// zoom factor per scrolled line
+pub const ZOOM_STEP: f32 = 1.1;
+
+/// zoom range, where the maximum resolves the `f64` steps between samples
+pub const MIN_ZOOM: f32 = 0.1;
+pub const MAX_ZOOM: f32 = 1e6;
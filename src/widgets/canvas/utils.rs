@@ -1,36 +1,39 @@
-use super::{LINE_WIDTH, UNIT};
+use super::{Viewport, AREA_ALPHA, LINE_WIDTH, MARKER_RADIUS, UNIT};
 use crate::{
-	color::iced::{BLUE, GRAY, LIGHT_GRAY},
+	color::iced::{BLUE, GOLD, GRAY, LIGHT_GRAY},
 	consts::EPS,
-	expression::{Expression, Function},
+	expression::{CompiledExpr, Expression, Function, IntervalFunction, Scalar},
+	numerics::find_critical_points,
 };
 use iced::{
 	widget::canvas::{self, Stroke},
 	Color, Point,
 };
+use std::ops::Range;
 
 #[inline]
 #[must_use]
-pub fn cartesian_to_screen(p: &Point, center: &Point) -> Point {
-	let x = center.x + p.x * UNIT;
-	let y = center.y - p.y * UNIT;
+pub fn cartesian_to_screen(p: &Point, center: &Point, scale: f32) -> Point {
+	let x = center.x + p.x * scale;
+	let y = center.y - p.y * scale;
 	Point { x, y }
 }
 
 /// Draw background on the canvas frame.
-pub fn draw_background(frame: &mut canvas::Frame) {
+pub fn draw_background(frame: &mut canvas::Frame, viewport: &Viewport) {
 	let axis_stroke = Stroke::default()
 		.with_color(LIGHT_GRAY)
 		.with_width(LINE_WIDTH);
 
 	let center = frame.center();
+	let scale = viewport.scale();
 	frame.fill_rectangle(Point::ORIGIN, frame.size(), Color::BLACK);
-	let w = (frame.width() / UNIT / 2.0) as i32;
-	let h = (frame.height() / UNIT / 2.0) as i32;
+	let w = (frame.width() / scale / 2.0) as i32;
+	let h = (frame.height() / scale / 2.0) as i32;
 	for i in -w..=w {
 		for j in -h..=h {
-			let x = center.x + i as f32 * UNIT;
-			let y = center.y + j as f32 * UNIT;
+			let x = center.x + i as f32 * scale;
+			let y = center.y + j as f32 * scale;
 			frame.fill(&canvas::Path::circle(Point { x, y }, 1.0), GRAY);
 		}
 	}
@@ -62,26 +65,68 @@ pub fn draw_background(frame: &mut canvas::Frame) {
 	);
 }
 
+/// Evaluate the function in the scalar type `T` at `i * step` for each i in the range.
+fn sample<T: Scalar>(expr: &Expression, range: Range<i32>, step: f64) -> Vec<Option<f32>> {
+	let xs: Vec<T> = range.map(|i| T::from_f64(f64::from(i) * step)).collect();
+	let mut ys = vec![None; xs.len()];
+	CompiledExpr::new(expr).eval_many(&xs, &mut ys);
+
+	ys.into_iter()
+		.map(|y| y.map(|y| y.to_f64() as f32))
+		.collect()
+}
+
+/// Does the function have a pole or a jump in `[lo, hi]`, by bounding it with intervals.
+fn is_discontinuous(expr: &Expression, lo: f64, hi: f64) -> bool {
+	expr.eval_interval(lo, hi)
+		.is_ok_and(|y| y.is_split() || !y.is_bounded())
+}
+
 /// Draw the function on the canvas frame.
-pub fn draw_function(frame: &mut canvas::Frame, expr: &Expression) {
+pub fn draw_function(frame: &mut canvas::Frame, viewport: &Viewport, expr: &Expression) {
 	let line_stroke = Stroke::default().with_width(LINE_WIDTH).with_color(BLUE);
 
 	let center = frame.center();
-	// half width in epsilons
+	let scale = viewport.scale();
+	// half width in steps, which are epsilons when not zoomed
 	let w = (((frame.width() / UNIT / 2.0) as i32 + 1) as f32 / EPS) as i32;
+	let step = f64::from(EPS) / f64::from(viewport.zoom());
 	let mut point_groups = Vec::new();
 	let mut points = Vec::new();
 
-	for i in -w..w {
-		let x = i as f32 * EPS;
-		let Some(y) = expr.eval(x) else {
+	let ys = if viewport.uses_f64(frame.width()) {
+		sample::<f64>(expr, -w..w, step)
+	} else {
+		sample::<f32>(expr, -w..w, step)
+	};
+
+	// samples per pixel column, which is checked for poles as a whole before its segments
+	let column = (1.0 / UNIT / EPS).ceil() as i32;
+	let mut has_pole = false;
+
+	for (i, y) in (-w..w).zip(ys) {
+		let x = (f64::from(i) * step) as f32;
+		if (i + w) % column == 0 {
+			// the column includes the segment from the previous sample
+			let lo = f64::from(i - 1) * step;
+			has_pole = is_discontinuous(expr, lo, lo + f64::from(column) * step);
+		}
+		let Some(y) = y else {
 			if !points.is_empty() {
 				point_groups.push(points);
 				points = Vec::new();
 			}
 			continue;
 		};
-		let point = cartesian_to_screen(&Point { x, y }, &center);
+		// do not join the points across a pole
+		if has_pole
+			&& !points.is_empty()
+			&& is_discontinuous(expr, f64::from(i - 1) * step, f64::from(i) * step)
+		{
+			point_groups.push(points);
+			points = Vec::new();
+		}
+		let point = cartesian_to_screen(&Point { x, y }, &center, scale);
 		points.push(point);
 	}
 	if !points.is_empty() {
@@ -99,6 +144,102 @@ pub fn draw_function(frame: &mut canvas::Frame, expr: &Expression) {
 	}
 }
 
+/// Shade the signed area between the function and the x-axis for x in `[from, to]`, where the
+/// area below the axis is in another color.
+pub fn draw_area(
+	frame: &mut canvas::Frame,
+	viewport: &Viewport,
+	expr: &Expression,
+	from: f64,
+	to: f64,
+) {
+	let center = frame.center();
+	let scale = viewport.scale();
+	let w = f64::from(frame.width() / scale / 2.0);
+	let (lo, hi) = (from.min(to).max(-w), from.max(to).min(w));
+	if lo >= hi {
+		return;
+	}
+
+	// a sample per pixel column, clamped to the frame height
+	let h = frame.height() / scale;
+	let samples = ((hi - lo) * f64::from(scale)).ceil().max(1.0) as i32;
+	let step = (hi - lo) / f64::from(samples);
+	let points: Vec<_> = (0..=samples)
+		.map(|i| {
+			let x = lo + f64::from(i) * step;
+			let y = expr.eval(x).ok().filter(|y: &f64| y.is_finite());
+			(x as f32, y.map(|y| (y as f32).clamp(-h, h)))
+		})
+		.collect();
+
+	// the parts above and below the axis are filled separately, each from the axis
+	for (color, clamp) in [
+		(BLUE, (|y: f32| y.max(0.0)) as fn(f32) -> f32),
+		(GOLD, |y: f32| y.min(0.0)),
+	] {
+		let color = Color {
+			a: AREA_ALPHA,
+			..color
+		};
+		for run in points.split(|(_, y)| y.is_none()) {
+			let (Some(&(first, _)), Some(&(last, _))) = (run.first(), run.last()) else {
+				continue;
+			};
+			let path = canvas::Path::new(|p| {
+				p.move_to(cartesian_to_screen(
+					&Point { x: first, y: 0.0 },
+					&center,
+					scale,
+				));
+				for (x, y) in run.iter().filter_map(|&(x, y)| Some((x, clamp(y?)))) {
+					p.line_to(cartesian_to_screen(&Point { x, y }, &center, scale));
+				}
+				p.line_to(cartesian_to_screen(
+					&Point { x: last, y: 0.0 },
+					&center,
+					scale,
+				));
+				p.close();
+			});
+			frame.fill(&path, color);
+		}
+	}
+}
+
+/// Find the roots and the local extrema of the function in a frame of the width, as cartesian
+/// points.
+#[must_use]
+pub fn find_markers(expr: &Expression, viewport: &Viewport, width: f32) -> Vec<(f64, f64)> {
+	let w = f64::from(width / viewport.scale() / 2.0);
+	// a sample per pixel column
+	let samples = usize::try_from(width.ceil() as i64).unwrap_or(0);
+	let compiled = CompiledExpr::<f64>::new(expr);
+
+	let (roots, extrema) = find_critical_points(&|x| compiled.eval(x).ok(), -w, w, samples);
+	roots
+		.into_iter()
+		.map(|x| (x, 0.0))
+		.chain(extrema.into_iter().map(|e| (e.x, e.y)))
+		.collect()
+}
+
+/// Mark the points, e.g., the roots and the local extrema, on the canvas frame.
+pub fn draw_markers(frame: &mut canvas::Frame, viewport: &Viewport, points: &[(f64, f64)]) {
+	let center = frame.center();
+	for &(x, y) in points {
+		let point = cartesian_to_screen(
+			&Point {
+				x: x as f32,
+				y: y as f32,
+			},
+			&center,
+			viewport.scale(),
+		);
+		frame.fill(&canvas::Path::circle(point, MARKER_RADIUS), GOLD);
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -107,7 +248,16 @@ mod tests {
 	fn test_cartesian_to_screen() {
 		let center = Point { x: 100.0, y: 100.0 };
 		let point = Point { x: 0.0, y: 0.0 };
-		let screen_point = cartesian_to_screen(&point, &center);
+		let screen_point = cartesian_to_screen(&point, &center, UNIT);
 		assert_eq!(screen_point, Point { x: 100.0, y: 100.0 });
+		let point = Point { x: 1.0, y: 1.0 };
+		let screen_point = cartesian_to_screen(&point, &center, 2.0 * UNIT);
+		assert_eq!(
+			screen_point,
+			Point {
+				x: 100.0 + 2.0 * UNIT,
+				y: 100.0 - 2.0 * UNIT
+			}
+		);
 	}
 }
@@ -10,6 +10,6 @@ fn simple_expression() {
 
 #[test]
 fn invalid_expression() {
-	assert!(parse("x x").is_err());
+	assert!(parse("x +").is_err());
 	assert!(parse("-").is_err());
 }
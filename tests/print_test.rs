@@ -0,0 +1,112 @@
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use rsap::{
+	binary_expr, constant,
+	expression::{func, parse, Expression, Unary},
+	factorial, func, log, neg, not, num, paren, var,
+};
+
+const OPERATORS: [&str; 13] = [
+	"+", "-", "*", "/", "**", "<", "<=", "==", "!=", ">=", ">", "and", "or",
+];
+
+fn random_expr(rng: &mut SmallRng, depth: u32) -> Expression {
+	if depth == 0 || rng.random_bool(0.2) {
+		return match rng.random_range(0..4) {
+			0 => num!(f32::from(rng.random_range(0u8..40)) / 4.0).into(),
+			1 => var!().into(),
+			2 => constant!(if rng.random_bool(0.5) { "e" } else { "pi" })
+				.unwrap()
+				.into(),
+			_ => factorial!(num!(f32::from(rng.random_range(0u8..6)))).into(),
+		};
+	}
+
+	let lhs = random_expr(rng, depth - 1);
+	match rng.random_range(0..12) {
+		0 => neg!(lhs).into(),
+		10 => not!(lhs).into(),
+		11 => factorial!(lhs).into(),
+		1 => paren!(lhs).into(),
+		2 => {
+			let name = func::FUNCTION_NAMES[rng.random_range(0..func::FUNCTION_NAMES.len())];
+			func!(name, lhs).unwrap().into()
+		}
+		3 => log!(lhs, random_expr(rng, depth - 1)).into(),
+		_ => {
+			let op = OPERATORS[rng.random_range(0..OPERATORS.len())];
+			binary_expr!(op, lhs, random_expr(rng, depth - 1))
+				.unwrap()
+				.into()
+		}
+	}
+}
+
+/// S-expression of the tree, ignoring parenthesis.
+fn shape(expr: &Expression) -> String {
+	match expr {
+		Expression::Unary(e) => match e.as_ref() {
+			Unary::Neg(e) => format!("(- {})", shape(e)),
+			Unary::Not(e) => format!("(not {})", shape(e)),
+		},
+		Expression::Parenthesis(e) => shape(e.inner()),
+		Expression::Binary(e) => {
+			let (lhs, rhs) = e.operands();
+			format!("({} {} {})", e.operator(), shape(lhs), shape(rhs))
+		}
+		Expression::Factorial(e) => format!("(! {})", shape(e.arg())),
+		Expression::Func(e) => format!("({} {})", e.name(), shape(e.arg())),
+		Expression::Func2(e) => {
+			let (lhs, rhs) = e.args();
+			format!("({} {} {})", e.name(), shape(lhs), shape(rhs))
+		}
+		_ => expr.to_string(),
+	}
+}
+
+#[test]
+fn minimal_parenthesis() {
+	let f = binary_expr!("*", add_x_1(), var!()).unwrap();
+	assert_eq!(f.to_string(), "(x + 1) * x");
+
+	let f = binary_expr!("-", var!(), add_x_1()).unwrap();
+	assert_eq!(f.to_string(), "x - (x + 1)");
+
+	let f = binary_expr!("+", add_x_1(), var!()).unwrap();
+	assert_eq!(f.to_string(), "x + 1 + x");
+
+	let pow_x_2 = || binary_expr!("**", var!(), num!(2.0)).unwrap();
+	let f = binary_expr!("**", pow_x_2(), num!(3.0)).unwrap();
+	assert_eq!(f.to_string(), "(x ** 2) ** 3");
+
+	let f = binary_expr!("**", num!(3.0), pow_x_2()).unwrap();
+	assert_eq!(f.to_string(), "3 ** x ** 2");
+
+	let f = binary_expr!("**", neg!(var!()), num!(2.0)).unwrap();
+	assert_eq!(f.to_string(), "(-x) ** 2");
+
+	let f = binary_expr!("**", var!(), neg!(num!(2.0))).unwrap();
+	assert_eq!(f.to_string(), "x ** -2");
+
+	let f = neg!(pow_x_2());
+	assert_eq!(f.to_string(), "-x ** 2");
+
+	let f = neg!(add_x_1());
+	assert_eq!(f.to_string(), "-(x + 1)");
+}
+
+fn add_x_1() -> Expression {
+	binary_expr!("+", var!(), num!(1.0)).unwrap().into()
+}
+
+#[test]
+fn round_trip() {
+	let mut rng = SmallRng::seed_from_u64(0);
+
+	for _ in 0..2000 {
+		let expr = random_expr(&mut rng, 6);
+		let printed = expr.to_string();
+		let parsed = parse(&printed).unwrap();
+
+		assert_eq!(shape(&parsed), shape(&expr), "{printed}");
+	}
+}
@@ -0,0 +1,197 @@
+#![cfg(feature = "serde")]
+
+use rsap::{
+	expression::{
+		parse, parse_program, parse_with, Binary, Conditional, Constant, CustomFunction,
+		Derivative, Expression, Func, Func2, FunctionRegistry, Integral, Parser, Unary,
+	},
+	factorial, num, paren, var,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+
+/// Serialize to JSON and back, keeping the exact tree.
+fn assert_round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: &T) {
+	let json = serde_json::to_string(value).unwrap();
+	let back: T = serde_json::from_str(&json).unwrap();
+	assert_eq!(&back, value, "{json}");
+}
+
+fn x() -> Expression {
+	var!().into()
+}
+
+fn one() -> Expression {
+	num!(1.0).into()
+}
+
+#[test]
+fn round_trip_expression() {
+	let program = parse_program("f(t) = t ** 2 + 1; f(x - 1)").unwrap();
+	for expr in [
+		Expression::from(num!(-1.5)),
+		factorial!(x()).into(),
+		Constant::PI.into(),
+		var!("t").into(),
+		Unary::Neg(x()).into(),
+		paren!(x()).into(),
+		Binary::Add((x(), one())).into(),
+		Func::Sin(x()).into(),
+		Func2::Log((x(), one())).into(),
+		// the call keeps its definition
+		Expression::from(program),
+		Conditional::new(x(), one(), Some(x())).into(),
+		Integral::new(x(), one(), x()).into(),
+	] {
+		assert_round_trip(&expr);
+	}
+}
+
+#[test]
+fn round_trip_constant() {
+	for constant in [Constant::E, Constant::PI, Constant::I] {
+		assert_round_trip(&constant);
+	}
+}
+
+#[test]
+fn round_trip_unary() {
+	for unary in [Unary::Neg(x()), Unary::Not(x())] {
+		assert_round_trip(&unary);
+	}
+}
+
+#[test]
+fn round_trip_binary() {
+	let operands = (x(), one());
+	for binary in [
+		Binary::Add(operands.clone()),
+		Binary::Sub(operands.clone()),
+		Binary::Mul(operands.clone()),
+		Binary::Div(operands.clone()),
+		Binary::Pow(operands.clone()),
+		Binary::Lt(operands.clone()),
+		Binary::Le(operands.clone()),
+		Binary::Eq(operands.clone()),
+		Binary::Ne(operands.clone()),
+		Binary::Ge(operands.clone()),
+		Binary::Gt(operands.clone()),
+		Binary::And(operands.clone()),
+		Binary::Or(operands),
+	] {
+		assert_round_trip(&binary);
+	}
+}
+
+#[test]
+fn round_trip_func() {
+	for func in [
+		Func::Sin(x()),
+		Func::Cos(x()),
+		Func::Tan(x()),
+		Func::Cot(x()),
+		Func::Sec(x()),
+		Func::Csc(x()),
+		Func::Arcsin(x()),
+		Func::Arccos(x()),
+		Func::Arctan(x()),
+		Func::Arccot(x()),
+		Func::Ln(x()),
+		Func::Sqrt(x()),
+		Func::Abs(x()),
+		Func::Exp(x()),
+		Func::Floor(x()),
+		Func::Ceil(x()),
+		Func::Round(x()),
+		Func::Sign(x()),
+		Func::Sinh(x()),
+		Func::Cosh(x()),
+		Func::Tanh(x()),
+		Func::Arcsinh(x()),
+		Func::Arccosh(x()),
+		Func::Arctanh(x()),
+		Func::Log10(x()),
+		Func::Log2(x()),
+	] {
+		assert_round_trip(&func);
+	}
+}
+
+#[test]
+fn round_trip_func2() {
+	let args = (one(), x());
+	for func2 in [
+		Func2::Log(args.clone()),
+		Func2::Min(args.clone()),
+		Func2::Max(args.clone()),
+		Func2::Atan2(args.clone()),
+		Func2::Mod(args.clone()),
+		Func2::Hypot(args.clone()),
+		Func2::Root(args.clone()),
+		Func2::Polygamma(args),
+	] {
+		assert_round_trip(&func2);
+	}
+}
+
+#[test]
+fn round_trip_conditional() {
+	for conditional in [
+		Conditional::new(x(), one(), Some(x())),
+		Conditional::new::<_, _, Expression>(x(), one(), None),
+	] {
+		assert_round_trip(&conditional);
+	}
+}
+
+#[test]
+fn round_trip_parsed() {
+	for expr in [
+		"1.5 + x! + pi * e",
+		"-x ** 2 + not x",
+		"sin(x) + sqrt(abs(x)) + log(2, x) + polygamma(1, x)",
+		"(x + 1) * (x - 1) / (x ** 2)",
+		"x < 1 or x <= 2 and x == 3 or x != 4 and x >= 5 or x > 6",
+		"if(x < 0, -x, x ** 2) + if(x > 1, ln(x))",
+		"integral(sin(x), 0, x)",
+	] {
+		assert_round_trip(&parse(expr).unwrap());
+	}
+	assert_round_trip(&parse_with("x * t", &["x", "t"]).unwrap());
+
+	// the derivative of factorials calls polygamma
+	assert_round_trip(&parse("x!").unwrap().derivative());
+}
+
+#[test]
+fn round_trip_json() {
+	let f = parse("2 * x").unwrap();
+	assert_eq!(
+		serde_json::to_string(&f).unwrap(),
+		r#"{"Binary":{"Mul":[{"Number":2.0},{"Variable":"x"}]}}"#
+	);
+}
+
+#[test]
+fn round_trip_custom() {
+	let registry = FunctionRegistry::new().with(CustomFunction::new(
+		"clamp01",
+		1,
+		|args| args[0].clamp(0.0, 1.0),
+		|_| vec![],
+	));
+	let f = Parser::new("clamp01(x) + 1")
+		.with_registry(&registry)
+		.parse()
+		.unwrap();
+	let json = serde_json::to_string(&f).unwrap();
+
+	let back = registry
+		.deserialize_expression(&mut serde_json::Deserializer::from_str(&json))
+		.unwrap();
+	assert_eq!(back, f);
+
+	// the function is not registered
+	let err = serde_json::from_str::<Expression>(&json).unwrap_err();
+	assert!(err.to_string().contains("unknown function `clamp01`"));
+}